@@ -1,26 +1,64 @@
+use derive_db_execute::Execute;
 use diesel::prelude::*;
-use jirs_data::{Comment, CommentId, IssueId, UserId};
+use jirs_data::{Comment, CommentId, IssueId, ProjectId, UserId};
 
-use crate::{db_create, db_delete, db_load, db_update};
+use crate::{db_delete, db_find, db_load, db_update};
 
 db_load! {
     LoadIssueComments,
-    msg => comments => comments.distinct_on(id).filter(issue_id.eq(msg.issue_id)),
+    msg => comments => comments
+        .distinct_on(id)
+        .filter(issue_id.eq(msg.issue_id))
+        .filter(issue_id.eq_any(project_issue_ids(msg.project_id))),
     Comment,
+    project_id => ProjectId,
     issue_id => IssueId
 }
 
-db_create! {
-    CreateComment,
-    msg => comments => diesel::insert_into(comments).values((
-        body.eq(msg.body),
-        user_id.eq(msg.user_id),
-        issue_id.eq(msg.issue_id),
-    )),
+db_find! {
+    FindComment,
+    msg => comments => comments
+        .filter(issue_id.eq_any(project_issue_ids(msg.project_id)))
+        .find(msg.comment_id),
     Comment,
-    issue_id => IssueId,
-    user_id => UserId,
-    body => String
+    project_id => ProjectId,
+    comment_id => CommentId
+}
+
+/// Create comment only if commented issue belongs to given project
+#[derive(Execute)]
+#[db_exec(result = "Comment", schema = "comments")]
+pub struct CreateComment {
+    pub project_id: ProjectId,
+    pub issue_id: IssueId,
+    pub user_id: UserId,
+    pub body: String,
+}
+
+impl CreateComment {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Comment, crate::DatabaseError> {
+        use crate::schema::comments::dsl::*;
+        let msg = self;
+
+        crate::issues::FindProjectIssue {
+            project_id: msg.project_id,
+            issue_id: msg.issue_id,
+        }
+        .execute(conn)?;
+        crate::q!(diesel::insert_into(comments).values((
+            body.eq(msg.body),
+            user_id.eq(msg.user_id),
+            issue_id.eq(msg.issue_id),
+        )))
+        .get_result(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Create,
+                crate::ResourceKind::Comment,
+            )
+        })
+    }
 }
 
 db_update! {
@@ -28,10 +66,12 @@ db_update! {
     msg => comments => diesel::update(
             comments
                 .filter(user_id.eq(msg.user_id))
+                .filter(issue_id.eq_any(project_issue_ids(msg.project_id)))
                 .find(msg.comment_id),
         )
         .set(body.eq(msg.body)),
     Comment,
+    project_id => ProjectId,
     comment_id => CommentId,
     user_id => UserId,
     body => String
@@ -42,9 +82,92 @@ db_delete! {
     msg => comments => diesel::delete(
             comments
                 .filter(user_id.eq(msg.user_id))
+                .filter(issue_id.eq_any(project_issue_ids(msg.project_id)))
                 .find(msg.comment_id),
         ),
     Comment,
+    project_id => ProjectId,
     comment_id => CommentId,
     user_id => UserId
 }
+
+fn project_issue_ids(
+    project_id: ProjectId,
+) -> crate::schema::issues::BoxedQuery<'static, diesel::pg::Pg, diesel::sql_types::Integer> {
+    use crate::schema::issues::dsl as issues;
+    issues::issues
+        .select(issues::id)
+        .filter(issues::project_id.eq(project_id))
+        .into_boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+    use crate::issues::tests::project_issue;
+
+    #[test]
+    fn reject_cross_project_comment_access() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (foo, foo_user, _) = project_issue(conn, "foo");
+        let (bar, bar_user, bar_issue) = project_issue(conn, "bar");
+        let comment = CreateComment {
+            project_id: bar.id,
+            issue_id: bar_issue.id,
+            user_id: bar_user.id,
+            body: "Hello".to_string(),
+        }
+        .execute(conn)
+        .unwrap();
+
+        let created = CreateComment {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+            user_id: foo_user.id,
+            body: "Intruder".to_string(),
+        }
+        .execute(conn);
+        let loaded = LoadIssueComments {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+        }
+        .execute(conn)
+        .unwrap();
+        let updated = UpdateComment {
+            project_id: foo.id,
+            comment_id: comment.id,
+            user_id: bar_user.id,
+            body: "Changed".to_string(),
+        }
+        .execute(conn);
+        let deleted = DeleteComment {
+            project_id: foo.id,
+            comment_id: comment.id,
+            user_id: bar_user.id,
+        }
+        .execute(conn)
+        .unwrap_or_default();
+        let own = LoadIssueComments {
+            project_id: bar.id,
+            issue_id: bar_issue.id,
+        }
+        .execute(conn)
+        .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(created.is_err());
+        assert!(loaded.is_empty());
+        assert!(updated.is_err());
+        assert_eq!(deleted, 0);
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].body, "Hello".to_string());
+    }
+}
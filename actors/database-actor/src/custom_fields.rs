@@ -0,0 +1,71 @@
+use diesel::prelude::*;
+use jirs_data::{CustomField, CustomFieldId, CustomFieldType, NameString, Position, ProjectId};
+
+use crate::{db_create, db_delete, db_find, db_load, db_update};
+
+db_find! {
+    FindCustomField,
+    msg => custom_fields => custom_fields
+            .filter(project_id.eq(msg.project_id))
+            .find(msg.custom_field_id),
+    CustomField,
+    project_id => ProjectId,
+    custom_field_id => CustomFieldId
+}
+
+db_load! {
+    LoadCustomFields,
+    msg => custom_fields => custom_fields
+            .distinct_on((position, id))
+            .filter(project_id.eq(msg.project_id))
+            .order((position.asc(), id.asc())),
+    CustomField,
+    project_id => ProjectId
+}
+
+db_create! {
+    CreateCustomField,
+    msg => custom_fields => diesel::insert_into(custom_fields).values((
+            project_id.eq(msg.project_id),
+            name.eq(msg.name),
+            field_type.eq(msg.field_type),
+            options.eq(msg.options),
+            position.eq(msg.position),
+    )),
+    CustomField,
+    project_id => ProjectId,
+    name => NameString,
+    field_type => CustomFieldType,
+    options => Vec<String>,
+    position => Position
+}
+
+db_update! {
+    UpdateCustomField,
+    msg => custom_fields => diesel::update(
+        custom_fields
+            .filter(project_id.eq(msg.project_id))
+            .find(msg.custom_field_id),
+    ).set((
+        name.eq(msg.name),
+        options.eq(msg.options),
+        updated_at.eq(chrono::Utc::now().naive_utc()),
+    )),
+    CustomField,
+    project_id => ProjectId,
+    custom_field_id => CustomFieldId,
+    name => NameString,
+    options => Vec<String>
+}
+
+db_delete! {
+    DeleteCustomField,
+    msg => custom_fields => diesel::delete(
+            custom_fields
+                .filter(project_id.eq(msg.project_id))
+                .find(msg.custom_field_id),
+        ),
+    CustomField,
+    project_id => ProjectId,
+    custom_field_id => CustomFieldId
+}
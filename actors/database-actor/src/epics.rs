@@ -5,8 +5,13 @@ use jirs_data::{DescriptionString, EndsAt, Epic, EpicId, ProjectId, StartsAt};
 use crate::{db_create, db_delete, db_load, db_update};
 
 #[derive(Execute)]
-#[db_exec(schema = "epics", result = "Epic", find = "epics.find(msg.epic_id)")]
+#[db_exec(
+    schema = "epics",
+    result = "Epic",
+    find = "epics.filter(project_id.eq(msg.project_id)).find(msg.epic_id)"
+)]
 pub struct FindEpic {
+    pub project_id: ProjectId,
     pub epic_id: EpicId,
 }
 
@@ -76,9 +81,73 @@ db_update! {
 db_delete! {
     DeleteEpic,
     msg => epics => diesel::delete(
-        epics.filter(user_id.eq(msg.user_id)).find(msg.epic_id)
+        epics
+            .filter(user_id.eq(msg.user_id))
+            .filter(project_id.eq(msg.project_id))
+            .find(msg.epic_id)
     ),
     Epic,
     user_id => i32,
+    project_id => i32,
     epic_id => i32
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+    use crate::issues::tests::project_issue;
+
+    #[test]
+    fn reject_cross_project_epic_access() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (foo, _, _) = project_issue(conn, "foo");
+        let (bar, bar_user, _) = project_issue(conn, "bar");
+        let epic = CreateEpic {
+            user_id: bar_user.id,
+            project_id: bar.id,
+            name: "Epic".to_string(),
+            description: None,
+            description_html: None,
+        }
+        .execute(conn)
+        .unwrap();
+
+        let found = FindEpic {
+            project_id: foo.id,
+            epic_id: epic.id,
+        }
+        .execute(conn);
+        let renamed = UpdateEpicName {
+            epic_id: epic.id,
+            project_id: foo.id,
+            name: "Renamed".to_string(),
+        }
+        .execute(conn);
+        let deleted = DeleteEpic {
+            user_id: bar_user.id,
+            project_id: foo.id,
+            epic_id: epic.id,
+        }
+        .execute(conn)
+        .unwrap_or_default();
+        let own = FindEpic {
+            project_id: bar.id,
+            epic_id: epic.id,
+        }
+        .execute(conn);
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(found.is_err());
+        assert!(renamed.is_err());
+        assert_eq!(deleted, 0);
+        assert_eq!(own.unwrap().name, "Epic".to_string());
+    }
+}
@@ -11,17 +11,28 @@ pub enum OperationError {
 
 #[derive(Debug)]
 pub enum ResourceKind {
+    CustomField,
     Epic,
     Invitation,
     IssueAssignee,
+    IssueCustomValue,
+    IssueHistory,
+    IssueLabel,
+    IssueLink,
     IssueStatus,
+    IssueWatcher,
     Issue,
+    Label,
     Message,
     Project,
+    Sprint,
     Token,
+    UserCredential,
     UserProject,
     UserSetting,
     User,
+    WorkflowTransition,
+    Worklog,
     Comment,
 }
 
@@ -33,6 +44,7 @@ pub enum InvitationError {
 #[derive(Debug)]
 pub enum TokenError {
     FailedToDisable,
+    FailedToRefresh,
 }
 
 #[derive(Debug)]
@@ -40,6 +52,9 @@ pub enum UserError {
     TakenPair(UsernameString, EmailString),
     InvalidPair(UsernameString, EmailString),
     UpdateProfile,
+    InvalidCredentials,
+    TotpRequired,
+    InvalidTotpCode,
 }
 
 #[derive(Debug)]
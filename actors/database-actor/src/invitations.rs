@@ -50,9 +50,12 @@ db_create! {
 
 db_delete! {
     DeleteInvitation,
-    msg => invitations => diesel::delete(invitations).filter(id.eq(msg.id)),
+    msg => invitations => diesel::delete(invitations)
+        .filter(id.eq(msg.id))
+        .filter(project_id.eq(msg.project_id)),
     Invitation,
-    id => InvitationId
+    id => InvitationId,
+    project_id => ProjectId
 }
 
 db_update! {
@@ -70,23 +73,42 @@ db_update! {
 
 pub struct RevokeInvitation {
     pub id: InvitationId,
+    pub project_id: ProjectId,
+}
+
+impl RevokeInvitation {
+    pub fn execute(self, conn: &DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::invitations::dsl::*;
+        let msg = self;
+        crate::q!(diesel::update(invitations)
+            .set((
+                state.eq(InvitationState::Revoked),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .filter(id.eq(msg.id))
+            .filter(project_id.eq(msg.project_id)))
+        .execute(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Update,
+                crate::ResourceKind::Invitation,
+            )
+        })
+    }
 }
 
 impl Message for RevokeInvitation {
-    type Result = Result<(), crate::DatabaseError>;
+    type Result = Result<usize, crate::DatabaseError>;
 }
 
 impl Handler<RevokeInvitation> for DbExecutor {
-    type Result = Result<(), crate::DatabaseError>;
+    type Result = Result<usize, crate::DatabaseError>;
 
     fn handle(&mut self, msg: RevokeInvitation, _ctx: &mut Self::Context) -> Self::Result {
         let conn = db_pool!(self);
-        UpdateInvitationState {
-            id: msg.id,
-            state: InvitationState::Revoked,
-        }
-        .execute(conn)?;
-        Ok(())
+
+        msg.execute(conn)
     }
 }
 
@@ -126,6 +148,7 @@ impl AcceptInvitation {
                     email: invitation.email.clone(),
                     project_id: Some(invitation.project_id),
                     role: UserRole::User,
+                    password: None,
                 }
                 .execute(conn)
             } {
@@ -139,7 +162,7 @@ impl AcceptInvitation {
                 email: invitation.email.clone(),
             }
             .execute(conn)?;
-            CreateBindToken { user_id: user.id }.execute(conn)?;
+            let token = CreateBindToken { user_id: user.id }.execute(conn)?;
 
             crate::user_projects::CreateUserProject {
                 user_id: user.id,
@@ -150,7 +173,11 @@ impl AcceptInvitation {
             }
             .execute(conn)?;
 
-            crate::tokens::FindUserId { user_id: user.id }.execute(conn)
+            // token is given to invited user directly so it's bound at once
+            match token.bind_token {
+                Some(bind_token) => crate::tokens::UseBindToken { token: bind_token }.execute(conn),
+                None => Ok(token),
+            }
         })
     }
 }
@@ -0,0 +1,101 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{CustomFieldId, CustomValue, IssueCustomValue, IssueId, ProjectId};
+
+use crate::db_load;
+use crate::schema::issues::dsl as issues_dsl;
+
+db_load! {
+    LoadIssueCustomValues,
+    msg => issue_custom_values => issue_custom_values
+            .distinct_on(id)
+            .filter(issue_id.eq(msg.issue_id))
+            .order(id.asc()),
+    IssueCustomValue,
+    issue_id => IssueId
+}
+
+db_load! {
+    LoadProjectIssueCustomValues,
+    msg => issue_custom_values => issue_custom_values
+            .distinct_on(id)
+            .filter(issue_id.eq_any(
+                issues_dsl::issues
+                    .select(issues_dsl::id)
+                    .filter(issues_dsl::project_id.eq(msg.project_id)),
+            ))
+            .order(id.asc()),
+    IssueCustomValue,
+    project_id => ProjectId
+}
+
+/// Replace issue value of custom field, `None` removes value.
+/// Value must be checked against field definition before.
+#[derive(Execute)]
+#[db_exec(result = "Vec<IssueCustomValue>", schema = "issue_custom_values")]
+pub struct SetIssueCustomValue {
+    pub issue_id: IssueId,
+    pub custom_field_id: CustomFieldId,
+    pub value: Option<CustomValue>,
+}
+
+impl SetIssueCustomValue {
+    pub fn execute(
+        self,
+        conn: &crate::DbPooledConn,
+    ) -> Result<Vec<IssueCustomValue>, crate::DatabaseError> {
+        use crate::schema::issue_custom_values::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            crate::q!(diesel::delete(
+                issue_custom_values
+                    .filter(issue_id.eq(msg.issue_id))
+                    .filter(custom_field_id.eq(msg.custom_field_id))
+            ))
+            .execute(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Delete,
+                    crate::ResourceKind::IssueCustomValue,
+                )
+            })?;
+
+            if let Some(value) = msg.value {
+                let (mut text, mut number, mut date, mut selected, mut user) =
+                    (None, None, None, vec![], None);
+                match value {
+                    CustomValue::Text(v) => text = Some(v),
+                    CustomValue::Number(v) => number = Some(v),
+                    CustomValue::Date(v) => date = Some(v),
+                    CustomValue::SingleSelect(v) => selected = vec![v],
+                    CustomValue::MultiSelect(v) => selected = v,
+                    CustomValue::User(v) => user = Some(v),
+                };
+                crate::q!(diesel::insert_into(issue_custom_values).values((
+                    issue_id.eq(msg.issue_id),
+                    custom_field_id.eq(msg.custom_field_id),
+                    text_value.eq(text),
+                    number_value.eq(number),
+                    date_value.eq(date),
+                    option_values.eq(selected),
+                    user_id.eq(user),
+                )))
+                .execute(conn)
+                .map_err(|e| {
+                    log::error!("{:?}", e);
+                    crate::DatabaseError::GenericFailure(
+                        crate::OperationError::Create,
+                        crate::ResourceKind::IssueCustomValue,
+                    )
+                })?;
+            }
+
+            LoadIssueCustomValues {
+                issue_id: msg.issue_id,
+            }
+            .execute(conn)
+        })
+    }
+}
@@ -0,0 +1,141 @@
+use diesel::prelude::*;
+use jirs_data::{IssueHistory, IssueHistoryField, IssueId, UserId};
+
+use crate::db_load;
+use crate::models::{CreateIssueHistoryForm, Issue};
+
+db_load! {
+    LoadIssueHistory,
+    msg => issue_history => issue_history
+            .filter(issue_id.eq(msg.issue_id))
+            .order((created_at.desc(), id.desc())),
+    IssueHistory,
+    issue_id => IssueId
+}
+
+pub type HistoryChange = (IssueHistoryField, Option<String>, Option<String>);
+
+pub struct CreateIssueHistory {
+    pub issue_id: IssueId,
+    pub user_id: UserId,
+    pub changes: Vec<HistoryChange>,
+}
+
+impl CreateIssueHistory {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::issue_history::dsl::*;
+        let CreateIssueHistory {
+            issue_id: i_id,
+            user_id: u_id,
+            changes,
+        } = self;
+        if changes.is_empty() {
+            return Ok(0);
+        }
+        let rows = changes
+            .into_iter()
+            .map(|(f, old, new)| CreateIssueHistoryForm {
+                issue_id: i_id,
+                user_id: u_id,
+                field: f,
+                old_value: old,
+                new_value: new,
+            })
+            .collect::<Vec<CreateIssueHistoryForm>>();
+        crate::q!(diesel::insert_into(issue_history).values(rows))
+            .execute(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Create,
+                    crate::ResourceKind::IssueHistory,
+                )
+            })
+    }
+}
+
+/// Compare issue before and after update and list every tracked field which
+/// was changed. Enum values are stored by name, references by id.
+pub fn issue_changes(old: &Issue, new: &Issue) -> Vec<HistoryChange> {
+    let mut changes = vec![];
+    let mut push = |field: IssueHistoryField, a: Option<String>, b: Option<String>| {
+        if a != b {
+            changes.push((field, a, b));
+        }
+    };
+    push(
+        IssueHistoryField::Title,
+        Some(old.title.clone()),
+        Some(new.title.clone()),
+    );
+    push(
+        IssueHistoryField::Type,
+        Some(format!("{:?}", old.issue_type)),
+        Some(format!("{:?}", new.issue_type)),
+    );
+    push(
+        IssueHistoryField::Status,
+        Some(old.issue_status_id.to_string()),
+        Some(new.issue_status_id.to_string()),
+    );
+    push(
+        IssueHistoryField::Priority,
+        Some(format!("{:?}", old.priority)),
+        Some(format!("{:?}", new.priority)),
+    );
+    push(
+        IssueHistoryField::Description,
+        old.description_text.clone(),
+        new.description_text.clone(),
+    );
+    push(
+        IssueHistoryField::Estimate,
+        old.estimate.map(|n| n.to_string()),
+        new.estimate.map(|n| n.to_string()),
+    );
+    push(
+        IssueHistoryField::TimeSpent,
+        old.time_spent.map(|n| n.to_string()),
+        new.time_spent.map(|n| n.to_string()),
+    );
+    push(
+        IssueHistoryField::TimeRemaining,
+        old.time_remaining.map(|n| n.to_string()),
+        new.time_remaining.map(|n| n.to_string()),
+    );
+    push(
+        IssueHistoryField::Reporter,
+        Some(old.reporter_id.to_string()),
+        Some(new.reporter_id.to_string()),
+    );
+    push(
+        IssueHistoryField::Epic,
+        old.epic_id.map(|n| n.to_string()),
+        new.epic_id.map(|n| n.to_string()),
+    );
+    push(
+        IssueHistoryField::Sprint,
+        old.sprint_id.map(|n| n.to_string()),
+        new.sprint_id.map(|n| n.to_string()),
+    );
+    changes
+}
+
+/// Assignees are stored as sorted, comma separated list of user ids.
+pub fn assignees_change(old: &[UserId], new: &[UserId]) -> Option<HistoryChange> {
+    let join = |ids: &[UserId]| {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let (old, new) = (join(old), join(new));
+    if old == new {
+        None
+    } else {
+        Some((IssueHistoryField::Assignees, Some(old), Some(new)))
+    }
+}
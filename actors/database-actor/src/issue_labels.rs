@@ -0,0 +1,65 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{IssueId, LabelId};
+
+use crate::db_load_field;
+
+db_load_field! {
+    LoadIssueLabelIds,
+    LabelId,
+    msg => issue_labels => issue_labels
+                .select(label_id)
+                .filter(issue_id.eq(msg.issue_id))
+                .order(label_id.asc()),
+    IssueLabel,
+    issue_id => IssueId
+}
+
+/// Replace all issue labels with given list
+#[derive(Execute)]
+#[db_exec(result = "Vec<LabelId>", schema = "issue_labels")]
+pub struct SetIssueLabels {
+    pub issue_id: IssueId,
+    pub label_ids: Vec<LabelId>,
+}
+
+impl SetIssueLabels {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<Vec<LabelId>, crate::DatabaseError> {
+        use crate::schema::issue_labels::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            crate::q!(diesel::delete(
+                issue_labels.filter(issue_id.eq(msg.issue_id))
+            ))
+            .execute(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Delete,
+                    crate::ResourceKind::IssueLabel,
+                )
+            })?;
+            let rows: Vec<_> = msg
+                .label_ids
+                .iter()
+                .map(|l_id| (issue_id.eq(msg.issue_id), label_id.eq(*l_id)))
+                .collect();
+            if !rows.is_empty() {
+                crate::q!(diesel::insert_into(issue_labels).values(rows))
+                    .execute(conn)
+                    .map_err(|e| {
+                        log::error!("{:?}", e);
+                        crate::DatabaseError::GenericFailure(
+                            crate::OperationError::Create,
+                            crate::ResourceKind::IssueLabel,
+                        )
+                    })?;
+            }
+            LoadIssueLabelIds {
+                issue_id: msg.issue_id,
+            }
+            .execute(conn)
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use diesel::prelude::*;
+use jirs_data::{IssueId, IssueLink, IssueLinkId, IssueLinkType, ProjectId};
+
+use crate::schema::issues::dsl as issues_dsl;
+use crate::{db_create, db_delete, db_load};
+
+db_load! {
+    LoadIssueLinks,
+    msg => issue_links => issue_links
+            .distinct_on(id)
+            .filter(source_id.eq(msg.issue_id).or(target_id.eq(msg.issue_id)))
+            .order(id.asc()),
+    IssueLink,
+    issue_id => IssueId
+}
+
+db_load! {
+    LoadProjectIssueLinks,
+    msg => issue_links => issue_links
+            .distinct_on(id)
+            .filter(source_id.eq_any(
+                issues_dsl::issues
+                    .select(issues_dsl::id)
+                    .filter(issues_dsl::project_id.eq(msg.project_id)),
+            ))
+            .order(id.asc()),
+    IssueLink,
+    project_id => ProjectId
+}
+
+db_create! {
+    CreateIssueLink,
+    msg => issue_links => diesel::insert_into(issue_links).values((
+            link_type.eq(msg.link_type),
+            source_id.eq(msg.source_id),
+            target_id.eq(msg.target_id),
+    )),
+    IssueLink,
+    link_type => IssueLinkType,
+    source_id => IssueId,
+    target_id => IssueId
+}
+
+db_delete! {
+    DeleteIssueLink,
+    msg => issue_links => diesel::delete(
+            issue_links
+                .filter(source_id.eq_any(
+                    issues_dsl::issues
+                        .select(issues_dsl::id)
+                        .filter(issues_dsl::project_id.eq(msg.project_id)),
+                ))
+                .find(msg.link_id),
+        ),
+    IssueLink,
+    project_id => ProjectId,
+    link_id => IssueLinkId
+}
@@ -1,3 +1,4 @@
+use derive_db_execute::Execute;
 use diesel::prelude::*;
 use jirs_data::{IssueStatus, IssueStatusId, Position, ProjectId, TitleString};
 
@@ -52,3 +53,45 @@ db_update! {
     position => Position,
     name => TitleString
 }
+
+db_update! {
+    UpdateIssueStatusWipLimit,
+    msg => issue_statuses => diesel::update(issue_statuses)
+        .set((
+            wip_limit.eq(msg.wip_limit),
+            wip_strict.eq(msg.wip_strict),
+            updated_at.eq(chrono::Utc::now().naive_utc()),
+        ))
+        .filter(id.eq(msg.issue_status_id))
+        .filter(project_id.eq(msg.project_id)),
+    IssueStatus,
+    issue_status_id => IssueStatusId,
+    project_id => ProjectId,
+    wip_limit => Option<i32>,
+    wip_strict => bool
+}
+
+/// Number of issues currently in given status
+#[derive(Execute)]
+#[db_exec(result = "i64", schema = "issues")]
+pub struct CountStatusIssues {
+    pub issue_status_id: IssueStatusId,
+}
+
+impl CountStatusIssues {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<i64, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+
+        crate::q!(issues
+            .filter(issue_status_id.eq(self.issue_status_id))
+            .count())
+        .get_result(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::LoadCollection,
+                crate::ResourceKind::Issue,
+            )
+        })
+    }
+}
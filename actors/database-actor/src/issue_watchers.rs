@@ -0,0 +1,109 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{IssueId, UserId};
+
+use crate::{db_delete, db_load_field};
+
+db_load_field! {
+    LoadIssueWatcherIds,
+    UserId,
+    msg => issue_watchers => issue_watchers
+                .select(user_id)
+                .filter(issue_id.eq(msg.issue_id))
+                .order(user_id.asc()),
+    IssueWatcher,
+    issue_id => IssueId
+}
+
+/// Add users to issue watchers, users already watching issue are skipped
+#[derive(Execute)]
+#[db_exec(result = "usize", schema = "issue_watchers")]
+pub struct WatchIssue {
+    pub issue_id: IssueId,
+    pub user_ids: Vec<UserId>,
+}
+
+impl WatchIssue {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::issue_watchers::dsl::*;
+        let msg = self;
+
+        let rows: Vec<_> = msg
+            .user_ids
+            .iter()
+            .map(|u_id| (issue_id.eq(msg.issue_id), user_id.eq(*u_id)))
+            .collect();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        crate::q!(diesel::insert_into(issue_watchers)
+            .values(rows)
+            .on_conflict_do_nothing())
+        .execute(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Create,
+                crate::ResourceKind::IssueWatcher,
+            )
+        })
+    }
+}
+
+db_delete! {
+    UnwatchIssue,
+    msg => issue_watchers => diesel::delete(
+        issue_watchers.filter(issue_id.eq(msg.issue_id).and(user_id.eq(msg.user_id)))
+    ),
+    IssueWatcher,
+    issue_id => IssueId,
+    user_id => UserId
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+    use crate::issues::tests::project_issue;
+
+    #[test]
+    fn watch_issue_once() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (_, user, issue) = project_issue(conn, "foo");
+        let (_, other, _) = project_issue(conn, "bar");
+        let first = WatchIssue {
+            issue_id: issue.id,
+            user_ids: vec![user.id, other.id],
+        }
+        .execute(conn)
+        .unwrap();
+        let second = WatchIssue {
+            issue_id: issue.id,
+            user_ids: vec![user.id],
+        }
+        .execute(conn)
+        .unwrap();
+        let removed = UnwatchIssue {
+            issue_id: issue.id,
+            user_id: other.id,
+        }
+        .execute(conn)
+        .unwrap();
+        let watchers = LoadIssueWatcherIds { issue_id: issue.id }
+            .execute(conn)
+            .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(first, 2);
+        assert_eq!(second, 0);
+        assert_eq!(removed, 1);
+        assert_eq!(watchers, vec![user.id]);
+    }
+}
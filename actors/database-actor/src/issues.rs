@@ -1,6 +1,10 @@
 use derive_db_execute::Execute;
 use diesel::expression::sql_literal::sql;
 use diesel::prelude::*;
+use diesel::sql_types::{Bool, Text};
+use jirs_data::search::{
+    matching_priorities, IssueSearchQuery, SearchCondition, SearchOperator, SearchUser,
+};
 use jirs_data::{IssueId, IssuePriority, IssueStatusId, IssueType, ProjectId, UserId};
 
 use crate::models::Issue;
@@ -15,6 +19,18 @@ pub struct LoadIssue {
     pub issue_id: IssueId,
 }
 
+/// Find issue only if it belongs to given project
+#[derive(Execute)]
+#[db_exec(
+    result = "Issue",
+    schema = "issues",
+    find = "issues.filter(id.eq(msg.issue_id)).filter(project_id.eq(msg.project_id)).distinct()"
+)]
+pub struct FindProjectIssue {
+    pub project_id: ProjectId,
+    pub issue_id: IssueId,
+}
+
 #[derive(Execute)]
 #[db_exec(
     result = "Issue",
@@ -25,9 +41,196 @@ pub struct LoadProjectIssues {
     pub project_id: ProjectId,
 }
 
+#[derive(Execute)]
+#[db_exec(
+    result = "Issue",
+    schema = "issues",
+    load = "issues.filter(parent_id.eq(msg.parent_id)).order(id.asc())"
+)]
+pub struct LoadSubtasks {
+    pub parent_id: IssueId,
+}
+
+/// Replace parent issue estimate, time spent and time remaining with sum of
+/// its sub-tasks values. Time spent also includes worklogs of parent issue.
+/// Issue without sub-tasks is returned unchanged.
+#[derive(Execute)]
+#[db_exec(result = "Issue", schema = "issues")]
+pub struct RollUpIssueTracking {
+    pub issue_id: IssueId,
+}
+
+impl RollUpIssueTracking {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        let subtasks = LoadSubtasks {
+            parent_id: msg.issue_id,
+        }
+        .execute(conn)?;
+        if subtasks.is_empty() {
+            return LoadIssue {
+                issue_id: msg.issue_id,
+            }
+            .execute(conn);
+        }
+        let total = |f: fn(&Issue) -> Option<i32>| -> Option<i32> {
+            subtasks
+                .iter()
+                .filter_map(f)
+                .fold(None, |sum, v| Some(sum.unwrap_or_default() + v))
+        };
+        let own_time_spent = crate::worklogs::worklogs_duration(conn, msg.issue_id)?;
+        let subtasks_time_spent = total(|issue| issue.time_spent);
+        let total_time_spent = match (own_time_spent, subtasks_time_spent) {
+            (None, None) => None,
+            (own, subtasks) => Some(own.unwrap_or_default() + subtasks.unwrap_or_default()),
+        };
+        crate::q!(diesel::update(issues.find(msg.issue_id)).set((
+            estimate.eq(total(|issue| issue.estimate)),
+            time_spent.eq(total_time_spent),
+            time_remaining.eq(total(|issue| issue.time_remaining)),
+        )))
+        .get_result(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Update,
+                crate::ResourceKind::Issue,
+            )
+        })
+    }
+}
+
+/// Maximal number of issues returned by single search
+pub const SEARCH_LIMIT: i64 = 100;
+
+#[derive(Execute)]
+#[db_exec(result = "Vec<Issue>", schema = "issues")]
+pub struct SearchIssues {
+    pub project_id: ProjectId,
+    pub user_id: UserId,
+    pub query: IssueSearchQuery,
+}
+
+impl SearchIssues {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Vec<Issue>, crate::DatabaseError> {
+        use crate::schema::issue_assignees::dsl as assignees;
+        use crate::schema::issues::dsl::*;
+
+        let current_project_id = self.project_id;
+        let current_user_id = self.user_id;
+        let mut q = issues
+            .filter(project_id.eq(current_project_id))
+            .into_boxed();
+
+        for condition in self.query.conditions {
+            q = match condition {
+                SearchCondition::Text(text) => q.filter(
+                    sql::<Bool>(
+                        "(to_tsvector('english', issues.title || ' ' || COALESCE(issues.description_text, '')) \
+                         @@ plainto_tsquery('english', ",
+                    )
+                    .bind::<Text, _>(text.clone())
+                    .sql(
+                        ") OR EXISTS (SELECT 1 FROM comments WHERE comments.issue_id = issues.id \
+                         AND to_tsvector('english', comments.body) @@ plainto_tsquery('english', ",
+                    )
+                    .bind::<Text, _>(text)
+                    .sql(")))"),
+                ),
+                SearchCondition::Assignee(op, user) => {
+                    let user_ids = resolve_users(conn, current_project_id, current_user_id, user)?;
+                    let assigned = assignees::issue_assignees
+                        .select(assignees::issue_id)
+                        .filter(assignees::user_id.eq_any(user_ids));
+                    match op {
+                        SearchOperator::NotEq => q.filter(id.ne_all(assigned)),
+                        _ => q.filter(id.eq_any(assigned)),
+                    }
+                }
+                SearchCondition::Reporter(op, user) => {
+                    let user_ids = resolve_users(conn, current_project_id, current_user_id, user)?;
+                    match op {
+                        SearchOperator::NotEq => q.filter(reporter_id.ne_all(user_ids)),
+                        _ => q.filter(reporter_id.eq_any(user_ids)),
+                    }
+                }
+                SearchCondition::Priority(op, p) => {
+                    q.filter(priority.eq_any(matching_priorities(op, p)))
+                }
+                SearchCondition::Type(SearchOperator::NotEq, t) => q.filter(issue_type.ne(t)),
+                SearchCondition::Type(_, t) => q.filter(issue_type.eq(t)),
+                SearchCondition::Status(op, name) => {
+                    let status_ids: Vec<IssueStatusId> =
+                        crate::issue_statuses::LoadIssueStatuses {
+                            project_id: current_project_id,
+                        }
+                        .execute(conn)?
+                        .into_iter()
+                        .filter(|status| status.name.to_lowercase() == name.to_lowercase())
+                        .map(|status| status.id)
+                        .collect();
+                    match op {
+                        SearchOperator::NotEq => q.filter(issue_status_id.ne_all(status_ids)),
+                        _ => q.filter(issue_status_id.eq_any(status_ids)),
+                    }
+                }
+                SearchCondition::Epic(op, name) => {
+                    let epic_ids: Vec<jirs_data::EpicId> = crate::epics::LoadEpics {
+                        project_id: current_project_id,
+                    }
+                    .execute(conn)?
+                    .into_iter()
+                    .filter(|epic| epic.name.to_lowercase() == name.to_lowercase())
+                    .map(|epic| epic.id)
+                    .collect();
+                    match op {
+                        SearchOperator::NotEq => {
+                            q.filter(epic_id.is_null().or(epic_id.ne_all(epic_ids)))
+                        }
+                        _ => q.filter(epic_id.eq_any(epic_ids)),
+                    }
+                }
+            };
+        }
+
+        crate::q!(q.order(list_position.asc()).limit(SEARCH_LIMIT))
+            .load(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::LoadCollection,
+                    crate::ResourceKind::Issue,
+                )
+            })
+    }
+}
+
+fn resolve_users(
+    conn: &crate::DbPooledConn,
+    project_id: ProjectId,
+    current_user_id: UserId,
+    user: SearchUser,
+) -> Result<Vec<UserId>, crate::DatabaseError> {
+    let name = match user {
+        SearchUser::Me => return Ok(vec![current_user_id]),
+        SearchUser::Name(name) => name.to_lowercase(),
+    };
+    Ok(crate::users::LoadProjectUsers { project_id }
+        .execute(conn)?
+        .into_iter()
+        .filter(|user| user.name.to_lowercase() == name)
+        .map(|user| user.id)
+        .collect())
+}
+
 #[derive(Default, Execute)]
 #[db_exec(result = "Issue", schema = "issues")]
 pub struct UpdateIssue {
+    /// Issue outside of this project is never changed
+    pub project_id: jirs_data::ProjectId,
     pub issue_id: jirs_data::IssueId,
     pub title: Option<String>,
     pub issue_type: Option<IssueType>,
@@ -38,38 +241,82 @@ pub struct UpdateIssue {
     pub estimate: Option<i32>,
     pub time_spent: Option<i32>,
     pub time_remaining: Option<i32>,
-    pub project_id: Option<jirs_data::ProjectId>,
     pub user_ids: Option<Vec<jirs_data::UserId>>,
     pub reporter_id: Option<jirs_data::UserId>,
     pub issue_status_id: Option<jirs_data::IssueStatusId>,
     pub epic_id: Option<Option<jirs_data::EpicId>>,
+    pub sprint_id: Option<Option<jirs_data::SprintId>>,
+    pub label_ids: Option<Vec<jirs_data::LabelId>>,
+    /// Custom field value, `None` inside removes value
+    pub custom_value: Option<(jirs_data::CustomFieldId, Option<jirs_data::CustomValue>)>,
+    /// When set every changed field is recorded in issue history
+    pub changed_by: Option<UserId>,
 }
 
 impl UpdateIssue {
-    fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
         let msg = self;
         use crate::schema::issues::dsl::*;
-        if let Some(user_ids) = msg.user_ids {
-            crate::issue_assignees::DropIssueAssignees {
+        crate::Guard::new(conn)?.run(|_guard| {
+            let old_issue = FindProjectIssue {
+                project_id: msg.project_id,
                 issue_id: msg.issue_id,
-                user_ids: user_ids.clone(),
             }
             .execute(conn)?;
+            let before = match msg.changed_by {
+                Some(_) => Some((
+                    old_issue,
+                    crate::issue_assignees::LoadAssigneesIds {
+                        issue_id: msg.issue_id,
+                    }
+                    .execute(conn)?,
+                )),
+                None => None,
+            };
+            let new_assignees = msg.user_ids.clone();
+            if let Some(user_ids) = msg.user_ids {
+                crate::issue_assignees::DropIssueAssignees {
+                    issue_id: msg.issue_id,
+                    user_ids: user_ids.clone(),
+                }
+                .execute(conn)?;
 
-            let existing: Vec<UserId> = crate::issue_assignees::LoadAssigneesIds {
-                issue_id: msg.issue_id,
-            }
-            .execute(conn)?;
-            crate::issue_assignees::AsignMultiple {
-                issue_id: msg.issue_id,
-                user_ids: user_ids
+                let existing: Vec<UserId> = crate::issue_assignees::LoadAssigneesIds {
+                    issue_id: msg.issue_id,
+                }
+                .execute(conn)?;
+                let added = user_ids
                     .into_iter()
                     .filter(|u_id| !existing.contains(u_id))
-                    .collect::<Vec<UserId>>(),
+                    .collect::<Vec<UserId>>();
+                if !added.is_empty() {
+                    crate::issue_assignees::AsignMultiple {
+                        issue_id: msg.issue_id,
+                        user_ids: added,
+                    }
+                    .execute(conn)?;
+                }
             }
-            .execute(conn)?;
-        }
-        diesel::update(issues.find(msg.issue_id))
+            if let Some(label_ids) = msg.label_ids {
+                crate::issue_labels::SetIssueLabels {
+                    issue_id: msg.issue_id,
+                    label_ids,
+                }
+                .execute(conn)?;
+            }
+            if let Some((custom_field_id, value)) = msg.custom_value {
+                crate::issue_custom_values::SetIssueCustomValue {
+                    issue_id: msg.issue_id,
+                    custom_field_id,
+                    value,
+                }
+                .execute(conn)?;
+            }
+            let issue: Issue = diesel::update(
+                issues
+                    .filter(project_id.eq(msg.project_id))
+                    .find(msg.issue_id),
+            )
             .set((
                 msg.title.map(|v| title.eq(v)),
                 msg.issue_type.map(|v| issue_type.eq(v)),
@@ -81,9 +328,9 @@ impl UpdateIssue {
                 msg.estimate.map(|v| estimate.eq(v)),
                 msg.time_spent.map(|v| time_spent.eq(v)),
                 msg.time_remaining.map(|v| time_remaining.eq(v)),
-                msg.project_id.map(|v| project_id.eq(v)),
                 msg.reporter_id.map(|v| reporter_id.eq(v)),
                 msg.epic_id.map(|v| epic_id.eq(v)),
+                msg.sprint_id.map(|v| sprint_id.eq(v)),
                 updated_at.eq(chrono::Utc::now().naive_utc()),
             ))
             .get_result(conn)
@@ -93,24 +340,80 @@ impl UpdateIssue {
                     crate::OperationError::Create,
                     crate::ResourceKind::Issue,
                 )
-            })
+            })?;
+
+            if let (Some(changed_by), Some((old_issue, old_assignees))) = (msg.changed_by, before) {
+                let mut changes = crate::issue_history::issue_changes(&old_issue, &issue);
+                if let Some(new_assignees) = new_assignees {
+                    changes.extend(crate::issue_history::assignees_change(
+                        &old_assignees,
+                        &new_assignees,
+                    ));
+                }
+                crate::issue_history::CreateIssueHistory {
+                    issue_id: issue.id,
+                    user_id: changed_by,
+                    changes,
+                }
+                .execute(conn)?;
+            }
+            if let Some(parent) = issue.parent_id {
+                RollUpIssueTracking { issue_id: parent }.execute(conn)?;
+            }
+            Ok(issue)
+        })
     }
 }
 
+/// Delete issue with all its sub-tasks and refresh parent tracking values
 #[derive(Execute)]
-#[db_exec(
-    result = "Issue",
-    schema = "issues",
-    destroy = r#"{
-        crate::issue_assignees::DeleteIssueAssignees { issue_id: msg.issue_id }
-            .execute(conn)?;
-        diesel::delete(issues.find(msg.issue_id))
-    }"#
-)]
+#[db_exec(result = "usize", schema = "issues")]
 pub struct DeleteIssue {
+    pub project_id: ProjectId,
     pub issue_id: IssueId,
 }
 
+impl DeleteIssue {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            let issue = FindProjectIssue {
+                project_id: msg.project_id,
+                issue_id: msg.issue_id,
+            }
+            .execute(conn)?;
+            let mut ids: Vec<IssueId> = LoadSubtasks {
+                parent_id: msg.issue_id,
+            }
+            .execute(conn)?
+            .into_iter()
+            .map(|subtask| subtask.id)
+            .collect();
+            ids.push(msg.issue_id);
+
+            for i_id in ids.iter().copied() {
+                crate::issue_assignees::DeleteIssueAssignees { issue_id: i_id }.execute(conn)?;
+            }
+            let n = crate::q!(diesel::delete(issues.filter(id.eq_any(ids))))
+                .execute(conn)
+                .map_err(|e| {
+                    log::error!("{:?}", e);
+                    crate::DatabaseError::GenericFailure(
+                        crate::OperationError::Delete,
+                        crate::ResourceKind::Issue,
+                    )
+                })?;
+
+            if let Some(parent) = issue.parent_id {
+                RollUpIssueTracking { issue_id: parent }.execute(conn)?;
+            }
+            Ok(n)
+        })
+    }
+}
+
 mod inner {
     use derive_db_execute::Execute;
     use diesel::prelude::*;
@@ -137,7 +440,8 @@ mod inner {
                     time_remaining.eq(msg.time_remaining),
                     reporter_id.eq(msg.reporter_id),
                     project_id.eq(msg.project_id),
-                    epic_id.eq(msg.epic_id)
+                    epic_id.eq(msg.epic_id),
+                    parent_id.eq(msg.parent_id)
                 ))
                 .on_conflict_do_nothing()
         "#
@@ -156,6 +460,7 @@ mod inner {
         pub project_id: jirs_data::ProjectId,
         pub reporter_id: jirs_data::UserId,
         pub epic_id: Option<jirs_data::EpicId>,
+        pub parent_id: Option<jirs_data::IssueId>,
     }
 }
 
@@ -175,63 +480,245 @@ pub struct CreateIssue {
     pub reporter_id: jirs_data::UserId,
     pub user_ids: Vec<jirs_data::UserId>,
     pub epic_id: Option<jirs_data::EpicId>,
+    pub parent_id: Option<jirs_data::IssueId>,
+    pub custom_values: Vec<(jirs_data::CustomFieldId, jirs_data::CustomValue)>,
 }
 
 impl CreateIssue {
-    fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
         use crate::schema::issues::dsl::*;
         let msg = self;
 
-        let pos = issues
-            .select(sql("COALESCE(max(list_position), 0) + 1"))
-            .get_result::<i32>(conn)
-            .map_err(|e| {
-                log::error!("resolve new issue position failed {}", e);
-                crate::DatabaseError::Issue(crate::IssueError::BadListPosition)
-            })?;
-        let i_s_id: IssueStatusId = if msg.issue_status_id == 0 {
-            crate::issue_statuses::LoadIssueStatuses {
+        crate::Guard::new(conn)?.run(|_guard| {
+            let pos = issues
+                .select(sql("COALESCE(max(list_position), 0) + 1"))
+                .get_result::<i32>(conn)
+                .map_err(|e| {
+                    log::error!("resolve new issue position failed {}", e);
+                    crate::DatabaseError::Issue(crate::IssueError::BadListPosition)
+                })?;
+            if let Some(parent) = msg.parent_id {
+                FindProjectIssue {
+                    project_id: msg.project_id,
+                    issue_id: parent,
+                }
+                .execute(conn)?;
+            }
+            let i_s_id: IssueStatusId = if msg.issue_status_id == 0 {
+                crate::issue_statuses::LoadIssueStatuses {
+                    project_id: msg.project_id,
+                }
+                .execute(conn)?
+                .first()
+                .ok_or_else(|| crate::DatabaseError::Issue(crate::IssueError::NoIssueStatuses))?
+                .id
+            } else {
+                msg.issue_status_id
+            };
+            let mut assign_users = msg
+                .user_ids
+                .iter()
+                .cloned()
+                .filter(|u_id| *u_id != msg.reporter_id)
+                .collect::<Vec<UserId>>();
+            assign_users.sort_unstable();
+            assign_users.dedup();
+            let issue = inner::CreateIssue {
+                title: msg.title,
+                list_position: pos,
+                issue_type: msg.issue_type,
+                issue_status_id: i_s_id,
+                priority: msg.priority,
+                description: msg.description,
+                description_text: msg.description_text,
+                estimate: msg.estimate,
+                time_spent: msg.time_spent,
+                time_remaining: msg.time_remaining,
                 project_id: msg.project_id,
+                reporter_id: msg.reporter_id,
+                epic_id: msg.epic_id,
+                parent_id: msg.parent_id,
             }
-            .execute(conn)?
-            .first()
-            .ok_or_else(|| crate::DatabaseError::Issue(crate::IssueError::NoIssueStatuses))?
-            .id
-        } else {
-            msg.issue_status_id
-        };
-        let assign_users = msg
-            .user_ids
-            .iter()
-            .cloned()
-            .filter(|u_id| *u_id != msg.reporter_id)
-            .collect::<Vec<UserId>>();
-        let issue = inner::CreateIssue {
-            title: msg.title,
-            list_position: pos,
-            issue_type: msg.issue_type,
-            issue_status_id: i_s_id,
-            priority: msg.priority,
-            description: msg.description,
-            description_text: msg.description_text,
-            estimate: msg.estimate,
-            time_spent: msg.time_spent,
-            time_remaining: msg.time_remaining,
-            project_id: msg.project_id,
-            reporter_id: msg.reporter_id,
-            epic_id: msg.epic_id,
-        }
-        .execute(conn)?;
-        crate::issue_assignees::AsignMultiple {
-            issue_id: issue.id,
-            user_ids: assign_users,
-        };
-        issues.find(issue.id).get_result(conn).map_err(|e| {
-            log::error!("{:?}", e);
-            crate::DatabaseError::GenericFailure(
-                crate::OperationError::Create,
-                crate::ResourceKind::Issue,
-            )
+            .execute(conn)?;
+            // inserting no rows returns no assignee and would fail
+            if !assign_users.is_empty() {
+                crate::issue_assignees::AsignMultiple {
+                    issue_id: issue.id,
+                    user_ids: assign_users,
+                }
+                .execute(conn)?;
+            }
+            for (custom_field_id, value) in msg.custom_values {
+                crate::issue_custom_values::SetIssueCustomValue {
+                    issue_id: issue.id,
+                    custom_field_id,
+                    value: Some(value),
+                }
+                .execute(conn)?;
+            }
+            if let Some(parent) = issue.parent_id {
+                RollUpIssueTracking { issue_id: parent }.execute(conn)?;
+            }
+            issues.find(issue.id).get_result(conn).map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Create,
+                    crate::ResourceKind::Issue,
+                )
+            })
         })
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use diesel::connection::TransactionManager;
+    use jirs_data::{Project, User};
+
+    use super::*;
+    use crate::build_pool;
+
+    /// Create project with single issue reported by new user
+    pub(crate) fn project_issue(conn: &crate::DbPooledConn, name: &str) -> (Project, User, Issue) {
+        let project = crate::projects::CreateProject {
+            name: name.to_string(),
+            url: None,
+            description: None,
+            category: None,
+            time_tracking: None,
+        }
+        .execute(conn)
+        .unwrap();
+        let user = crate::users::CreateUser {
+            name: name.to_string(),
+            email: format!("{}@example.com", name),
+        }
+        .execute(conn)
+        .unwrap();
+        let issue = CreateIssue {
+            title: format!("{} issue", name),
+            issue_type: IssueType::Task,
+            issue_status_id: 0,
+            priority: IssuePriority::Medium,
+            description: None,
+            description_text: None,
+            estimate: None,
+            time_spent: None,
+            time_remaining: None,
+            project_id: project.id,
+            reporter_id: user.id,
+            user_ids: vec![],
+            epic_id: None,
+            parent_id: None,
+            custom_values: vec![],
+        }
+        .execute(conn)
+        .unwrap();
+        (project, user, issue)
+    }
+
+    #[test]
+    fn create_issue_with_assignees() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (project, reporter, _) = project_issue(conn, "foo");
+        let (_, assignee, _) = project_issue(conn, "bar");
+        let issue = CreateIssue {
+            title: "assigned".to_string(),
+            issue_type: IssueType::Task,
+            issue_status_id: 0,
+            priority: IssuePriority::Medium,
+            description: None,
+            description_text: None,
+            estimate: None,
+            time_spent: None,
+            time_remaining: None,
+            project_id: project.id,
+            reporter_id: reporter.id,
+            user_ids: vec![reporter.id, assignee.id, assignee.id],
+            epic_id: None,
+            parent_id: None,
+            custom_values: vec![],
+        }
+        .execute(conn)
+        .unwrap();
+        let assignees = crate::issue_assignees::LoadAssigneesIds { issue_id: issue.id }
+            .execute(conn)
+            .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(assignees, vec![assignee.id]);
+    }
+
+    #[test]
+    fn reject_cross_project_issue_access() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (foo, _, foo_issue) = project_issue(conn, "foo");
+        let (bar, bar_user, bar_issue) = project_issue(conn, "bar");
+
+        let found = FindProjectIssue {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+        }
+        .execute(conn);
+        let updated = UpdateIssue {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+            title: Some("hijacked".to_string()),
+            ..Default::default()
+        }
+        .execute(conn);
+        let subtask = CreateIssue {
+            title: "subtask".to_string(),
+            issue_type: IssueType::Subtask,
+            issue_status_id: 0,
+            priority: IssuePriority::Medium,
+            description: None,
+            description_text: None,
+            estimate: None,
+            time_spent: None,
+            time_remaining: None,
+            project_id: foo.id,
+            reporter_id: bar_user.id,
+            user_ids: vec![],
+            epic_id: None,
+            parent_id: Some(bar_issue.id),
+            custom_values: vec![],
+        }
+        .execute(conn);
+        let deleted = DeleteIssue {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+        }
+        .execute(conn);
+        let own = UpdateIssue {
+            project_id: foo.id,
+            issue_id: foo_issue.id,
+            title: Some("renamed".to_string()),
+            ..Default::default()
+        }
+        .execute(conn);
+        let untouched = FindProjectIssue {
+            project_id: bar.id,
+            issue_id: bar_issue.id,
+        }
+        .execute(conn);
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(found.is_err());
+        assert!(updated.is_err());
+        assert!(subtask.is_err());
+        assert!(deleted.is_err());
+        assert_eq!(own.unwrap().title, "renamed".to_string());
+        assert_eq!(untouched.unwrap().title, "bar issue".to_string());
+    }
+}
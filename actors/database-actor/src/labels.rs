@@ -0,0 +1,39 @@
+use diesel::prelude::*;
+use jirs_data::{Label, LabelId, NameString, ProjectId};
+
+use crate::{db_create, db_delete, db_load};
+
+db_load! {
+    LoadLabels,
+    msg => labels => labels
+            .distinct_on(id)
+            .filter(project_id.eq(msg.project_id))
+            .order(id.asc()),
+    Label,
+    project_id => ProjectId
+}
+
+db_create! {
+    CreateLabel,
+    msg => labels => diesel::insert_into(labels).values((
+            project_id.eq(msg.project_id),
+            name.eq(msg.name),
+            color.eq(msg.color),
+    )),
+    Label,
+    project_id => ProjectId,
+    name => NameString,
+    color => String
+}
+
+db_delete! {
+    DeleteLabel,
+    msg => labels => diesel::delete(
+            labels
+                .filter(project_id.eq(msg.project_id))
+                .find(msg.label_id),
+        ),
+    Label,
+    project_id => ProjectId,
+    label_id => LabelId
+}
@@ -10,21 +10,33 @@ pub use errors::*;
 
 pub mod authorize_user;
 pub mod comments;
+pub mod custom_fields;
 pub mod epics;
 pub mod errors;
 pub mod invitations;
 pub mod issue_assignees;
+pub mod issue_custom_values;
+pub mod issue_history;
+pub mod issue_labels;
+pub mod issue_links;
 pub mod issue_statuses;
+pub mod issue_watchers;
 pub mod issues;
+pub mod labels;
 pub mod messages;
 pub mod models;
 pub mod prelude;
 pub mod projects;
+pub mod reports;
 pub mod schema;
+pub mod sprints;
 pub mod tokens;
+pub mod user_credentials;
 pub mod user_projects;
 pub mod user_settings;
 pub mod users;
+pub mod workflow_transitions;
+pub mod worklogs;
 
 pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
 pub type DbPooledConn = r2d2::PooledConnection<ConnectionManager<PgConnection>>;
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
+use derive_db_execute::Execute;
 use diesel::prelude::*;
-use jirs_data::{BindToken, Message, MessageId, MessageType, User, UserId};
+use jirs_data::{BindToken, Message, MessageId, MessageType, NotificationMode, User, UserId};
 
 use crate::users::{FindUser, LookupUser};
 use crate::{db_create, db_delete, db_load};
@@ -64,3 +67,185 @@ db_load! {
     token => BindToken,
     user_id => UserId
 }
+
+/// Remember messages were sent to receiver e-mail so they will not be included
+/// in next digest
+#[derive(Execute)]
+#[db_exec(result = "usize", schema = "messages")]
+pub struct MarkMessagesNotified {
+    pub message_ids: Vec<MessageId>,
+}
+
+impl MarkMessagesNotified {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::messages::dsl::*;
+        let msg = self;
+
+        crate::q!(diesel::update(messages.filter(id.eq_any(msg.message_ids)))
+            .set(notified_at.eq(chrono::Utc::now().naive_utc())))
+        .execute(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Update,
+                crate::ResourceKind::Message,
+            )
+        })
+    }
+}
+
+/// Unseen messages of single receiver which were not sent by e-mail yet
+#[derive(Debug)]
+pub struct MessageDigest {
+    pub receiver: User,
+    /// Message with its sender
+    pub messages: Vec<(Message, User)>,
+}
+
+/// Collect unseen and not yet mailed messages of users who chose daily digest.
+/// Invitations are skipped because they are always sent immediately
+#[derive(Execute)]
+#[db_exec(result = "Vec<MessageDigest>", schema = "messages")]
+pub struct LoadMessageDigests;
+
+impl LoadMessageDigests {
+    fn execute(
+        self,
+        conn: &crate::DbPooledConn,
+    ) -> Result<Vec<MessageDigest>, crate::DatabaseError> {
+        use crate::schema::messages::dsl::*;
+        use crate::schema::user_settings::dsl::{notification_mode, user_id, user_settings};
+        use crate::schema::users::dsl::{id as users_id, users};
+
+        let receiver_ids: Vec<UserId> = crate::q!(user_settings
+            .select(user_id)
+            .filter(notification_mode.eq(NotificationMode::DailyDigest)))
+        .load(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::LoadCollection,
+                crate::ResourceKind::UserSetting,
+            )
+        })?;
+        let rows: Vec<Message> = crate::q!(messages
+            .filter(receiver_id.eq_any(receiver_ids))
+            .filter(notified_at.is_null())
+            .filter(message_type.ne(MessageType::ReceivedInvitation))
+            .order_by(created_at.asc()))
+        .load(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::LoadCollection,
+                crate::ResourceKind::Message,
+            )
+        })?;
+
+        let user_ids: Vec<UserId> = rows
+            .iter()
+            .flat_map(|message| vec![message.receiver_id, message.sender_id])
+            .collect();
+        let people: BTreeMap<UserId, User> = crate::q!(users.filter(users_id.eq_any(user_ids)))
+            .load::<User>(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::LoadCollection,
+                    crate::ResourceKind::User,
+                )
+            })?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+
+        let mut digests: BTreeMap<UserId, MessageDigest> = BTreeMap::new();
+        for message in rows {
+            let (receiver, sender) = match (
+                people.get(&message.receiver_id),
+                people.get(&message.sender_id),
+            ) {
+                (Some(receiver), Some(sender)) => (receiver, sender),
+                _ => continue,
+            };
+            digests
+                .entry(receiver.id)
+                .or_insert_with(|| MessageDigest {
+                    receiver: receiver.clone(),
+                    messages: vec![],
+                })
+                .messages
+                .push((message, sender.clone()));
+        }
+        Ok(digests.into_iter().map(|(_, digest)| digest).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+    use crate::users::CreateUser;
+
+    #[test]
+    fn collect_digest_of_users_who_chose_it() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let create_user = |name: &str| {
+            CreateUser {
+                name: name.to_string(),
+                email: format!("{}@example.com", name),
+            }
+            .execute(conn)
+            .unwrap()
+        };
+        let sender = create_user("sender");
+        let digest_user = create_user("digest");
+        let immediate_user = create_user("immediate");
+        crate::user_settings::UpdateNotificationMode {
+            user_id: digest_user.id,
+            mode: NotificationMode::DailyDigest,
+        }
+        .execute(conn)
+        .unwrap();
+
+        let create_message = |receiver: &User, message_type: MessageType| {
+            CreateMessage {
+                receiver: CreateMessageReceiver::Reference(receiver.id),
+                sender_id: sender.id,
+                summary: "summary".to_string(),
+                description: "description".to_string(),
+                message_type,
+                hyper_link: "".to_string(),
+            }
+            .execute(conn)
+            .unwrap()
+        };
+        let mention = create_message(&digest_user, MessageType::Mention);
+        create_message(&digest_user, MessageType::ReceivedInvitation);
+        create_message(&immediate_user, MessageType::Mention);
+
+        let digests = LoadMessageDigests.execute(conn).unwrap();
+        let marked = MarkMessagesNotified {
+            message_ids: vec![mention.id],
+        }
+        .execute(conn)
+        .unwrap();
+        let after_mark = LoadMessageDigests.execute(conn).unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].receiver.id, digest_user.id);
+        assert_eq!(digests[0].messages.len(), 1);
+        assert_eq!(digests[0].messages[0].0.id, mention.id);
+        assert_eq!(digests[0].messages[0].1.id, sender.id);
+        assert_eq!(marked, 1);
+        assert!(after_mark.is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::NaiveDateTime;
 use jirs_data::{
-    EpicId, InvitationState, IssuePriority, IssueStatusId, IssueType, ProjectCategory, ProjectId,
-    TimeTracking, UserId,
+    EpicId, InvitationState, IssueHistoryField, IssueId, IssuePriority, IssueStatusId, IssueType,
+    ProjectCategory, ProjectId, SprintId, TimeTracking, UserId,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -26,6 +26,8 @@ pub struct Issue {
     pub updated_at: NaiveDateTime,
     pub issue_status_id: IssueStatusId,
     pub epic_id: Option<EpicId>,
+    pub sprint_id: Option<SprintId>,
+    pub parent_id: Option<IssueId>,
 }
 
 impl Into<jirs_data::Issue> for Issue {
@@ -47,8 +49,11 @@ impl Into<jirs_data::Issue> for Issue {
             updated_at: self.updated_at,
             issue_status_id: self.issue_status_id,
             epic_id: self.epic_id,
+            sprint_id: self.sprint_id,
+            parent_id: self.parent_id,
 
             user_ids: vec![],
+            label_ids: vec![],
         }
     }
 }
@@ -78,6 +83,16 @@ pub struct CreateIssueAssigneeForm {
     pub user_id: i32,
 }
 
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[table_name = "issue_history"]
+pub struct CreateIssueHistoryForm {
+    pub issue_id: i32,
+    pub user_id: i32,
+    pub field: IssueHistoryField,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Insertable)]
 #[table_name = "projects"]
 pub struct UpdateProjectForm {
@@ -123,3 +138,15 @@ pub struct InvitationForm {
     pub project_id: i32,
     pub invited_by_id: i32,
 }
+
+/// Secrets used by password and two factor sign in, never sent to client
+#[derive(Debug, Queryable)]
+pub struct UserCredential {
+    pub id: i32,
+    pub user_id: UserId,
+    pub password_hash: Option<String>,
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
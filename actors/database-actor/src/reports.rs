@@ -0,0 +1,483 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{
+    BurndownPoint, CumulativeFlowPoint, EndsAt, EpicId, IssueHistory, IssueHistoryField, IssueId,
+    IssueStatus, IssueStatusId, ProjectId, StartsAt, VelocityPoint,
+};
+
+use crate::models::Issue;
+
+/// Number of weeks on velocity chart, including current one
+const VELOCITY_WEEKS: i64 = 8;
+
+/// Issue status with highest position, same as in sprint completion
+fn done_status_id(
+    conn: &crate::DbPooledConn,
+    project_id: ProjectId,
+) -> Result<Option<IssueStatusId>, crate::DatabaseError> {
+    Ok(crate::issue_statuses::LoadIssueStatuses { project_id }
+        .execute(conn)?
+        .into_iter()
+        .max_by_key(|status| status.position)
+        .map(|status| status.id))
+}
+
+/// Status changes of issues grouped by issue, each list sorted by creation
+/// time
+type StatusHistory = HashMap<IssueId, Vec<IssueHistory>>;
+
+fn load_status_history(
+    conn: &crate::DbPooledConn,
+    ids: Vec<IssueId>,
+) -> Result<StatusHistory, crate::DatabaseError> {
+    use crate::schema::issue_history::dsl::*;
+    let rows: Vec<IssueHistory> = crate::q!(issue_history
+        .filter(field.eq(IssueHistoryField::Status))
+        .filter(issue_id.eq_any(ids)))
+    .load(conn)
+    .map_err(|e| {
+        log::error!("{:?}", e);
+        crate::DatabaseError::GenericFailure(
+            crate::OperationError::LoadCollection,
+            crate::ResourceKind::IssueHistory,
+        )
+    })?;
+    Ok(group_history(rows))
+}
+
+fn group_history(rows: Vec<IssueHistory>) -> StatusHistory {
+    let mut history = StatusHistory::new();
+    for entry in rows {
+        history.entry(entry.issue_id).or_default().push(entry);
+    }
+    for changes in history.values_mut() {
+        changes.sort_by_key(|entry| (entry.created_at, entry.id));
+    }
+    history
+}
+
+fn issue_changes<'h>(issue: &Issue, history: &'h StatusHistory) -> &'h [IssueHistory] {
+    history
+        .get(&issue.id)
+        .map(|changes| changes.as_slice())
+        .unwrap_or_default()
+}
+
+/// Moment when issue was moved to done status for the last time. Done issue
+/// without status history was created as done.
+fn completed_at(
+    issue: &Issue,
+    done: Option<IssueStatusId>,
+    history: &StatusHistory,
+) -> Option<NaiveDateTime> {
+    if Some(issue.issue_status_id) != done {
+        return None;
+    }
+    let done = issue.issue_status_id.to_string();
+    issue_changes(issue, history)
+        .iter()
+        .filter(|entry| entry.new_value.as_ref() == Some(&done))
+        .map(|entry| entry.created_at)
+        .max()
+        .or(Some(issue.created_at))
+}
+
+/// Sum of estimates of issues which existed and were not done at the end of
+/// each day
+fn burndown_points(
+    rows: &[Issue],
+    done: Option<IssueStatusId>,
+    history: &StatusHistory,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Vec<BurndownPoint> {
+    let completed: Vec<(&Issue, Option<NaiveDate>)> = rows
+        .iter()
+        .map(|issue| {
+            (
+                issue,
+                completed_at(issue, done, history).map(|at| at.date()),
+            )
+        })
+        .collect();
+
+    let mut points = vec![];
+    let mut day = first;
+    while day <= last {
+        let remaining = completed
+            .iter()
+            .filter(|(issue, completed)| {
+                issue.created_at.date() <= day && completed.filter(|at| *at <= day).is_none()
+            })
+            .map(|(issue, _)| issue.estimate.unwrap_or_default())
+            .sum();
+        points.push(BurndownPoint { day, remaining });
+        day += Duration::days(1);
+    }
+    points
+}
+
+/// Completed estimate of done issues in each of last weeks ending with
+/// `this_week`
+fn velocity_points(
+    rows: &[Issue],
+    done: IssueStatusId,
+    history: &StatusHistory,
+    this_week: NaiveDate,
+) -> Vec<VelocityPoint> {
+    let mut points: Vec<VelocityPoint> = (0..VELOCITY_WEEKS)
+        .rev()
+        .map(|n| VelocityPoint {
+            week: this_week - Duration::weeks(n),
+            completed: 0,
+        })
+        .collect();
+    for issue in rows.iter() {
+        let at = match completed_at(issue, Some(done), history) {
+            Some(at) => at.date(),
+            _ => continue,
+        };
+        let week = at - Duration::days(at.weekday().num_days_from_monday() as i64);
+        if let Some(point) = points.iter_mut().find(|point| point.week == week) {
+            point.completed += issue.estimate.unwrap_or_default();
+        }
+    }
+    points
+}
+
+/// Remaining estimate of epic issues for each day from epic start until its
+/// end or today. Epic without dates uses its creation day and today.
+#[derive(Execute)]
+#[db_exec(result = "Vec<BurndownPoint>", schema = "issues")]
+pub struct LoadEpicBurndown {
+    pub project_id: ProjectId,
+    pub epic_id: EpicId,
+}
+
+impl LoadEpicBurndown {
+    fn execute(
+        self,
+        conn: &crate::DbPooledConn,
+    ) -> Result<Vec<BurndownPoint>, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        let epic = crate::epics::FindEpic {
+            project_id: msg.project_id,
+            epic_id: msg.epic_id,
+        }
+        .execute(conn)?;
+
+        let rows: Vec<Issue> = crate::q!(issues
+            .filter(project_id.eq(msg.project_id))
+            .filter(epic_id.eq(msg.epic_id)))
+        .load(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::LoadCollection,
+                crate::ResourceKind::Issue,
+            )
+        })?;
+        let done = done_status_id(conn, msg.project_id)?;
+        let history = load_status_history(conn, rows.iter().map(|issue| issue.id).collect())?;
+
+        let today = chrono::Utc::now().naive_utc().date();
+        let first = epic.starts_at.unwrap_or(epic.created_at).date();
+        let last = epic.ends_at.map(|at| at.date()).unwrap_or(today).min(today);
+        Ok(burndown_points(&rows, done, &history, first, last))
+    }
+}
+
+/// Completed estimate of project issues in each of last weeks
+#[derive(Execute)]
+#[db_exec(result = "Vec<VelocityPoint>", schema = "issues")]
+pub struct LoadVelocity {
+    pub project_id: ProjectId,
+}
+
+impl LoadVelocity {
+    fn execute(
+        self,
+        conn: &crate::DbPooledConn,
+    ) -> Result<Vec<VelocityPoint>, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        let done = match done_status_id(conn, msg.project_id)? {
+            Some(done) => done,
+            _ => return Ok(vec![]),
+        };
+        let rows: Vec<Issue> = crate::q!(issues
+            .filter(project_id.eq(msg.project_id))
+            .filter(issue_status_id.eq(done)))
+        .load(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::LoadCollection,
+                crate::ResourceKind::Issue,
+            )
+        })?;
+        let history = load_status_history(conn, rows.iter().map(|issue| issue.id).collect())?;
+
+        let today = chrono::Utc::now().naive_utc().date();
+        let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        Ok(velocity_points(&rows, done, &history, this_week))
+    }
+}
+
+/// Longest range of cumulative flow diagram
+const CUMULATIVE_FLOW_MAX_DAYS: i64 = 366;
+
+/// Number of project issues in each status for every day of range. Status of
+/// issue at the end of the day is restored from recorded status changes.
+#[derive(Execute)]
+#[db_exec(result = "Vec<CumulativeFlowPoint>", schema = "issues")]
+pub struct LoadCumulativeFlow {
+    pub project_id: ProjectId,
+    pub from: StartsAt,
+    pub to: EndsAt,
+}
+
+impl LoadCumulativeFlow {
+    fn execute(
+        self,
+        conn: &crate::DbPooledConn,
+    ) -> Result<Vec<CumulativeFlowPoint>, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        let mut statuses = crate::issue_statuses::LoadIssueStatuses {
+            project_id: msg.project_id,
+        }
+        .execute(conn)?;
+        statuses.sort_by_key(|status| status.position);
+
+        let rows: Vec<Issue> = crate::q!(issues.filter(project_id.eq(msg.project_id)))
+            .load(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::LoadCollection,
+                    crate::ResourceKind::Issue,
+                )
+            })?;
+        let history = load_status_history(conn, rows.iter().map(|issue| issue.id).collect())?;
+
+        let today = chrono::Utc::now().naive_utc().date();
+        let last = msg.to.date().min(today);
+        let first = msg
+            .from
+            .date()
+            .max(last - Duration::days(CUMULATIVE_FLOW_MAX_DAYS - 1));
+        Ok(cumulative_flow_points(
+            &statuses, &rows, &history, first, last,
+        ))
+    }
+}
+
+/// Count issues in each status at the end of every day. Changes of each issue
+/// are walked once while going through days.
+fn cumulative_flow_points(
+    statuses: &[IssueStatus],
+    rows: &[Issue],
+    history: &StatusHistory,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Vec<CumulativeFlowPoint> {
+    let mut points = vec![];
+    let mut day = first;
+    while day <= last {
+        points.push(CumulativeFlowPoint {
+            day,
+            counts: statuses.iter().map(|status| (status.id, 0)).collect(),
+        });
+        day += Duration::days(1);
+    }
+
+    for issue in rows {
+        let changes = issue_changes(issue, history);
+        let mut applied = 0;
+        for point in points
+            .iter_mut()
+            .filter(|point| issue.created_at.date() <= point.day)
+        {
+            while applied < changes.len() && changes[applied].created_at.date() <= point.day {
+                applied += 1;
+            }
+            let status_id = status_after(issue, changes, applied);
+            if let Some((_, n)) = point
+                .counts
+                .iter_mut()
+                .find(|(status, _)| *status == status_id)
+            {
+                *n += 1;
+            }
+        }
+    }
+    points
+}
+
+/// Issue status after given number of its changes. Before first change issue
+/// was in status from which it was moved.
+fn status_after(issue: &Issue, changes: &[IssueHistory], applied: usize) -> IssueStatusId {
+    let value = match (changes.first(), applied) {
+        (_, n) if n > 0 => changes[n - 1].new_value.as_ref(),
+        (Some(entry), _) => entry.old_value.as_ref(),
+        _ => return issue.issue_status_id,
+    };
+    value
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(issue.issue_status_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use jirs_data::{IssuePriority, IssueType};
+
+    use super::*;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(2021, 8, day).and_hms(12, 0, 0)
+    }
+
+    fn issue(id: IssueId, issue_status_id: IssueStatusId, created: u32, estimate: i32) -> Issue {
+        Issue {
+            id,
+            title: format!("issue {}", id),
+            issue_type: IssueType::Task,
+            priority: IssuePriority::Medium,
+            list_position: id,
+            description: None,
+            description_text: None,
+            estimate: Some(estimate),
+            time_spent: None,
+            time_remaining: None,
+            reporter_id: 1,
+            project_id: 1,
+            created_at: at(created),
+            updated_at: at(created),
+            issue_status_id,
+            epic_id: None,
+            sprint_id: None,
+            parent_id: None,
+        }
+    }
+
+    fn change(
+        id: i32,
+        issue_id: IssueId,
+        from: IssueStatusId,
+        to: IssueStatusId,
+        day: u32,
+    ) -> IssueHistory {
+        IssueHistory {
+            id,
+            issue_id,
+            user_id: 1,
+            field: IssueHistoryField::Status,
+            old_value: Some(from.to_string()),
+            new_value: Some(to.to_string()),
+            created_at: at(day),
+            updated_at: at(day),
+        }
+    }
+
+    fn status(id: IssueStatusId) -> IssueStatus {
+        IssueStatus {
+            id,
+            name: format!("status {}", id),
+            position: id,
+            project_id: 1,
+            created_at: at(1),
+            updated_at: at(1),
+            wip_limit: None,
+            wip_strict: false,
+        }
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        at(day).date()
+    }
+
+    #[test]
+    fn sort_history_of_each_issue() {
+        let history = group_history(vec![
+            change(3, 1, 2, 3, 5),
+            change(1, 2, 1, 3, 4),
+            change(2, 1, 1, 2, 3),
+        ]);
+
+        let ids =
+            |issue_id: IssueId| -> Vec<i32> { history[&issue_id].iter().map(|e| e.id).collect() };
+        assert_eq!(ids(1), vec![2, 3]);
+        assert_eq!(ids(2), vec![1]);
+    }
+
+    #[test]
+    fn count_issues_in_status_at_end_of_day() {
+        let statuses = vec![status(1), status(2), status(3)];
+        let rows = vec![issue(1, 3, 1, 1), issue(2, 2, 2, 1), issue(3, 1, 3, 1)];
+        let history = group_history(vec![
+            change(1, 1, 1, 2, 2),
+            change(2, 1, 2, 3, 3),
+            change(3, 2, 1, 2, 3),
+        ]);
+
+        let points = cumulative_flow_points(&statuses, &rows, &history, day(1), day(4));
+
+        let counts: Vec<Vec<(IssueStatusId, i32)>> =
+            points.iter().map(|point| point.counts.clone()).collect();
+        assert_eq!(
+            points.iter().map(|point| point.day).collect::<Vec<_>>(),
+            vec![day(1), day(2), day(3), day(4)]
+        );
+        assert_eq!(counts[0], vec![(1, 1), (2, 0), (3, 0)]);
+        assert_eq!(counts[1], vec![(1, 1), (2, 1), (3, 0)]);
+        assert_eq!(counts[2], vec![(1, 1), (2, 1), (3, 1)]);
+        assert_eq!(counts[3], vec![(1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn burndown_remaining_estimate() {
+        let rows = vec![issue(1, 3, 1, 5), issue(2, 3, 1, 3), issue(3, 1, 2, 2)];
+        // second issue was created as done
+        let history = group_history(vec![change(1, 1, 1, 3, 3)]);
+
+        let points = burndown_points(&rows, Some(3), &history, day(1), day(3));
+
+        assert_eq!(
+            points
+                .iter()
+                .map(|point| (point.day, point.remaining))
+                .collect::<Vec<_>>(),
+            vec![(day(1), 5), (day(2), 7), (day(3), 2)]
+        );
+    }
+
+    #[test]
+    fn velocity_sums_completed_estimate_per_week() {
+        // 2021-08-02 and 2021-08-09 are mondays
+        let rows = vec![issue(1, 3, 1, 5), issue(2, 3, 1, 3), issue(3, 3, 1, 2)];
+        let history = group_history(vec![
+            change(1, 1, 1, 3, 3),
+            change(2, 2, 1, 3, 10),
+            change(3, 3, 1, 3, 12),
+        ]);
+
+        let points = velocity_points(&rows, 3, &history, day(9));
+
+        assert_eq!(points.len(), VELOCITY_WEEKS as usize);
+        assert_eq!(points[points.len() - 1].week, day(9));
+        assert_eq!(points[points.len() - 1].completed, 5);
+        assert_eq!(points[points.len() - 2].week, day(2));
+        assert_eq!(points[points.len() - 2].completed, 5);
+        assert!(points[..points.len() - 2]
+            .iter()
+            .all(|point| point.completed == 0));
+    }
+}
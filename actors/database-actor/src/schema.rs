@@ -47,6 +47,65 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `custom_fields` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    custom_fields (id) {
+        /// The `id` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `name` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        name -> Text,
+        /// The `field_type` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `CustomFieldTypeType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        field_type -> CustomFieldTypeType,
+        /// The `options` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Array<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        options -> Array<Text>,
+        /// The `position` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        position -> Int4,
+        /// The `project_id` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        project_id -> Int4,
+        /// The `created_at` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `custom_fields` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use jirs_data::*;
@@ -230,6 +289,224 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `issue_custom_values` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    issue_custom_values (id) {
+        /// The `id` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `issue_id` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        issue_id -> Int4,
+        /// The `custom_field_id` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        custom_field_id -> Int4,
+        /// The `text_value` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        text_value -> Nullable<Text>,
+        /// The `number_value` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        number_value -> Nullable<Float8>,
+        /// The `date_value` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        date_value -> Nullable<Timestamp>,
+        /// The `option_values` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Array<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        option_values -> Array<Text>,
+        /// The `user_id` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        user_id -> Nullable<Int4>,
+        /// The `created_at` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `issue_custom_values` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `issue_history` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    issue_history (id) {
+        /// The `id` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `issue_id` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        issue_id -> Int4,
+        /// The `user_id` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        user_id -> Int4,
+        /// The `field` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `IssueHistoryFieldType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        field -> IssueHistoryFieldType,
+        /// The `old_value` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        old_value -> Nullable<Text>,
+        /// The `new_value` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        new_value -> Nullable<Text>,
+        /// The `created_at` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `issue_history` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `issue_labels` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    issue_labels (id) {
+        /// The `id` column of the `issue_labels` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `issue_id` column of the `issue_labels` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        issue_id -> Int4,
+        /// The `label_id` column of the `issue_labels` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        label_id -> Int4,
+        /// The `created_at` column of the `issue_labels` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `issue_labels` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `issue_links` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    issue_links (id) {
+        /// The `id` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `link_type` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `IssueLinkTypeType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        link_type -> IssueLinkTypeType,
+        /// The `source_id` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        source_id -> Int4,
+        /// The `target_id` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        target_id -> Int4,
+        /// The `created_at` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `issue_links` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use jirs_data::*;
@@ -274,6 +551,59 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         updated_at -> Timestamp,
+        /// The `wip_limit` column of the `issue_statuses` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        wip_limit -> Nullable<Int4>,
+        /// The `wip_strict` column of the `issue_statuses` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        wip_strict -> Bool,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `issue_watchers` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    issue_watchers (id) {
+        /// The `id` column of the `issue_watchers` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `issue_id` column of the `issue_watchers` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        issue_id -> Int4,
+        /// The `user_id` column of the `issue_watchers` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        user_id -> Int4,
+        /// The `created_at` column of the `issue_watchers` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `issue_watchers` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
     }
 }
 
@@ -381,6 +711,65 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         epic_id -> Nullable<Int4>,
+        /// The `sprint_id` column of the `issues` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        sprint_id -> Nullable<Int4>,
+        /// The `parent_id` column of the `issues` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        parent_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `labels` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    labels (id) {
+        /// The `id` column of the `labels` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `name` column of the `labels` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        name -> Text,
+        /// The `color` column of the `labels` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        color -> Text,
+        /// The `project_id` column of the `labels` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        project_id -> Int4,
+        /// The `created_at` column of the `labels` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `labels` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
     }
 }
 
@@ -446,6 +835,12 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         updated_at -> Timestamp,
+        /// The `notified_at` column of the `messages` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        notified_at -> Nullable<Timestamp>,
     }
 }
 
@@ -508,6 +903,77 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `sprints` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    sprints (id) {
+        /// The `id` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `name` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        name -> Text,
+        /// The `goal` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        goal -> Nullable<Text>,
+        /// The `project_id` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        project_id -> Int4,
+        /// The `state` column of the `sprints` table.
+        ///
+        /// Its SQL type is `SprintStateType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        state -> SprintStateType,
+        /// The `starts_at` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        starts_at -> Nullable<Timestamp>,
+        /// The `ends_at` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        ends_at -> Nullable<Timestamp>,
+        /// The `completed_at` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        completed_at -> Nullable<Timestamp>,
+        /// The `created_at` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `sprints` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use jirs_data::*;
@@ -558,6 +1024,71 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         bind_token -> Nullable<Uuid>,
+        /// The `access_expires_at` column of the `tokens` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        access_expires_at -> Timestamp,
+        /// The `refresh_expires_at` column of the `tokens` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        refresh_expires_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `user_credentials` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    user_credentials (id) {
+        /// The `id` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `user_id` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        user_id -> Int4,
+        /// The `password_hash` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        password_hash -> Nullable<Text>,
+        /// The `totp_secret` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_secret -> Nullable<Text>,
+        /// The `totp_enabled` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_enabled -> Bool,
+        /// The `created_at` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `user_credentials` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
     }
 }
 
@@ -646,6 +1177,18 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         text_editor_mode -> TextEditorModeType,
+        /// The `board_grouping` column of the `user_settings` table.
+        ///
+        /// Its SQL type is `BoardGroupingType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        board_grouping -> BoardGroupingType,
+        /// The `notification_mode` column of the `user_settings` table.
+        ///
+        /// Its SQL type is `NotificationModeType`.
+        ///
+        /// (Automatically generated by Diesel.)
+        notification_mode -> NotificationModeType,
     }
 }
 
@@ -696,35 +1239,187 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `workflow_transitions` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    workflow_transitions (id) {
+        /// The `id` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `project_id` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        project_id -> Int4,
+        /// The `from_status_id` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        from_status_id -> Int4,
+        /// The `to_status_id` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        to_status_id -> Int4,
+        /// The `required_role` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Nullable<UserRoleType>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        required_role -> Nullable<UserRoleType>,
+        /// The `require_assignee` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        require_assignee -> Bool,
+        /// The `require_estimate` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        require_estimate -> Bool,
+        /// The `created_at` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `workflow_transitions` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use jirs_data::*;
+
+    /// Representation of the `worklogs` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    worklogs (id) {
+        /// The `id` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `issue_id` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        issue_id -> Int4,
+        /// The `user_id` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        user_id -> Int4,
+        /// The `duration` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        duration -> Int4,
+        /// The `started_at` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        started_at -> Timestamp,
+        /// The `comment` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        comment -> Nullable<Text>,
+        /// The `created_at` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `updated_at` column of the `worklogs` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        updated_at -> Timestamp,
+    }
+}
+
 joinable!(comments -> issues (issue_id));
 joinable!(comments -> users (user_id));
+joinable!(custom_fields -> projects (project_id));
 joinable!(epics -> projects (project_id));
 joinable!(epics -> users (user_id));
 joinable!(invitations -> projects (project_id));
 joinable!(invitations -> users (invited_by_id));
 joinable!(issue_assignees -> issues (issue_id));
 joinable!(issue_assignees -> users (user_id));
+joinable!(issue_custom_values -> custom_fields (custom_field_id));
+joinable!(issue_custom_values -> issues (issue_id));
+joinable!(issue_custom_values -> users (user_id));
+joinable!(issue_history -> issues (issue_id));
+joinable!(issue_history -> users (user_id));
+joinable!(issue_labels -> issues (issue_id));
+joinable!(issue_labels -> labels (label_id));
 joinable!(issue_statuses -> projects (project_id));
+joinable!(issue_watchers -> issues (issue_id));
+joinable!(issue_watchers -> users (user_id));
 joinable!(issues -> epics (epic_id));
 joinable!(issues -> issue_statuses (issue_status_id));
 joinable!(issues -> projects (project_id));
+joinable!(issues -> sprints (sprint_id));
 joinable!(issues -> users (reporter_id));
+joinable!(labels -> projects (project_id));
+joinable!(sprints -> projects (project_id));
 joinable!(tokens -> users (user_id));
+joinable!(user_credentials -> users (user_id));
 joinable!(user_projects -> projects (project_id));
 joinable!(user_projects -> users (user_id));
 joinable!(user_settings -> users (user_id));
+joinable!(workflow_transitions -> projects (project_id));
+joinable!(worklogs -> issues (issue_id));
+joinable!(worklogs -> users (user_id));
 
 allow_tables_to_appear_in_same_query!(
     comments,
+    custom_fields,
     epics,
     invitations,
     issue_assignees,
+    issue_custom_values,
+    issue_history,
+    issue_labels,
+    issue_links,
     issue_statuses,
+    issue_watchers,
     issues,
+    labels,
     messages,
     projects,
+    sprints,
     tokens,
+    user_credentials,
     user_projects,
     user_settings,
     users,
+    workflow_transitions,
+    worklogs,
 );
@@ -0,0 +1,203 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{
+    DescriptionString, EndsAt, IssueHistoryField, IssueId, NameString, ProjectId, Sprint, SprintId,
+    SprintState, StartsAt, UserId,
+};
+
+use crate::{db_create, db_find, db_load, db_update};
+
+db_find! {
+    FindSprint,
+    msg => sprints => sprints
+            .filter(project_id.eq(msg.project_id))
+            .find(msg.sprint_id),
+    Sprint,
+    project_id => ProjectId,
+    sprint_id => SprintId
+}
+
+db_load! {
+    LoadSprints,
+    msg => sprints => sprints
+            .distinct_on(id)
+            .filter(project_id.eq(msg.project_id))
+            .order(id.asc()),
+    Sprint,
+    project_id => ProjectId
+}
+
+db_create! {
+    CreateSprint,
+    msg => sprints => diesel::insert_into(sprints).values((
+            project_id.eq(msg.project_id),
+            name.eq(msg.name),
+            goal.eq(msg.goal),
+    )),
+    Sprint,
+    project_id => ProjectId,
+    name => NameString,
+    goal => Option<DescriptionString>
+}
+
+db_update! {
+    StartSprint,
+    msg => sprints => diesel::update(
+        sprints
+            .filter(project_id.eq(msg.project_id))
+            .filter(state.eq(SprintState::Future))
+            .find(msg.sprint_id),
+    ).set((
+        state.eq(SprintState::Active),
+        starts_at.eq(msg.starts_at),
+        ends_at.eq(msg.ends_at),
+        updated_at.eq(chrono::Utc::now().naive_utc()),
+    )),
+    Sprint,
+    project_id => ProjectId,
+    sprint_id => SprintId,
+    starts_at => StartsAt,
+    ends_at => EndsAt
+}
+
+/// Close active sprint. Every issue which is not in last issue status column
+/// is moved to `move_to` sprint or to backlog when `move_to` is `None`.
+/// Each move is recorded in issue history as made by `completed_by`.
+#[derive(Execute)]
+#[db_exec(result = "Sprint", schema = "sprints")]
+pub struct CompleteSprint {
+    pub project_id: ProjectId,
+    pub sprint_id: SprintId,
+    pub move_to: Option<SprintId>,
+    pub completed_by: UserId,
+}
+
+impl CompleteSprint {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Sprint, crate::DatabaseError> {
+        use crate::schema::issues::dsl as issues_dsl;
+        use crate::schema::sprints::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            let done_status_id = crate::issue_statuses::LoadIssueStatuses {
+                project_id: msg.project_id,
+            }
+            .execute(conn)?
+            .into_iter()
+            .max_by_key(|status| status.position)
+            .map(|status| status.id)
+            .unwrap_or_default();
+
+            let moved: Vec<IssueId> = crate::q!(diesel::update(
+                issues_dsl::issues
+                    .filter(issues_dsl::project_id.eq(msg.project_id))
+                    .filter(issues_dsl::sprint_id.eq(msg.sprint_id))
+                    .filter(issues_dsl::issue_status_id.ne(done_status_id)),
+            )
+            .set(issues_dsl::sprint_id.eq(msg.move_to))
+            .returning(issues_dsl::id))
+            .get_results(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Update,
+                    crate::ResourceKind::Issue,
+                )
+            })?;
+            for issue_id in moved {
+                crate::issue_history::CreateIssueHistory {
+                    issue_id,
+                    user_id: msg.completed_by,
+                    changes: vec![(
+                        IssueHistoryField::Sprint,
+                        Some(msg.sprint_id.to_string()),
+                        msg.move_to.map(|id| id.to_string()),
+                    )],
+                }
+                .execute(conn)?;
+            }
+
+            crate::q!(diesel::update(
+                sprints
+                    .filter(project_id.eq(msg.project_id))
+                    .filter(state.eq(SprintState::Active))
+                    .find(msg.sprint_id),
+            )
+            .set((
+                state.eq(SprintState::Completed),
+                completed_at.eq(chrono::Utc::now().naive_utc()),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            )))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Update,
+                    crate::ResourceKind::Sprint,
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+    use crate::issues::tests::project_issue;
+
+    #[test]
+    fn record_moved_issues_in_history() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (project, user, issue) = project_issue(conn, "foo");
+        let sprint = CreateSprint {
+            project_id: project.id,
+            name: "first".to_string(),
+            goal: None,
+        }
+        .execute(conn)
+        .unwrap();
+        let now = chrono::Utc::now().naive_utc();
+        StartSprint {
+            project_id: project.id,
+            sprint_id: sprint.id,
+            starts_at: now,
+            ends_at: now + chrono::Duration::days(14),
+        }
+        .execute(conn)
+        .unwrap();
+        crate::issues::UpdateIssue {
+            project_id: project.id,
+            issue_id: issue.id,
+            sprint_id: Some(Some(sprint.id)),
+            ..Default::default()
+        }
+        .execute(conn)
+        .unwrap();
+        let completed = CompleteSprint {
+            project_id: project.id,
+            sprint_id: sprint.id,
+            move_to: None,
+            completed_by: user.id,
+        }
+        .execute(conn)
+        .unwrap();
+        let history = crate::issue_history::LoadIssueHistory { issue_id: issue.id }
+            .execute(conn)
+            .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(completed.state, SprintState::Completed);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].field, IssueHistoryField::Sprint);
+        assert_eq!(history[0].old_value, Some(sprint.id.to_string()));
+        assert_eq!(history[0].new_value, None);
+    }
+}
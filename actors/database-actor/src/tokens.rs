@@ -1,7 +1,18 @@
+use derive_db_execute::Execute;
 use diesel::prelude::*;
 use jirs_data::{Token, UserId};
 
-use crate::{db_create, db_find, db_update};
+use crate::{db_create, db_delete, db_find, db_load, db_update};
+
+/// Expiration time of access and refresh token issued now
+fn expiration() -> (chrono::NaiveDateTime, chrono::NaiveDateTime) {
+    let config = jirs_config::auth::config();
+    let now = chrono::Utc::now().naive_utc();
+    (
+        now + chrono::Duration::seconds(config.access_token_lifetime),
+        now + chrono::Duration::seconds(config.refresh_token_lifetime),
+    )
+}
 
 db_find! {
     FindUserId,
@@ -21,7 +32,12 @@ db_update! {
     UseBindToken,
     msg => conn => tokens => {
         let token = FindBindToken { token: msg.token }.execute(conn)?;
-        diesel::update(tokens.find(token.id)).set(bind_token.eq(None as Option<uuid::Uuid>))
+        let (access_expires, refresh_expires) = expiration();
+        diesel::update(tokens.find(token.id)).set((
+            bind_token.eq(None as Option<uuid::Uuid>),
+            access_expires_at.eq(access_expires),
+            refresh_expires_at.eq(refresh_expires),
+        ))
     },
     Token,
     token => uuid::Uuid
@@ -29,19 +45,181 @@ db_update! {
 
 db_find! {
     FindAccessToken,
-    msg => tokens => tokens.filter(access_token.eq(msg.token)),
+    msg => tokens => tokens
+        .filter(access_token.eq(msg.token))
+        .filter(access_expires_at.gt(chrono::Utc::now().naive_utc())),
     Token,
     token => uuid::Uuid
 }
 
 db_create! {
     CreateBindToken,
-    msg => tokens => diesel::insert_into(tokens).values((
-        user_id.eq(msg.user_id),
-        access_token.eq(uuid::Uuid::new_v4()),
-        refresh_token.eq(uuid::Uuid::new_v4()),
-        bind_token.eq(Some(uuid::Uuid::new_v4())),
-    )),
+    msg => tokens => {
+        let (access_expires, refresh_expires) = expiration();
+        diesel::insert_into(tokens).values((
+            user_id.eq(msg.user_id),
+            access_token.eq(uuid::Uuid::new_v4()),
+            refresh_token.eq(uuid::Uuid::new_v4()),
+            bind_token.eq(Some(uuid::Uuid::new_v4())),
+            access_expires_at.eq(access_expires),
+            refresh_expires_at.eq(refresh_expires),
+        ))
+    },
+    Token,
+    user_id => UserId
+}
+
+// Session of user who proved identity without single use token
+db_create! {
+    CreateAccessToken,
+    msg => tokens => {
+        let (access_expires, refresh_expires) = expiration();
+        diesel::insert_into(tokens).values((
+            user_id.eq(msg.user_id),
+            access_token.eq(uuid::Uuid::new_v4()),
+            refresh_token.eq(uuid::Uuid::new_v4()),
+            access_expires_at.eq(access_expires),
+            refresh_expires_at.eq(refresh_expires),
+        ))
+    },
+    Token,
+    user_id => UserId
+}
+
+/// Replace both access and refresh token of session with not expired refresh
+/// token
+#[derive(Execute)]
+#[db_exec(result = "Token", schema = "tokens")]
+pub struct RefreshToken {
+    pub refresh_token: uuid::Uuid,
+}
+
+impl RefreshToken {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Token, crate::DatabaseError> {
+        use crate::schema::tokens::dsl::*;
+        let msg = self;
+
+        let (access_expires, refresh_expires) = expiration();
+        crate::q!(diesel::update(
+            tokens
+                .filter(refresh_token.eq(msg.refresh_token))
+                .filter(bind_token.is_null())
+                .filter(refresh_expires_at.gt(chrono::Utc::now().naive_utc())),
+        )
+        .set((
+            access_token.eq(uuid::Uuid::new_v4()),
+            refresh_token.eq(uuid::Uuid::new_v4()),
+            access_expires_at.eq(access_expires),
+            refresh_expires_at.eq(refresh_expires),
+            updated_at.eq(chrono::Utc::now().naive_utc()),
+        )))
+        .get_result(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::Token(crate::TokenError::FailedToRefresh)
+        })
+    }
+}
+
+db_load! {
+    LoadUserSessions,
+    msg => tokens => tokens
+        .filter(user_id.eq(msg.user_id))
+        .filter(bind_token.is_null())
+        .filter(refresh_expires_at.gt(chrono::Utc::now().naive_utc()))
+        .order_by(updated_at.desc()),
+    Token,
+    user_id => UserId
+}
+
+db_delete! {
+    DeleteUserTokens,
+    msg => tokens => diesel::delete(tokens.filter(user_id.eq(msg.user_id))),
     Token,
     user_id => UserId
 }
+
+/// Remove sessions which can't be refreshed anymore
+#[derive(Execute)]
+#[db_exec(result = "usize", schema = "tokens")]
+pub struct DeleteExpiredTokens;
+
+impl DeleteExpiredTokens {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::tokens::dsl::*;
+
+        crate::q!(diesel::delete(
+            tokens.filter(refresh_expires_at.lt(chrono::Utc::now().naive_utc()))
+        ))
+        .execute(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Delete,
+                crate::ResourceKind::Token,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+
+    #[test]
+    fn refresh_replaces_expired_access_token() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let user = crate::users::CreateUser {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+        }
+        .execute(conn)
+        .unwrap();
+        let created = CreateBindToken { user_id: user.id }.execute(conn).unwrap();
+        let token = UseBindToken {
+            token: created.bind_token.unwrap(),
+        }
+        .execute(conn)
+        .unwrap();
+        {
+            use crate::schema::tokens::dsl::*;
+            diesel::update(tokens.find(token.id))
+                .set(access_expires_at.eq(chrono::Utc::now().naive_utc()))
+                .execute(conn)
+                .unwrap();
+        }
+
+        let expired = FindAccessToken {
+            token: token.access_token,
+        }
+        .execute(conn);
+        let refreshed = RefreshToken {
+            refresh_token: token.refresh_token,
+        }
+        .execute(conn)
+        .unwrap();
+        let reused = RefreshToken {
+            refresh_token: token.refresh_token,
+        }
+        .execute(conn);
+        let found = FindAccessToken {
+            token: refreshed.access_token,
+        }
+        .execute(conn);
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(expired.is_err());
+        assert_ne!(refreshed.access_token, token.access_token);
+        assert_ne!(refreshed.refresh_token, token.refresh_token);
+        assert!(reused.is_err());
+        assert_eq!(found.unwrap().id, token.id);
+    }
+}
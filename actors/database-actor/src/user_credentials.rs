@@ -0,0 +1,503 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use hmac::{Hmac, Mac, NewMac};
+use jirs_data::{EmailString, User, UserId, UsernameString};
+use sha1::Sha1;
+
+use crate::models::UserCredential;
+use crate::users::LookupUser;
+use crate::{db_find, DatabaseError, OperationError, ResourceKind, UserError};
+
+/// Seconds for which single time based code is valid
+const TOTP_STEP: u64 = 30;
+const TOTP_ALPHABET: base32::Alphabet = base32::Alphabet::RFC4648 { padding: false };
+
+db_find! {
+    FindUserCredential,
+    msg => user_credentials => user_credentials.filter(user_id.eq(msg.user_id)),
+    UserCredential,
+    user_id => UserId
+}
+
+// Queries below are not passed to `q!` because they contain secrets which
+// should never be written to logs
+
+/// Replace user password with new argon2 hash
+#[derive(Execute)]
+#[db_exec(result = "UserCredential", schema = "user_credentials")]
+pub struct SetPassword {
+    pub user_id: UserId,
+    pub password: String,
+}
+
+impl SetPassword {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<UserCredential, DatabaseError> {
+        use crate::schema::user_credentials::dsl::*;
+        let msg = self;
+
+        let hash = hash_password(msg.password.as_str())?;
+        diesel::insert_into(user_credentials)
+            .values((
+                user_id.eq(msg.user_id),
+                password_hash.eq(Some(hash.as_str())),
+            ))
+            .on_conflict(user_id)
+            .do_update()
+            .set((
+                password_hash.eq(Some(hash.as_str())),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                DatabaseError::GenericFailure(OperationError::Update, ResourceKind::UserCredential)
+            })
+    }
+}
+
+/// Replace password of signed in user. Current password is required and, if
+/// two factor authentication is enabled, code from authenticator application
+/// as well. User without any credentials sets first password freely
+#[derive(Execute)]
+#[db_exec(result = "UserCredential", schema = "user_credentials")]
+pub struct ChangePassword {
+    pub user_id: UserId,
+    pub current_password: Option<String>,
+    pub totp: Option<String>,
+    pub password: String,
+}
+
+impl ChangePassword {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<UserCredential, DatabaseError> {
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            if let Ok(credential) = (FindUserCredential {
+                user_id: msg.user_id,
+            })
+            .execute(conn)
+            {
+                let password_matches = match (
+                    credential.password_hash.as_deref(),
+                    msg.current_password.as_ref(),
+                ) {
+                    (Some(hash), Some(password)) => {
+                        argon2::verify_encoded(hash, password.as_bytes()).unwrap_or_default()
+                    }
+                    _ => false,
+                };
+                let code_matches = match (
+                    credential.totp_enabled,
+                    credential.totp_secret.as_deref(),
+                    msg.totp.as_ref(),
+                ) {
+                    (true, Some(secret), Some(code)) => {
+                        verify_totp(secret, code.as_str(), unix_now())
+                    }
+                    _ => false,
+                };
+                if credential.password_hash.is_some() && !password_matches {
+                    return Err(DatabaseError::User(UserError::InvalidCredentials));
+                }
+                if credential.totp_enabled && !code_matches {
+                    return Err(DatabaseError::User(UserError::InvalidTotpCode));
+                }
+            }
+            SetPassword {
+                user_id: msg.user_id,
+                password: msg.password,
+            }
+            .execute(conn)
+        })
+    }
+}
+
+/// Find user by name, e-mail and password. If user enabled two factor
+/// authentication code from authenticator application is also required
+#[derive(Execute)]
+#[db_exec(result = "User", schema = "users")]
+pub struct VerifyCredentials {
+    pub name: UsernameString,
+    pub email: EmailString,
+    pub password: String,
+    pub totp: Option<String>,
+}
+
+impl VerifyCredentials {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<User, DatabaseError> {
+        let msg = self;
+        let invalid = || DatabaseError::User(UserError::InvalidCredentials);
+
+        let user = LookupUser {
+            name: msg.name,
+            email: msg.email,
+        }
+        .execute(conn)
+        .map_err(|_| invalid())?;
+        let credential = FindUserCredential { user_id: user.id }
+            .execute(conn)
+            .map_err(|_| invalid())?;
+        let hash = credential.password_hash.as_deref().ok_or_else(invalid)?;
+        if !argon2::verify_encoded(hash, msg.password.as_bytes()).unwrap_or_default() {
+            return Err(invalid());
+        }
+
+        if credential.totp_enabled {
+            let code = msg
+                .totp
+                .ok_or(DatabaseError::User(UserError::TotpRequired))?;
+            let secret = credential.totp_secret.unwrap_or_default();
+            if !verify_totp(secret.as_str(), code.as_str(), unix_now()) {
+                return Err(DatabaseError::User(UserError::InvalidTotpCode));
+            }
+        }
+        Ok(user)
+    }
+}
+
+/// Generate new not yet enabled secret for authenticator application.
+/// Enabled secret can't be replaced, it must be disabled first
+#[derive(Execute)]
+#[db_exec(result = "UserCredential", schema = "user_credentials")]
+pub struct SetupTotp {
+    pub user_id: UserId,
+}
+
+impl SetupTotp {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<UserCredential, DatabaseError> {
+        use crate::schema::user_credentials::dsl::*;
+        let msg = self;
+
+        let enabled = FindUserCredential {
+            user_id: msg.user_id,
+        }
+        .execute(conn)
+        .map(|credential| credential.totp_enabled)
+        .unwrap_or_default();
+        if enabled {
+            return Err(DatabaseError::GenericFailure(
+                OperationError::Update,
+                ResourceKind::UserCredential,
+            ));
+        }
+
+        let secret = base32::encode(TOTP_ALPHABET, &rand::random::<[u8; 20]>());
+        diesel::insert_into(user_credentials)
+            .values((
+                user_id.eq(msg.user_id),
+                totp_secret.eq(Some(secret.as_str())),
+                totp_enabled.eq(false),
+            ))
+            .on_conflict(user_id)
+            .do_update()
+            .set((
+                totp_secret.eq(Some(secret.as_str())),
+                totp_enabled.eq(false),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                DatabaseError::GenericFailure(OperationError::Update, ResourceKind::UserCredential)
+            })
+    }
+}
+
+/// Turn on two factor authentication if given code matches generated secret
+#[derive(Execute)]
+#[db_exec(result = "UserCredential", schema = "user_credentials")]
+pub struct EnableTotp {
+    pub user_id: UserId,
+    pub code: String,
+}
+
+impl EnableTotp {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<UserCredential, DatabaseError> {
+        use crate::schema::user_credentials::dsl::*;
+        let msg = self;
+
+        let credential = FindUserCredential {
+            user_id: msg.user_id,
+        }
+        .execute(conn)?;
+        let secret = credential.totp_secret.unwrap_or_default();
+        if !verify_totp(secret.as_str(), msg.code.as_str(), unix_now()) {
+            return Err(DatabaseError::User(UserError::InvalidTotpCode));
+        }
+        diesel::update(user_credentials.find(credential.id))
+            .set((
+                totp_enabled.eq(true),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                DatabaseError::GenericFailure(OperationError::Update, ResourceKind::UserCredential)
+            })
+    }
+}
+
+/// Turn off two factor authentication, current code is required
+#[derive(Execute)]
+#[db_exec(result = "UserCredential", schema = "user_credentials")]
+pub struct DisableTotp {
+    pub user_id: UserId,
+    pub code: String,
+}
+
+impl DisableTotp {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<UserCredential, DatabaseError> {
+        use crate::schema::user_credentials::dsl::*;
+        let msg = self;
+
+        let credential = FindUserCredential {
+            user_id: msg.user_id,
+        }
+        .execute(conn)?;
+        let secret = credential.totp_secret.unwrap_or_default();
+        if !credential.totp_enabled || !verify_totp(secret.as_str(), msg.code.as_str(), unix_now())
+        {
+            return Err(DatabaseError::User(UserError::InvalidTotpCode));
+        }
+        diesel::update(user_credentials.find(credential.id))
+            .set((
+                totp_secret.eq(None as Option<String>),
+                totp_enabled.eq(false),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                DatabaseError::GenericFailure(OperationError::Update, ResourceKind::UserCredential)
+            })
+    }
+}
+
+/// Link which can be scanned by authenticator application
+pub fn totp_url(issuer: &str, account: &str, secret: &str) -> String {
+    use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+    let issuer = utf8_percent_encode(issuer, NON_ALPHANUMERIC).to_string();
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}",
+        issuer = issuer,
+        account = utf8_percent_encode(account, NON_ALPHANUMERIC),
+        secret = secret,
+    )
+}
+
+fn hash_password(password: &str) -> Result<String, DatabaseError> {
+    let config = argon2::Config {
+        variant: argon2::Variant::Argon2id,
+        ..argon2::Config::default()
+    };
+    argon2::hash_encoded(password.as_bytes(), &rand::random::<[u8; 16]>(), &config).map_err(|e| {
+        log::error!("{:?}", e);
+        DatabaseError::GenericFailure(OperationError::Update, ResourceKind::UserCredential)
+    })
+}
+
+fn unix_now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// Accept code of current, previous and next step to tolerate clock drift
+fn verify_totp(secret: &str, code: &str, timestamp: u64) -> bool {
+    let secret = match base32::decode(TOTP_ALPHABET, secret) {
+        Some(secret) if !secret.is_empty() => secret,
+        _ => return false,
+    };
+    let code = code.trim();
+    let code: u32 = match code.parse() {
+        Ok(n) if code.len() == 6 => n,
+        _ => return false,
+    };
+    let counter = timestamp / TOTP_STEP;
+    (counter.saturating_sub(1)..=counter + 1).any(|counter| hotp(&secret, counter) == code)
+}
+
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_varkey(secret).expect("HMAC accepts key of any size");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let code = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    code % 1_000_000
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+
+    use super::*;
+    use crate::build_pool;
+
+    #[test]
+    fn generate_rfc_6238_codes() {
+        let secret = b"12345678901234567890";
+        assert_eq!(hotp(secret, 59 / TOTP_STEP), 287082);
+        assert_eq!(hotp(secret, 1111111109 / TOTP_STEP), 81804);
+
+        let encoded = base32::encode(TOTP_ALPHABET, secret);
+        assert!(verify_totp(encoded.as_str(), "287082", 59));
+        assert!(verify_totp(
+            encoded.as_str(),
+            "081804",
+            1111111109 + TOTP_STEP
+        ));
+        assert!(!verify_totp(encoded.as_str(), "81804", 1111111109));
+        assert!(!verify_totp(encoded.as_str(), "287082", 59 + 3 * TOTP_STEP));
+    }
+
+    #[test]
+    fn verify_password_and_totp() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let user = crate::users::CreateUser {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+        }
+        .execute(conn)
+        .unwrap();
+        let verify = |password: &str, totp: Option<String>| {
+            VerifyCredentials {
+                name: "foo".to_string(),
+                email: "foo@example.com".to_string(),
+                password: password.to_string(),
+                totp,
+            }
+            .execute(conn)
+        };
+
+        let without_password = verify("secret password", None);
+        SetPassword {
+            user_id: user.id,
+            password: "secret password".to_string(),
+        }
+        .execute(conn)
+        .unwrap();
+        let bad_password = verify("wrong password", None);
+        let signed_in = verify("secret password", None);
+
+        let secret = SetupTotp { user_id: user.id }
+            .execute(conn)
+            .unwrap()
+            .totp_secret
+            .unwrap();
+        let decoded = base32::decode(TOTP_ALPHABET, secret.as_str()).unwrap();
+        let code = format!("{:06}", hotp(&decoded, unix_now() / TOTP_STEP));
+        EnableTotp {
+            user_id: user.id,
+            code: code.clone(),
+        }
+        .execute(conn)
+        .unwrap();
+        let replaced = SetupTotp { user_id: user.id }.execute(conn);
+        let missing_code = verify("secret password", None);
+        let with_code = verify("secret password", Some(code));
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(without_password.is_err());
+        assert!(bad_password.is_err());
+        assert_eq!(signed_in.unwrap().id, user.id);
+        assert!(replaced.is_err());
+        assert!(matches!(
+            missing_code,
+            Err(DatabaseError::User(UserError::TotpRequired))
+        ));
+        assert_eq!(with_code.unwrap().id, user.id);
+    }
+
+    #[test]
+    fn require_current_password_and_code_on_change() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let user = crate::users::CreateUser {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+        }
+        .execute(conn)
+        .unwrap();
+        let change = |current: Option<&str>, totp: Option<String>, password: &str| {
+            ChangePassword {
+                user_id: user.id,
+                current_password: current.map(|s| s.to_string()),
+                totp,
+                password: password.to_string(),
+            }
+            .execute(conn)
+        };
+
+        let first = change(None, None, "first password");
+        let missing_password = change(None, None, "second password");
+        let wrong_password = change(Some("wrong password"), None, "second password");
+        let second = change(Some("first password"), None, "second password");
+
+        let secret = SetupTotp { user_id: user.id }
+            .execute(conn)
+            .unwrap()
+            .totp_secret
+            .unwrap();
+        let decoded = base32::decode(TOTP_ALPHABET, secret.as_str()).unwrap();
+        let code = format!("{:06}", hotp(&decoded, unix_now() / TOTP_STEP));
+        EnableTotp {
+            user_id: user.id,
+            code: code.clone(),
+        }
+        .execute(conn)
+        .unwrap();
+        let code_only = change(None, Some(code.clone()), "third password");
+        let password_only = change(Some("second password"), None, "third password");
+        let wrong_code = change(
+            Some("second password"),
+            Some("000000".to_string()),
+            "third password",
+        );
+        let third = change(Some("second password"), Some(code), "third password");
+        let signed_in = VerifyCredentials {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+            password: "second password".to_string(),
+            totp: None,
+        }
+        .execute(conn);
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(first.is_ok());
+        assert!(matches!(
+            missing_password,
+            Err(DatabaseError::User(UserError::InvalidCredentials))
+        ));
+        assert!(matches!(
+            wrong_password,
+            Err(DatabaseError::User(UserError::InvalidCredentials))
+        ));
+        assert!(second.is_ok());
+        assert!(matches!(
+            code_only,
+            Err(DatabaseError::User(UserError::InvalidCredentials))
+        ));
+        assert!(matches!(
+            password_only,
+            Err(DatabaseError::User(UserError::InvalidTotpCode))
+        ));
+        assert!(matches!(
+            wrong_code,
+            Err(DatabaseError::User(UserError::InvalidTotpCode))
+        ));
+        assert!(third.is_ok());
+        assert!(signed_in.is_err());
+    }
+}
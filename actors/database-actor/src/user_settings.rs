@@ -1,5 +1,5 @@
 use diesel::prelude::*;
-use jirs_data::{TextEditorMode, UserId, UserSetting};
+use jirs_data::{BoardGrouping, NotificationMode, TextEditorMode, UserId, UserSetting};
 
 use crate::{db_find, db_update};
 
@@ -28,9 +28,39 @@ db_update! {
     mode => TextEditorMode
 }
 
+db_update! {
+    UpdateBoardGrouping,
+    msg => conn => user_settings => {
+        inner::UpdateGrouping { user_id: msg.user_id, grouping: msg.grouping }
+        .execute(conn).or_else(|_|
+            inner::CreateGrouping { user_id: msg.user_id, grouping: msg.grouping }
+            .execute(conn)
+        )?;
+        user_settings.filter(user_id.eq(msg.user_id))
+    },
+    UserSetting,
+    user_id => UserId,
+    grouping => BoardGrouping
+}
+
+db_update! {
+    UpdateNotificationMode,
+    msg => conn => user_settings => {
+        inner::UpdateNotification { user_id: msg.user_id, mode: msg.mode }
+        .execute(conn).or_else(|_|
+            inner::CreateNotification { user_id: msg.user_id, mode: msg.mode }
+            .execute(conn)
+        )?;
+        user_settings.filter(user_id.eq(msg.user_id))
+    },
+    UserSetting,
+    user_id => UserId,
+    mode => NotificationMode
+}
+
 mod inner {
     use diesel::prelude::*;
-    use jirs_data::{TextEditorMode, UserId, UserSetting};
+    use jirs_data::{BoardGrouping, NotificationMode, TextEditorMode, UserId, UserSetting};
 
     use crate::{db_create, db_update};
 
@@ -54,4 +84,46 @@ mod inner {
         user_id => UserId,
         mode => TextEditorMode
     }
+
+    db_update! {
+        UpdateGrouping,
+        msg => user_settings => {
+            diesel::update(user_settings.filter(user_id.eq(msg.user_id))).set(board_grouping.eq(msg.grouping))
+        },
+        UserSetting,
+        user_id => UserId,
+        grouping => BoardGrouping
+    }
+
+    db_create! {
+        CreateGrouping,
+        msg => user_settings => diesel::insert_into(user_settings).values((
+            user_id.eq(msg.user_id),
+            board_grouping.eq(msg.grouping)
+        )),
+        UserSetting,
+        user_id => UserId,
+        grouping => BoardGrouping
+    }
+
+    db_update! {
+        UpdateNotification,
+        msg => user_settings => {
+            diesel::update(user_settings.filter(user_id.eq(msg.user_id))).set(notification_mode.eq(msg.mode))
+        },
+        UserSetting,
+        user_id => UserId,
+        mode => NotificationMode
+    }
+
+    db_create! {
+        CreateNotification,
+        msg => user_settings => diesel::insert_into(user_settings).values((
+            user_id.eq(msg.user_id),
+            notification_mode.eq(msg.mode)
+        )),
+        UserSetting,
+        user_id => UserId,
+        mode => NotificationMode
+    }
 }
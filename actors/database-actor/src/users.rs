@@ -98,13 +98,22 @@ db_create! {
             role: msg.role,
         }
         .execute(conn)?;
+
+        if let Some(password) = msg.password {
+            crate::user_credentials::SetPassword {
+                user_id: user.id,
+                password,
+            }
+            .execute(conn)?;
+        }
         users.find(user.id)
     },
     User,
     name => UsernameString,
     email => EmailString,
     project_id => Option<ProjectId>,
-    role => UserRole
+    role => UserRole,
+    password => Option<String>
 }
 
 db_load! {
@@ -224,4 +233,33 @@ mod tests {
         assert_eq!(res2, 1);
         assert_eq!(res3, 1);
     }
+
+    #[test]
+    fn register_with_password() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let user = Register {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+            project_id: None,
+            role: UserRole::Owner,
+            password: Some("secret password".to_string()),
+        }
+        .execute(conn)
+        .unwrap();
+        let signed_in = crate::user_credentials::VerifyCredentials {
+            name: "foo".to_string(),
+            email: "foo@example.com".to_string(),
+            password: "secret password".to_string(),
+            totp: None,
+        }
+        .execute(conn);
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(signed_in.unwrap().id, user.id);
+    }
 }
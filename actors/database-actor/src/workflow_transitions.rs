@@ -0,0 +1,45 @@
+use diesel::prelude::*;
+use jirs_data::{IssueStatusId, ProjectId, UserRole, WorkflowTransition, WorkflowTransitionId};
+
+use crate::{db_create, db_delete, db_load};
+
+db_load! {
+    LoadWorkflowTransitions,
+    msg => workflow_transitions => workflow_transitions
+            .distinct_on(id)
+            .filter(project_id.eq(msg.project_id))
+            .order(id.asc()),
+    WorkflowTransition,
+    project_id => ProjectId
+}
+
+db_create! {
+    CreateWorkflowTransition,
+    msg => workflow_transitions => diesel::insert_into(workflow_transitions).values((
+            project_id.eq(msg.project_id),
+            from_status_id.eq(msg.from_status_id),
+            to_status_id.eq(msg.to_status_id),
+            required_role.eq(msg.required_role),
+            require_assignee.eq(msg.require_assignee),
+            require_estimate.eq(msg.require_estimate),
+    )),
+    WorkflowTransition,
+    project_id => ProjectId,
+    from_status_id => IssueStatusId,
+    to_status_id => IssueStatusId,
+    required_role => Option<UserRole>,
+    require_assignee => bool,
+    require_estimate => bool
+}
+
+db_delete! {
+    DeleteWorkflowTransition,
+    msg => workflow_transitions => diesel::delete(
+            workflow_transitions
+                .filter(project_id.eq(msg.project_id))
+                .find(msg.workflow_transition_id),
+        ),
+    WorkflowTransition,
+    project_id => ProjectId,
+    workflow_transition_id => WorkflowTransitionId
+}
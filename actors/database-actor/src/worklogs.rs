@@ -0,0 +1,379 @@
+use derive_db_execute::Execute;
+use diesel::prelude::*;
+use jirs_data::{IssueId, ProjectId, StartsAt, UserId, Worklog, WorklogId};
+
+use crate::models::Issue;
+use crate::schema::issues::dsl as issues_dsl;
+use crate::{db_find, db_load};
+
+db_find! {
+    FindWorklog,
+    msg => worklogs => worklogs
+            .filter(issue_id.eq_any(
+                issues_dsl::issues
+                    .select(issues_dsl::id)
+                    .filter(issues_dsl::project_id.eq(msg.project_id)),
+            ))
+            .find(msg.worklog_id),
+    Worklog,
+    project_id => ProjectId,
+    worklog_id => WorklogId
+}
+
+db_load! {
+    LoadWorklogs,
+    msg => worklogs => worklogs
+            .distinct_on((started_at, id))
+            .filter(issue_id.eq(msg.issue_id))
+            .order((started_at.asc(), id.asc())),
+    Worklog,
+    issue_id => IssueId
+}
+
+db_load! {
+    LoadProjectWorklogs,
+    msg => worklogs => worklogs
+            .distinct_on((started_at, id))
+            .filter(issue_id.eq_any(
+                issues_dsl::issues
+                    .select(issues_dsl::id)
+                    .filter(issues_dsl::project_id.eq(msg.project_id)),
+            ))
+            .order((started_at.asc(), id.asc())),
+    Worklog,
+    project_id => ProjectId
+}
+
+/// Sum of issue worklogs durations, `None` when issue has no worklogs
+pub(crate) fn worklogs_duration(
+    conn: &crate::DbPooledConn,
+    issue_id: IssueId,
+) -> Result<Option<i32>, crate::DatabaseError> {
+    let durations: Vec<i32> = LoadWorklogs { issue_id }
+        .execute(conn)?
+        .into_iter()
+        .map(|worklog| worklog.duration)
+        .collect();
+    Ok(match durations.is_empty() {
+        true => None,
+        false => Some(durations.into_iter().sum::<i32>()),
+    })
+}
+
+/// Replace issue time spent with sum of its worklogs and refresh parent
+/// tracking values. Issue without worklogs has no time spent. Time spent of
+/// issue with sub-tasks is rolled up and includes its own worklogs.
+#[derive(Execute)]
+#[db_exec(result = "Issue", schema = "issues")]
+pub struct SyncIssueTimeSpent {
+    pub issue_id: IssueId,
+}
+
+impl SyncIssueTimeSpent {
+    pub fn execute(self, conn: &crate::DbPooledConn) -> Result<Issue, crate::DatabaseError> {
+        use crate::schema::issues::dsl::*;
+        let msg = self;
+
+        let has_subtasks = !crate::issues::LoadSubtasks {
+            parent_id: msg.issue_id,
+        }
+        .execute(conn)?
+        .is_empty();
+        if has_subtasks {
+            return crate::issues::RollUpIssueTracking {
+                issue_id: msg.issue_id,
+            }
+            .execute(conn);
+        }
+
+        let total = worklogs_duration(conn, msg.issue_id)?;
+        let issue: Issue = crate::q!(diesel::update(issues.find(msg.issue_id)).set((
+            time_spent.eq(total),
+            updated_at.eq(chrono::Utc::now().naive_utc()),
+        )))
+        .get_result(conn)
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            crate::DatabaseError::GenericFailure(
+                crate::OperationError::Update,
+                crate::ResourceKind::Issue,
+            )
+        })?;
+        if let Some(parent) = issue.parent_id {
+            crate::issues::RollUpIssueTracking { issue_id: parent }.execute(conn)?;
+        }
+        Ok(issue)
+    }
+}
+
+/// Create worklog only if issue belongs to given project
+#[derive(Execute)]
+#[db_exec(result = "Worklog", schema = "worklogs")]
+pub struct CreateWorklog {
+    pub project_id: ProjectId,
+    pub issue_id: IssueId,
+    pub user_id: UserId,
+    pub duration: i32,
+    pub started_at: StartsAt,
+    pub comment: Option<String>,
+}
+
+impl CreateWorklog {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Worklog, crate::DatabaseError> {
+        use crate::schema::worklogs::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            crate::issues::FindProjectIssue {
+                project_id: msg.project_id,
+                issue_id: msg.issue_id,
+            }
+            .execute(conn)?;
+            let worklog: Worklog = crate::q!(diesel::insert_into(worklogs).values((
+                issue_id.eq(msg.issue_id),
+                user_id.eq(msg.user_id),
+                duration.eq(msg.duration),
+                started_at.eq(msg.started_at),
+                comment.eq(msg.comment),
+            )))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Create,
+                    crate::ResourceKind::Worklog,
+                )
+            })?;
+            SyncIssueTimeSpent {
+                issue_id: worklog.issue_id,
+            }
+            .execute(conn)?;
+            Ok(worklog)
+        })
+    }
+}
+
+/// Change worklog of project issue, only its author can do it
+#[derive(Execute)]
+#[db_exec(result = "Worklog", schema = "worklogs")]
+pub struct UpdateWorklog {
+    pub project_id: ProjectId,
+    pub worklog_id: WorklogId,
+    pub user_id: UserId,
+    pub duration: i32,
+    pub started_at: StartsAt,
+    pub comment: Option<String>,
+}
+
+impl UpdateWorklog {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<Worklog, crate::DatabaseError> {
+        use crate::schema::worklogs::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            let worklog: Worklog = crate::q!(diesel::update(
+                worklogs
+                    .filter(user_id.eq(msg.user_id))
+                    .filter(
+                        issue_id.eq_any(
+                            issues_dsl::issues
+                                .select(issues_dsl::id)
+                                .filter(issues_dsl::project_id.eq(msg.project_id)),
+                        ),
+                    )
+                    .find(msg.worklog_id)
+            )
+            .set((
+                duration.eq(msg.duration),
+                started_at.eq(msg.started_at),
+                comment.eq(msg.comment),
+                updated_at.eq(chrono::Utc::now().naive_utc()),
+            )))
+            .get_result(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Update,
+                    crate::ResourceKind::Worklog,
+                )
+            })?;
+            SyncIssueTimeSpent {
+                issue_id: worklog.issue_id,
+            }
+            .execute(conn)?;
+            Ok(worklog)
+        })
+    }
+}
+
+/// Remove worklog of project issue, only its author can do it
+#[derive(Execute)]
+#[db_exec(result = "usize", schema = "worklogs")]
+pub struct DeleteWorklog {
+    pub project_id: ProjectId,
+    pub worklog_id: WorklogId,
+    pub user_id: UserId,
+}
+
+impl DeleteWorklog {
+    fn execute(self, conn: &crate::DbPooledConn) -> Result<usize, crate::DatabaseError> {
+        use crate::schema::worklogs::dsl::*;
+        let msg = self;
+
+        crate::Guard::new(conn)?.run(|_guard| {
+            let worklog = FindWorklog {
+                project_id: msg.project_id,
+                worklog_id: msg.worklog_id,
+            }
+            .execute(conn)?;
+            let n = crate::q!(diesel::delete(
+                worklogs
+                    .filter(user_id.eq(msg.user_id))
+                    .filter(issue_id.eq(worklog.issue_id))
+                    .find(msg.worklog_id)
+            ))
+            .execute(conn)
+            .map_err(|e| {
+                log::error!("{:?}", e);
+                crate::DatabaseError::GenericFailure(
+                    crate::OperationError::Delete,
+                    crate::ResourceKind::Worklog,
+                )
+            })?;
+            SyncIssueTimeSpent {
+                issue_id: worklog.issue_id,
+            }
+            .execute(conn)?;
+            Ok(n)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::TransactionManager;
+    use jirs_data::{IssuePriority, IssueType};
+
+    use super::*;
+    use crate::build_pool;
+    use crate::issues::tests::project_issue;
+
+    #[test]
+    fn keep_subtasks_time_spent_in_parent() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (project, user, parent) = project_issue(conn, "foo");
+        let subtask = crate::issues::CreateIssue {
+            title: "subtask".to_string(),
+            issue_type: IssueType::Subtask,
+            issue_status_id: 0,
+            priority: IssuePriority::Medium,
+            description: None,
+            description_text: None,
+            estimate: None,
+            time_spent: None,
+            time_remaining: None,
+            project_id: project.id,
+            reporter_id: user.id,
+            user_ids: vec![],
+            epic_id: None,
+            parent_id: Some(parent.id),
+            custom_values: vec![],
+        }
+        .execute(conn)
+        .unwrap();
+        let log = |issue_id, duration| {
+            CreateWorklog {
+                project_id: project.id,
+                issue_id,
+                user_id: user.id,
+                duration,
+                started_at: chrono::Utc::now().naive_utc(),
+                comment: None,
+            }
+            .execute(conn)
+            .unwrap()
+        };
+        log(subtask.id, 3);
+        log(parent.id, 2);
+        log(subtask.id, 4);
+        let parent = crate::issues::LoadIssue {
+            issue_id: parent.id,
+        }
+        .execute(conn)
+        .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert_eq!(parent.time_spent, Some(9));
+    }
+
+    #[test]
+    fn reject_cross_project_worklog_access() {
+        let pool = build_pool();
+        let conn = &pool.get().unwrap();
+        let tm = conn.transaction_manager();
+        tm.begin_transaction(conn).unwrap();
+
+        let (foo, foo_user, _) = project_issue(conn, "foo");
+        let (bar, bar_user, bar_issue) = project_issue(conn, "bar");
+        let worklog = CreateWorklog {
+            project_id: bar.id,
+            issue_id: bar_issue.id,
+            user_id: bar_user.id,
+            duration: 5,
+            started_at: chrono::Utc::now().naive_utc(),
+            comment: None,
+        }
+        .execute(conn)
+        .unwrap();
+
+        let created = CreateWorklog {
+            project_id: foo.id,
+            issue_id: bar_issue.id,
+            user_id: foo_user.id,
+            duration: 1,
+            started_at: chrono::Utc::now().naive_utc(),
+            comment: None,
+        }
+        .execute(conn);
+        let found = FindWorklog {
+            project_id: foo.id,
+            worklog_id: worklog.id,
+        }
+        .execute(conn);
+        let updated = UpdateWorklog {
+            project_id: foo.id,
+            worklog_id: worklog.id,
+            user_id: bar_user.id,
+            duration: 50,
+            started_at: worklog.started_at,
+            comment: None,
+        }
+        .execute(conn);
+        let deleted = DeleteWorklog {
+            project_id: foo.id,
+            worklog_id: worklog.id,
+            user_id: bar_user.id,
+        }
+        .execute(conn)
+        .unwrap_or_default();
+        let own = LoadWorklogs {
+            issue_id: bar_issue.id,
+        }
+        .execute(conn)
+        .unwrap();
+
+        tm.rollback_transaction(conn).unwrap();
+
+        assert!(created.is_err());
+        assert!(found.is_err());
+        assert!(updated.is_err());
+        assert_eq!(deleted, 0);
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].duration, 5);
+    }
+}
@@ -0,0 +1,155 @@
+use actix::{Handler, Message};
+
+use crate::MailExecutor;
+
+/// Event which message is informing about
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NotificationKind {
+    Assigned,
+    Mentioned,
+    Watched,
+}
+
+impl NotificationKind {
+    fn heading(&self, sender_name: &str) -> String {
+        match self {
+            NotificationKind::Assigned => format!("{} assigned you to issue", sender_name),
+            NotificationKind::Mentioned => format!("{} mentioned you", sender_name),
+            NotificationKind::Watched => format!("{} updated issue you are watching", sender_name),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationItem {
+    pub kind: NotificationKind,
+    pub sender_name: String,
+    pub summary: String,
+    pub description: String,
+    /// Application path, for example `/issues/1`
+    pub link: Option<String>,
+}
+
+/// Single message sent right after it was created
+#[derive(Debug)]
+pub struct Notify {
+    pub email: String,
+    pub receiver_name: String,
+    pub item: NotificationItem,
+}
+
+impl Message for Notify {
+    type Result = Result<(), String>;
+}
+
+impl Handler<Notify> for MailExecutor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: Notify, _ctx: &mut Self::Context) -> Self::Result {
+        let addr = jirs_config::web::Configuration::read().full_addr();
+        let subject = format!("JIRS: {}", msg.item.kind.heading(&msg.item.sender_name));
+        let html = layout(
+            msg.receiver_name.as_str(),
+            item_html(&msg.item, addr.as_str()).as_str(),
+        );
+        self.send_html(msg.email.as_str(), subject.as_str(), html.as_str())
+    }
+}
+
+/// All messages collected since last digest
+#[derive(Debug)]
+pub struct Digest {
+    pub email: String,
+    pub receiver_name: String,
+    pub items: Vec<NotificationItem>,
+}
+
+impl Message for Digest {
+    type Result = Result<(), String>;
+}
+
+impl Handler<Digest> for MailExecutor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: Digest, _ctx: &mut Self::Context) -> Self::Result {
+        let addr = jirs_config::web::Configuration::read().full_addr();
+        let subject = format!("JIRS: {} new notifications", msg.items.len());
+        let body: String = msg
+            .items
+            .iter()
+            .map(|item| item_html(item, addr.as_str()))
+            .collect();
+        let html = layout(msg.receiver_name.as_str(), body.as_str());
+        self.send_html(msg.email.as_str(), subject.as_str(), html.as_str())
+    }
+}
+
+impl MailExecutor {
+    fn send_html(&mut self, to: &str, subject: &str, html: &str) -> Result<(), String> {
+        use lettre::Transport;
+
+        let email = lettre_email::Email::builder()
+            .from(self.config.from.as_str())
+            .to(to)
+            .html(html)
+            .subject(subject)
+            .build()
+            .map_err(|_| "Email is not valid".to_string())?;
+
+        self.transport
+            .send(email.into())
+            .map(|_| ())
+            .map_err(|e| format!("Mailer: {}", e))
+    }
+}
+
+fn layout(receiver_name: &str, body: &str) -> String {
+    format!(
+        r#"
+            <!DOCTYPE html>
+            <html>
+            <head><meta charset="UTF-8"></head>
+            <body>
+            <h1>Hello {receiver_name}!</h1>
+            {body}
+            <p>
+                You can change how often you receive those e-mails in your profile.
+            </p>
+            </body>
+            </html>
+            "#,
+        receiver_name = escape(receiver_name),
+        body = body,
+    )
+}
+
+fn item_html(item: &NotificationItem, addr: &str) -> String {
+    let link = match item.link.as_deref() {
+        Some(path) if path.starts_with('/') => format!(
+            r#"<p><a href="{addr}{path}">{addr}{path}</a></p>"#,
+            addr = addr,
+            path = escape(path),
+        ),
+        _ => "".to_string(),
+    };
+    format!(
+        r#"
+            <h2>{heading}</h2>
+            <p><strong>{summary}</strong></p>
+            <p>{description}</p>
+            {link}
+            "#,
+        heading = escape(item.kind.heading(&item.sender_name).as_str()),
+        summary = escape(item.summary.as_str()),
+        description = escape(item.description.as_str()),
+        link = link,
+    )
+}
+
+/// Messages contains text written by users which must not be treated as HTML
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
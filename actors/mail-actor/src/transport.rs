@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use jirs_config::mail::{Configuration, Transport};
+use lettre::SendableEmail;
+
+lazy_static::lazy_static! {
+    static ref CAPTURED: Mutex<Vec<CapturedMail>> = Mutex::new(vec![]);
+}
+
+/// E-mail kept by `memory` transport
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedMail {
+    pub message_id: String,
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    /// Full message with headers
+    pub message: String,
+}
+
+/// Remove and return all e-mails kept by `memory` transport
+pub fn take_captured_mails() -> Vec<CapturedMail> {
+    match CAPTURED.lock() {
+        Ok(mut mails) => std::mem::take(&mut *mails),
+        Err(e) => {
+            log::error!("{}", e);
+            vec![]
+        }
+    }
+}
+
+/// Delivers e-mails in way selected by `transport` in `mail.toml`
+pub enum MailTransport {
+    Smtp(lettre::SmtpTransport),
+    File(PathBuf),
+    Memory,
+}
+
+impl MailTransport {
+    pub fn new(config: &Configuration) -> Self {
+        match config.transport {
+            Transport::Smtp => MailTransport::Smtp(mail_client(config).transport()),
+            Transport::File => MailTransport::File(PathBuf::from(config.spool_dir.as_str())),
+            Transport::Memory => MailTransport::Memory,
+        }
+    }
+}
+
+impl<'a> lettre::Transport<'a> for MailTransport {
+    type Result = Result<(), String>;
+
+    fn send(&mut self, email: SendableEmail) -> Self::Result {
+        match self {
+            MailTransport::Smtp(transport) => lettre::Transport::send(transport, email)
+                .map(|_| ())
+                .map_err(|e| format!("{}", e)),
+            MailTransport::File(dir) => write_eml(dir, captured(email)?),
+            MailTransport::Memory => {
+                let mail = captured(email)?;
+                CAPTURED.lock().map_err(|e| format!("{}", e))?.push(mail);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn captured(email: SendableEmail) -> Result<CapturedMail, String> {
+    let message_id = email.message_id().to_string();
+    let from = email.envelope().from().map(|address| address.to_string());
+    let to = email
+        .envelope()
+        .to()
+        .iter()
+        .map(|address| address.to_string())
+        .collect();
+    let message = email.message_to_string().map_err(|e| format!("{}", e))?;
+    Ok(CapturedMail {
+        message_id,
+        from,
+        to,
+        message,
+    })
+}
+
+/// Each e-mail is written to separate file named after its message id
+fn write_eml(dir: &Path, mail: CapturedMail) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}", e))?;
+    let name: String = mail
+        .message_id
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect();
+    std::fs::write(dir.join(format!("{}.eml", name)), mail.message).map_err(|e| format!("{}", e))
+}
+
+fn mail_client(config: &Configuration) -> lettre::SmtpClient {
+    let Configuration {
+        user: mail_user,
+        pass: mail_pass,
+        host: mail_host,
+        ..
+    } = &config;
+
+    lettre::SmtpClient::new_simple(mail_host)
+        .expect("Failed to init SMTP client")
+        .credentials(lettre::smtp::authentication::Credentials::new(
+            mail_user.clone(),
+            mail_pass.clone(),
+        ))
+        .connection_reuse(lettre::smtp::ConnectionReuseParameters::ReuseUnlimited)
+        .smtp_utf8(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use lettre::Transport as _;
+
+    use super::*;
+
+    fn email(to: &str) -> SendableEmail {
+        lettre_email::Email::builder()
+            .from("contact@jirs.pl")
+            .to(to)
+            .html("<p>Hello</p>")
+            .subject("Welcome to JIRS")
+            .build()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn write_eml_files_to_spool_dir() {
+        let dir = std::env::temp_dir().join(format!("jirs-mails-{}", uuid::Uuid::new_v4()));
+        let mut transport = MailTransport::File(dir.clone());
+        transport.send(email("foo@example.com")).unwrap();
+
+        let files: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        let content = std::fs::read_to_string(&files[0]).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "eml");
+        assert!(content.contains("Subject: Welcome to JIRS"));
+    }
+
+    #[test]
+    fn capture_mails_in_memory() {
+        let mut transport = MailTransport::Memory;
+        transport.send(email("bar@example.com")).unwrap();
+
+        let mails = take_captured_mails();
+
+        assert!(mails
+            .iter()
+            .any(|mail| mail.to == vec!["bar@example.com".to_string()]));
+        assert!(take_captured_mails()
+            .iter()
+            .all(|mail| mail.to != vec!["bar@example.com".to_string()]));
+    }
+}
@@ -0,0 +1,145 @@
+use actix::Addr;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{delete, get, post, put, Error, HttpRequest, HttpResponse};
+use database_actor::comments::LoadIssueComments;
+use database_actor::DbExecutor;
+use highlight_actor::HighlightActor;
+use jirs_data::{CommentId, CreateCommentPayload, IssueId, UpdateCommentPayload};
+use mail_actor::MailExecutor;
+use serde::{Deserialize, Serialize};
+use websocket_actor::handlers;
+use websocket_actor::server::WsServer;
+
+use crate::api::issues::find_project_issue;
+use crate::api::{apply_changes, current_user_project, require_role};
+use crate::db_or_service_error;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentBody {
+    pub body: String,
+}
+
+#[get("/issues/{id}/comments")]
+pub async fn list(
+    req: HttpRequest,
+    path: Path<IssueId>,
+    db: Data<Addr<DbExecutor>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let issue = find_project_issue(path.into_inner(), user_project.project_id, &db).await?;
+    let comments = db_or_service_error!(
+        db,
+        LoadIssueComments {
+            project_id: user_project.project_id,
+            issue_id: issue.id
+        }
+    );
+    Ok(HttpResponse::Ok().json(comments))
+}
+
+#[post("/issues/{id}/comments")]
+pub async fn create(
+    req: HttpRequest,
+    path: Path<IssueId>,
+    body: Json<CommentBody>,
+    db: Data<Addr<DbExecutor>>,
+    mail: Data<Addr<MailExecutor>>,
+    ws: Data<Addr<WsServer>>,
+    hi: Data<Addr<HighlightActor>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<CreateCommentPayload>(&req, &db).await?;
+    let issue = find_project_issue(path.into_inner(), user_project.project_id, &db).await?;
+    let issue_id = issue.id;
+    let body = body.into_inner().body;
+    let comment = apply_changes(user, user_project, &db, &mail, &ws, &hi, move |changes| {
+        changes.create_comment(issue_id, body)
+    })
+    .await?;
+    Ok(HttpResponse::Created().json(comment))
+}
+
+#[put("/comments/{id}")]
+pub async fn update(
+    req: HttpRequest,
+    path: Path<CommentId>,
+    body: Json<CommentBody>,
+    db: Data<Addr<DbExecutor>>,
+    mail: Data<Addr<MailExecutor>>,
+    ws: Data<Addr<WsServer>>,
+    hi: Data<Addr<HighlightActor>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<UpdateCommentPayload>(&req, &db).await?;
+    let comment_id = path.into_inner();
+    let body = body.into_inner().body;
+    let comment = apply_changes(user, user_project, &db, &mail, &ws, &hi, move |changes| {
+        changes.update_comment(comment_id, body)
+    })
+    .await?;
+    Ok(HttpResponse::Ok().json(comment))
+}
+
+#[delete("/comments/{id}")]
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<CommentId>,
+    db: Data<Addr<DbExecutor>>,
+    mail: Data<Addr<MailExecutor>>,
+    ws: Data<Addr<WsServer>>,
+    hi: Data<Addr<HighlightActor>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<handlers::DeleteComment>(&req, &db).await?;
+    let comment_id = path.into_inner();
+    apply_changes(user, user_project, &db, &mail, &ws, &hi, move |changes| {
+        changes.delete_comment(comment_id)
+    })
+    .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use database_actor::issue_watchers::LoadIssueWatcherIds;
+    use database_actor::messages::LoadMessages;
+    use jirs_data::{Comment, Issue, MessageType};
+
+    use super::CommentBody;
+    use crate::api::tests::{issue_payload, manager_and_user, test_pool};
+
+    #[actix_rt::test]
+    async fn notify_mentioned_in_comment() {
+        let pool = test_pool();
+        let (manager, token, user) = manager_and_user(&pool);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/issues")
+            .header("Authorization", token.as_str())
+            .set_json(&issue_payload(user.id, vec![]))
+            .to_request();
+        let issue: Issue = test::read_response_json(&mut app, req).await;
+        let req = test::TestRequest::post()
+            .uri(format!("/api/v1/issues/{}/comments", issue.id).as_str())
+            .header("Authorization", token.as_str())
+            .set_json(&CommentBody {
+                body: "@bar take a look".to_string(),
+            })
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let comment: Comment = test::read_body_json(res).await;
+
+        let conn = &pool.get().unwrap();
+        let messages = LoadMessages { user_id: user.id }.execute(conn).unwrap();
+        let watchers = LoadIssueWatcherIds { issue_id: issue.id }
+            .execute(conn)
+            .unwrap();
+
+        assert_eq!(comment.user_id, manager.id);
+        assert!(messages
+            .iter()
+            .any(|message| message.message_type == MessageType::Mention));
+        assert!(watchers.contains(&manager.id));
+    }
+}
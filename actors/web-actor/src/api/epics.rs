@@ -0,0 +1,198 @@
+use actix::Addr;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{delete, get, post, put, Error, HttpRequest, HttpResponse};
+use database_actor::epics::{
+    CreateEpic, DeleteEpic, LoadEpics, UpdateEpicEndsAt, UpdateEpicName, UpdateEpicStartsAt,
+};
+use database_actor::DbExecutor;
+use jirs_data::{DescriptionString, EndsAt, EpicId, NameString, StartsAt, WsMsg};
+use serde::{Deserialize, Serialize};
+use websocket_actor::handlers;
+use websocket_actor::server::{InnerMsg, WsServer};
+
+use crate::api::{current_user_project, require_role};
+use crate::db_or_service_error;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEpicBody {
+    pub name: NameString,
+    pub description: Option<DescriptionString>,
+    pub description_html: Option<DescriptionString>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EpicNameBody {
+    pub name: NameString,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EpicStartsAtBody {
+    pub starts_at: Option<StartsAt>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EpicEndsAtBody {
+    pub ends_at: Option<EndsAt>,
+}
+
+#[get("/epics")]
+pub async fn list(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let epics = db_or_service_error!(
+        db,
+        LoadEpics {
+            project_id: user_project.project_id
+        }
+    );
+    Ok(HttpResponse::Ok().json(epics))
+}
+
+#[post("/epics")]
+pub async fn create(
+    req: HttpRequest,
+    body: Json<CreateEpicBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<handlers::CreateEpic>(&req, &db).await?;
+    let CreateEpicBody {
+        name,
+        description,
+        description_html,
+    } = body.into_inner();
+    let epic = db_or_service_error!(
+        db,
+        CreateEpic {
+            user_id: user.id,
+            project_id: user_project.project_id,
+            name,
+            description,
+            description_html,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::EpicCreated(epic.clone()),
+    ));
+    Ok(HttpResponse::Created().json(epic))
+}
+
+#[put("/epics/{id}/name")]
+pub async fn update_name(
+    req: HttpRequest,
+    path: Path<EpicId>,
+    body: Json<EpicNameBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::UpdateEpicName>(&req, &db).await?;
+    let epic = db_or_service_error!(
+        db,
+        UpdateEpicName {
+            epic_id: path.into_inner(),
+            project_id: user_project.project_id,
+            name: body.into_inner().name,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::EpicUpdated(epic.clone()),
+    ));
+    Ok(HttpResponse::Ok().json(epic))
+}
+
+#[put("/epics/{id}/starts-at")]
+pub async fn update_starts_at(
+    req: HttpRequest,
+    path: Path<EpicId>,
+    body: Json<EpicStartsAtBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::UpdateEpicStartsAt>(&req, &db).await?;
+    let epic = db_or_service_error!(
+        db,
+        UpdateEpicStartsAt {
+            epic_id: path.into_inner(),
+            project_id: user_project.project_id,
+            starts_at: body.into_inner().starts_at,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::EpicUpdated(epic.clone()),
+    ));
+    Ok(HttpResponse::Ok().json(epic))
+}
+
+#[put("/epics/{id}/ends-at")]
+pub async fn update_ends_at(
+    req: HttpRequest,
+    path: Path<EpicId>,
+    body: Json<EpicEndsAtBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::UpdateEpicEndsAt>(&req, &db).await?;
+    let epic = db_or_service_error!(
+        db,
+        UpdateEpicEndsAt {
+            epic_id: path.into_inner(),
+            project_id: user_project.project_id,
+            ends_at: body.into_inner().ends_at,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::EpicUpdated(epic.clone()),
+    ));
+    Ok(HttpResponse::Ok().json(epic))
+}
+
+#[delete("/epics/{id}")]
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<EpicId>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<handlers::DeleteEpic>(&req, &db).await?;
+    let epic_id = path.into_inner();
+    let n = db_or_service_error!(
+        db,
+        DeleteEpic {
+            user_id: user.id,
+            project_id: user_project.project_id,
+            epic_id,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::EpicDeleted(epic_id, n),
+    ));
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use jirs_data::UserRole;
+
+    use crate::api::tests::{member, test_pool};
+
+    #[actix_rt::test]
+    async fn forbid_epic_delete_to_user() {
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::User);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::delete()
+            .uri("/api/v1/epics/1")
+            .header("Authorization", token.as_str())
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
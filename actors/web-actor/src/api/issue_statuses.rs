@@ -0,0 +1,165 @@
+use actix::Addr;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{delete, get, post, put, Error, HttpRequest, HttpResponse};
+use database_actor::issue_statuses::{
+    CreateIssueStatus, DeleteIssueStatus, LoadIssueStatuses, UpdateIssueStatus,
+};
+use database_actor::DbExecutor;
+use jirs_data::{IssueStatusId, Position, TitleString, WsMsg};
+use serde::{Deserialize, Serialize};
+use websocket_actor::handlers;
+use websocket_actor::server::{InnerMsg, WsServer};
+
+use crate::api::{current_user_project, require_role};
+use crate::db_or_service_error;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueStatusBody {
+    pub name: TitleString,
+    pub position: Position,
+}
+
+#[get("/issue-statuses")]
+pub async fn list(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let issue_statuses = db_or_service_error!(
+        db,
+        LoadIssueStatuses {
+            project_id: user_project.project_id
+        }
+    );
+    Ok(HttpResponse::Ok().json(issue_statuses))
+}
+
+#[post("/issue-statuses")]
+pub async fn create(
+    req: HttpRequest,
+    body: Json<IssueStatusBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::CreateIssueStatus>(&req, &db).await?;
+    let IssueStatusBody { name, position } = body.into_inner();
+    let issue_status = db_or_service_error!(
+        db,
+        CreateIssueStatus {
+            project_id: user_project.project_id,
+            position,
+            name,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::IssueStatusCreated(issue_status.clone()),
+    ));
+    Ok(HttpResponse::Created().json(issue_status))
+}
+
+#[put("/issue-statuses/{id}")]
+pub async fn update(
+    req: HttpRequest,
+    path: Path<IssueStatusId>,
+    body: Json<IssueStatusBody>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::UpdateIssueStatus>(&req, &db).await?;
+    let IssueStatusBody { name, position } = body.into_inner();
+    let issue_status = db_or_service_error!(
+        db,
+        UpdateIssueStatus {
+            issue_status_id: path.into_inner(),
+            project_id: user_project.project_id,
+            position,
+            name,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::IssueStatusUpdated(issue_status.clone()),
+    ));
+    Ok(HttpResponse::Ok().json(issue_status))
+}
+
+#[delete("/issue-statuses/{id}")]
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<IssueStatusId>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::DeleteIssueStatus>(&req, &db).await?;
+    let issue_status_id = path.into_inner();
+    let n = db_or_service_error!(
+        db,
+        DeleteIssueStatus {
+            issue_status_id,
+            project_id: user_project.project_id,
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::IssueStatusDeleted(issue_status_id, n),
+    ));
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use jirs_data::UserRole;
+
+    use super::*;
+    use crate::api::tests::{member, test_pool};
+
+    fn body() -> IssueStatusBody {
+        IssueStatusBody {
+            name: "Review".to_string(),
+            position: 9,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn forbid_status_changes_to_user() {
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::User);
+        let mut app = crate::test_app!(pool);
+
+        let create = test::TestRequest::post()
+            .uri("/api/v1/issue-statuses")
+            .header("Authorization", token.as_str())
+            .set_json(&body())
+            .to_request();
+        let update = test::TestRequest::put()
+            .uri("/api/v1/issue-statuses/1")
+            .header("Authorization", token.as_str())
+            .set_json(&body())
+            .to_request();
+        let delete = test::TestRequest::delete()
+            .uri("/api/v1/issue-statuses/1")
+            .header("Authorization", token.as_str())
+            .to_request();
+
+        for req in vec![create, update, delete] {
+            let res = test::call_service(&mut app, req).await;
+            assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn allow_manager_to_create_status() {
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::Manager);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/issue-statuses")
+            .header("Authorization", token.as_str())
+            .set_json(&body())
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,279 @@
+use actix::Addr;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{delete, get, post, put, Error, HttpRequest, HttpResponse};
+use database_actor::issue_assignees::LoadAssignees;
+use database_actor::issue_labels::LoadIssueLabelIds;
+use database_actor::issues::{DeleteIssue, FindProjectIssue, LoadProjectIssues};
+use database_actor::DbExecutor;
+use highlight_actor::HighlightActor;
+use jirs_data::{CreateIssuePayload, Issue, IssueFieldId, IssueId, PayloadVariant, WsMsg};
+use mail_actor::MailExecutor;
+use serde::{Deserialize, Serialize};
+use websocket_actor::handlers::{self, UpdateIssueHandler};
+use websocket_actor::server::{InnerMsg, WsServer};
+
+use crate::api::{apply_changes, current_user_project, require_role};
+use crate::{db_or_service_error, ServiceError};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateIssueBody {
+    pub field_id: IssueFieldId,
+    pub payload: PayloadVariant,
+}
+
+#[get("/issues")]
+pub async fn list(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let v = db_or_service_error!(
+        db,
+        LoadProjectIssues {
+            project_id: user_project.project_id
+        }
+    );
+    let mut issues: Vec<Issue> = Vec::with_capacity(v.len());
+    for issue in v {
+        issues.push(with_assignees(issue.into(), &db).await?);
+    }
+    issues.sort_by(|a, b| a.list_position.cmp(&b.list_position));
+    Ok(HttpResponse::Ok().json(issues))
+}
+
+#[get("/issues/{id}")]
+pub async fn show(
+    req: HttpRequest,
+    path: Path<IssueId>,
+    db: Data<Addr<DbExecutor>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let issue = find_project_issue(path.into_inner(), user_project.project_id, &db).await?;
+    Ok(HttpResponse::Ok().json(with_assignees(issue, &db).await?))
+}
+
+#[post("/issues")]
+pub async fn create(
+    req: HttpRequest,
+    payload: Json<CreateIssuePayload>,
+    db: Data<Addr<DbExecutor>>,
+    mail: Data<Addr<MailExecutor>>,
+    ws: Data<Addr<WsServer>>,
+    hi: Data<Addr<HighlightActor>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<CreateIssuePayload>(&req, &db).await?;
+    let mut payload = payload.into_inner();
+    payload.project_id = user_project.project_id;
+    let issue = apply_changes(user, user_project, &db, &mail, &ws, &hi, move |changes| {
+        let issue = changes.create_issue(payload)?;
+        changes.broadcast(&WsMsg::IssueCreated(issue.clone()));
+        Ok(issue)
+    })
+    .await?;
+    Ok(HttpResponse::Created().json(issue))
+}
+
+#[put("/issues/{id}")]
+pub async fn update(
+    req: HttpRequest,
+    path: Path<IssueId>,
+    body: Json<UpdateIssueBody>,
+    db: Data<Addr<DbExecutor>>,
+    mail: Data<Addr<MailExecutor>>,
+    ws: Data<Addr<WsServer>>,
+    hi: Data<Addr<HighlightActor>>,
+) -> Result<HttpResponse, Error> {
+    let (user, user_project) = require_role::<UpdateIssueHandler>(&req, &db).await?;
+    let issue = find_project_issue(path.into_inner(), user_project.project_id, &db).await?;
+    let UpdateIssueBody { field_id, payload } = body.into_inner();
+    let issue = apply_changes(user, user_project, &db, &mail, &ws, &hi, move |changes| {
+        changes.update_issue(UpdateIssueHandler {
+            id: issue.id,
+            field_id,
+            payload,
+        })
+    })
+    .await?;
+    Ok(HttpResponse::Ok().json(issue))
+}
+
+#[delete("/issues/{id}")]
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<IssueId>,
+    db: Data<Addr<DbExecutor>>,
+    ws: Data<Addr<WsServer>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<handlers::DeleteIssue>(&req, &db).await?;
+    let issue = find_project_issue(path.into_inner(), user_project.project_id, &db).await?;
+    let n = db_or_service_error!(
+        db,
+        DeleteIssue {
+            project_id: user_project.project_id,
+            issue_id: issue.id
+        }
+    );
+    ws.do_send(InnerMsg::BroadcastToChannel(
+        user_project.project_id,
+        WsMsg::IssueDeleted(issue.id, n),
+    ));
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Load issue and make sure it belongs to given project
+pub(crate) async fn find_project_issue(
+    issue_id: IssueId,
+    project_id: jirs_data::ProjectId,
+    db: &Data<Addr<DbExecutor>>,
+) -> Result<Issue, HttpResponse> {
+    let issue: Issue = match db
+        .send(FindProjectIssue {
+            project_id,
+            issue_id,
+        })
+        .await
+    {
+        Ok(Ok(issue)) => issue.into(),
+        Ok(Err(e)) => {
+            log::error!("{:?}", e);
+            return Err(ServiceError::RecordNotFound("Issue".to_string()).into_http_response());
+        }
+        Err(e) => {
+            log::error!("{:?}", e);
+            return Err(ServiceError::DatabaseConnectionLost.into_http_response());
+        }
+    };
+    Ok(issue)
+}
+
+async fn with_assignees(
+    mut issue: Issue,
+    db: &Data<Addr<DbExecutor>>,
+) -> Result<Issue, HttpResponse> {
+    match db.send(LoadAssignees { issue_id: issue.id }).await {
+        Ok(Ok(assignees)) => {
+            issue.user_ids = assignees.into_iter().map(|a| a.user_id).collect();
+        }
+        Ok(Err(e)) => {
+            log::error!("{:?}", e);
+            return Err(ServiceError::from(e).into_http_response());
+        }
+        Err(e) => {
+            log::error!("{:?}", e);
+            return Err(ServiceError::DatabaseConnectionLost.into_http_response());
+        }
+    }
+    match db.send(LoadIssueLabelIds { issue_id: issue.id }).await {
+        Ok(Ok(label_ids)) => {
+            issue.label_ids = label_ids;
+            Ok(issue)
+        }
+        Ok(Err(e)) => {
+            log::error!("{:?}", e);
+            Err(ServiceError::from(e).into_http_response())
+        }
+        Err(e) => {
+            log::error!("{:?}", e);
+            Err(ServiceError::DatabaseConnectionLost.into_http_response())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use database_actor::issue_watchers::LoadIssueWatcherIds;
+    use database_actor::messages::LoadMessages;
+    use jirs_data::{Issue, IssueFieldId, MessageType, PayloadVariant, UserRole};
+
+    use super::UpdateIssueBody;
+    use crate::api::tests::{issue_payload, manager_and_user, member, test_pool};
+
+    #[actix_rt::test]
+    async fn forbid_issue_delete_to_user() {
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::User);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::delete()
+            .uri("/api/v1/issues/1")
+            .header("Authorization", token.as_str())
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_rt::test]
+    async fn notify_assignees_of_created_issue() {
+        let pool = test_pool();
+        let (manager, token, user) = manager_and_user(&pool);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/issues")
+            .header("Authorization", token.as_str())
+            .set_json(&issue_payload(manager.id, vec![user.id]))
+            .to_request();
+        let issue: Issue = test::read_response_json(&mut app, req).await;
+
+        let conn = &pool.get().unwrap();
+        let messages = LoadMessages { user_id: user.id }.execute(conn).unwrap();
+        let watchers = LoadIssueWatcherIds { issue_id: issue.id }
+            .execute(conn)
+            .unwrap();
+
+        assert_eq!(issue.user_ids, vec![user.id]);
+        assert!(messages
+            .iter()
+            .any(|message| message.message_type == MessageType::AssignedToIssue));
+        assert!(watchers.contains(&manager.id) && watchers.contains(&user.id));
+    }
+
+    #[actix_rt::test]
+    async fn reject_assignee_from_other_project() {
+        let pool = test_pool();
+        let (manager, token, _) = manager_and_user(&pool);
+        let (outsider, _) = member(&pool, "baz", UserRole::User);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/issues")
+            .header("Authorization", token.as_str())
+            .set_json(&issue_payload(manager.id, vec![outsider.id]))
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_rt::test]
+    async fn notify_mentioned_in_updated_description() {
+        let pool = test_pool();
+        let (manager, token, user) = manager_and_user(&pool);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/issues")
+            .header("Authorization", token.as_str())
+            .set_json(&issue_payload(manager.id, vec![]))
+            .to_request();
+        let issue: Issue = test::read_response_json(&mut app, req).await;
+        let req = test::TestRequest::put()
+            .uri(format!("/api/v1/issues/{}", issue.id).as_str())
+            .header("Authorization", token.as_str())
+            .set_json(&UpdateIssueBody {
+                field_id: IssueFieldId::Description,
+                payload: PayloadVariant::String("please check @bar".to_string()),
+            })
+            .to_request();
+        let issue: Issue = test::read_response_json(&mut app, req).await;
+
+        let messages = LoadMessages { user_id: user.id }
+            .execute(&pool.get().unwrap())
+            .unwrap();
+
+        assert_eq!(issue.description_text.as_deref(), Some("please check @bar"));
+        assert!(messages
+            .iter()
+            .any(|message| message.message_type == MessageType::Mention));
+    }
+}
@@ -0,0 +1,300 @@
+use actix::Addr;
+use actix_web::error::BlockingError;
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse, Scope};
+use database_actor::user_projects::CurrentUserProject;
+use database_actor::DbExecutor;
+use highlight_actor::HighlightActor;
+use jirs_data::{User, UserProject};
+use mail_actor::MailExecutor;
+use websocket_actor::changes::{ChangeContext, ChangeError};
+use websocket_actor::permissions::{is_allowed, RequiredRole};
+use websocket_actor::server::WsServer;
+
+use crate::{user_from_request, ServiceError};
+
+pub mod comments;
+pub mod epics;
+pub mod issue_statuses;
+pub mod issues;
+pub mod projects;
+pub mod users;
+
+#[macro_export]
+macro_rules! db_or_service_error {
+    ($db: expr, $msg: expr) => {
+        match $db.send($msg).await {
+            Ok(Ok(r)) => r,
+            Ok(Err(e)) => {
+                log::error!("{:?}", e);
+                return Ok($crate::ServiceError::from(e).into_http_response());
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                return Ok($crate::ServiceError::DatabaseConnectionLost.into_http_response());
+            }
+        }
+    };
+}
+
+/// JSON API mirroring WebSocket messages. Every request must contain
+/// `Authorization: Bearer <access token>` header.
+pub fn service() -> Scope {
+    web::scope("/api/v1")
+        // issues
+        .service(issues::list)
+        .service(issues::show)
+        .service(issues::create)
+        .service(issues::update)
+        .service(issues::delete)
+        // comments
+        .service(comments::list)
+        .service(comments::create)
+        .service(comments::update)
+        .service(comments::delete)
+        // epics
+        .service(epics::list)
+        .service(epics::create)
+        .service(epics::update_name)
+        .service(epics::update_starts_at)
+        .service(epics::update_ends_at)
+        .service(epics::delete)
+        // issue statuses
+        .service(issue_statuses::list)
+        .service(issue_statuses::create)
+        .service(issue_statuses::update)
+        .service(issue_statuses::delete)
+        // projects
+        .service(projects::list)
+        .service(projects::update)
+        // users
+        .service(users::list)
+        .service(users::me)
+}
+
+pub async fn current_user_project(
+    req: &HttpRequest,
+    db: &Data<Addr<DbExecutor>>,
+) -> Result<(User, UserProject), HttpResponse> {
+    let user = user_from_request(req.clone(), db).await?;
+    match db.send(CurrentUserProject { user_id: user.id }).await {
+        Ok(Ok(user_project)) => Ok((user, user_project)),
+        Ok(Err(e)) => {
+            log::error!("{:?}", e);
+            Err(ServiceError::from(e).into_http_response())
+        }
+        Err(e) => {
+            log::error!("{:?}", e);
+            Err(ServiceError::DatabaseConnectionLost.into_http_response())
+        }
+    }
+}
+
+/// Current user project if user role allows to handle WebSocket message
+/// performing same action
+pub async fn require_role<Msg: RequiredRole>(
+    req: &HttpRequest,
+    db: &Data<Addr<DbExecutor>>,
+) -> Result<(User, UserProject), HttpResponse> {
+    let (user, user_project) = current_user_project(req, db).await?;
+    if !is_allowed::<Msg>(user_project.role) {
+        return Err(ServiceError::Forbidden.into_http_response());
+    }
+    Ok((user, user_project))
+}
+
+/// Run issue or comment change of given member on blocking thread pool.
+/// `ChangeContext` waits for every database answer and would otherwise block
+/// HTTP worker
+pub async fn apply_changes<F, R>(
+    user: User,
+    user_project: UserProject,
+    db: &Data<Addr<DbExecutor>>,
+    mail: &Data<Addr<MailExecutor>>,
+    ws: &Data<Addr<WsServer>>,
+    hi: &Data<Addr<HighlightActor>>,
+    f: F,
+) -> Result<R, HttpResponse>
+where
+    F: FnOnce(&ChangeContext<'_>) -> Result<R, ChangeError> + Send + 'static,
+    R: Send + 'static,
+{
+    let (db, mail, ws, hi) = (
+        db.get_ref().clone(),
+        mail.get_ref().clone(),
+        ws.get_ref().clone(),
+        hi.get_ref().clone(),
+    );
+    let res = web::block(move || {
+        f(&ChangeContext {
+            db: &db,
+            mail: &mail,
+            ws: &ws,
+            hi: &hi,
+            user: &user,
+            user_project: &user_project,
+        })
+    })
+    .await;
+    match res {
+        Ok(r) => Ok(r),
+        Err(BlockingError::Error(e)) => Err(ServiceError::from(e).into_http_response()),
+        Err(BlockingError::Canceled) => {
+            Err(ServiceError::DatabaseConnectionLost.into_http_response())
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use database_actor::tokens::CreateAccessToken;
+    use database_actor::user_projects::CurrentUserProject;
+    use database_actor::users::Register;
+    use database_actor::DbPool;
+    use diesel::r2d2::{ConnectionManager, CustomizeConnection, Error, Pool};
+    use diesel::{Connection, PgConnection};
+    use jirs_data::{CreateIssuePayload, IssuePriority, IssueType, ProjectId, UserId, UserRole};
+
+    use super::*;
+
+    /// Connection works inside transaction which is never committed
+    #[derive(Debug)]
+    struct TestTransaction;
+
+    impl CustomizeConnection<PgConnection, Error> for TestTransaction {
+        fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), Error> {
+            conn.begin_test_transaction().map_err(Error::QueryError)
+        }
+    }
+
+    /// Pool with single connection so fixtures and requests share data which
+    /// is dropped after test
+    pub(crate) fn test_pool() -> DbPool {
+        let config = jirs_config::database::Configuration::read();
+        Pool::builder()
+            .max_size(1)
+            .connection_customizer(Box::new(TestTransaction))
+            .build(ConnectionManager::<PgConnection>::new(config.database_url))
+            .unwrap()
+    }
+
+    /// Signed in user with given role in new project and authorization header
+    /// value for this user
+    pub(crate) fn member(pool: &DbPool, name: &str, role: UserRole) -> (User, String) {
+        project_member(pool, name, role, None)
+    }
+
+    /// Same as `member` but user joins given project if there is one
+    pub(crate) fn project_member(
+        pool: &DbPool,
+        name: &str,
+        role: UserRole,
+        project_id: Option<ProjectId>,
+    ) -> (User, String) {
+        let conn = &pool.get().unwrap();
+        let user = Register {
+            name: name.to_string(),
+            email: format!("{}@example.com", name),
+            project_id,
+            role,
+            password: None,
+        }
+        .execute(conn)
+        .unwrap();
+        let token = CreateAccessToken { user_id: user.id }
+            .execute(conn)
+            .unwrap();
+        (user, format!("Bearer {}", token.access_token))
+    }
+
+    /// Manager with authorization header and user of same project
+    pub(crate) fn manager_and_user(pool: &DbPool) -> (User, String, User) {
+        let (manager, token) = member(pool, "foo", UserRole::Manager);
+        let project_id = CurrentUserProject {
+            user_id: manager.id,
+        }
+        .execute(&pool.get().unwrap())
+        .unwrap()
+        .project_id;
+        let (user, _) = project_member(pool, "bar", UserRole::User, Some(project_id));
+        (manager, token, user)
+    }
+
+    /// New task in current project of reporter
+    pub(crate) fn issue_payload(reporter_id: UserId, user_ids: Vec<UserId>) -> CreateIssuePayload {
+        CreateIssuePayload {
+            title: "foo issue".to_string(),
+            issue_type: IssueType::Task,
+            priority: IssuePriority::Medium,
+            description: None,
+            description_text: None,
+            estimate: None,
+            time_spent: None,
+            time_remaining: None,
+            // JSON API creates issue in current project
+            project_id: 0,
+            user_ids,
+            reporter_id,
+            issue_status_id: 0,
+            epic_id: None,
+            parent_id: None,
+            custom_values: vec![],
+        }
+    }
+
+    /// Application with JSON API using given pool
+    #[macro_export]
+    macro_rules! test_app {
+        ($pool: expr) => {{
+            let pool = $pool.clone();
+            let db = actix::SyncArbiter::start(1, move || database_actor::DbExecutor {
+                pool: pool.clone(),
+                config: jirs_config::database::Configuration::read(),
+            });
+            let hi = actix::SyncArbiter::start(1, highlight_actor::HighlightActor::default);
+            let mail = actix::SyncArbiter::start(1, || mail_actor::MailExecutor {
+                transport: mail_actor::MailTransport::Memory,
+                config: jirs_config::mail::Configuration::read(),
+            });
+            actix_web::test::init_service(
+                actix_web::App::new()
+                    .data(db)
+                    .data(hi)
+                    .data(mail)
+                    .data(<websocket_actor::server::WsServer as actix::Actor>::start_default())
+                    .data(
+                        <websocket_actor::rate_limit::RateLimiter as actix::Actor>::start_default(),
+                    )
+                    .service($crate::api::service()),
+            )
+            .await
+        }};
+    }
+
+    #[actix_rt::test]
+    async fn reject_remote_after_too_many_unknown_tokens() {
+        use actix_web::http::StatusCode;
+        use actix_web::test;
+
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::User);
+        let mut app = crate::test_app!(pool);
+        let config = jirs_config::rate_limit::config();
+        let me = |token: &str| {
+            test::TestRequest::get()
+                .uri("/api/v1/users/me")
+                .header("Authorization", token)
+                .peer_addr("10.0.0.1:8000".parse().unwrap())
+                .to_request()
+        };
+
+        for _ in 0..config.remote_attempts {
+            let guess = format!("Bearer {}", uuid::Uuid::new_v4());
+            let res = test::call_service(&mut app, me(guess.as_str())).await;
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        }
+        let res = test::call_service(&mut app, me(token.as_str())).await;
+
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
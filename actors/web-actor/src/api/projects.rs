@@ -0,0 +1,72 @@
+use actix::Addr;
+use actix_web::web::{Data, Json};
+use actix_web::{get, put, Error, HttpRequest, HttpResponse};
+use database_actor::projects::{LoadProjects, UpdateProject};
+use database_actor::DbExecutor;
+use jirs_data::UpdateProjectPayload;
+
+use crate::api::require_role;
+use crate::{db_or_service_error, user_from_request};
+
+#[get("/projects")]
+pub async fn list(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let user = user_from_request(req, &db).await?;
+    let projects = db_or_service_error!(db, LoadProjects { user_id: user.id });
+    Ok(HttpResponse::Ok().json(projects))
+}
+
+/// Update current project, `id` from payload is ignored
+#[put("/projects/current")]
+pub async fn update(
+    req: HttpRequest,
+    payload: Json<UpdateProjectPayload>,
+    db: Data<Addr<DbExecutor>>,
+) -> Result<HttpResponse, Error> {
+    let (_, user_project) = require_role::<UpdateProjectPayload>(&req, &db).await?;
+    let payload = payload.into_inner();
+    let project = db_or_service_error!(
+        db,
+        UpdateProject {
+            project_id: user_project.project_id,
+            name: payload.name,
+            url: payload.url,
+            description: payload.description,
+            category: payload.category,
+            time_tracking: payload.time_tracking,
+        }
+    );
+    Ok(HttpResponse::Ok().json(project))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+    use jirs_data::UserRole;
+
+    use super::*;
+    use crate::api::tests::{member, test_pool};
+
+    #[actix_rt::test]
+    async fn forbid_project_update_to_user() {
+        let pool = test_pool();
+        let (_, token) = member(&pool, "foo", UserRole::User);
+        let mut app = crate::test_app!(pool);
+
+        let req = test::TestRequest::put()
+            .uri("/api/v1/projects/current")
+            .header("Authorization", token.as_str())
+            .set_json(&UpdateProjectPayload {
+                id: 0,
+                name: Some("bar".to_string()),
+                url: None,
+                description: None,
+                category: None,
+                time_tracking: None,
+            })
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,26 @@
+use actix::Addr;
+use actix_web::web::Data;
+use actix_web::{get, Error, HttpRequest, HttpResponse};
+use database_actor::users::LoadProjectUsers;
+use database_actor::DbExecutor;
+
+use crate::api::current_user_project;
+use crate::{db_or_service_error, user_from_request};
+
+#[get("/users")]
+pub async fn list(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let (_, user_project) = current_user_project(&req, &db).await?;
+    let users = db_or_service_error!(
+        db,
+        LoadProjectUsers {
+            project_id: user_project.project_id
+        }
+    );
+    Ok(HttpResponse::Ok().json(users))
+}
+
+#[get("/users/me")]
+pub async fn me(req: HttpRequest, db: Data<Addr<DbExecutor>>) -> Result<HttpResponse, Error> {
+    let user = user_from_request(req, &db).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
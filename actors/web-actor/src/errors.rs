@@ -1,6 +1,8 @@
 use actix_web::HttpResponse;
+use database_actor::{DatabaseError, OperationError};
 use jirs_data::msg::WsError;
 use jirs_data::ErrorResponse;
+use websocket_actor::changes::ChangeError;
 
 const TOKEN_NOT_FOUND: &str = "Token not found";
 const DATABASE_CONNECTION_FAILED: &str = "Database connection failed";
@@ -15,6 +17,8 @@ pub enum HighlightError {
 #[derive(Debug)]
 pub enum ServiceError {
     Unauthorized,
+    Forbidden,
+    TooManyRequests,
     DatabaseConnectionLost,
     DatabaseQueryFailed(String),
     RecordNotFound(String),
@@ -29,12 +33,41 @@ impl ServiceError {
     }
 }
 
+impl From<DatabaseError> for ServiceError {
+    fn from(e: DatabaseError) -> Self {
+        match e {
+            DatabaseError::DatabaseConnectionLost => ServiceError::DatabaseConnectionLost,
+            DatabaseError::GenericFailure(OperationError::LoadSingle, kind) => {
+                ServiceError::RecordNotFound(format!("{:?}", kind))
+            }
+            e => ServiceError::DatabaseQueryFailed(format!("{:?}", e)),
+        }
+    }
+}
+
+impl From<ChangeError> for ServiceError {
+    fn from(e: ChangeError) -> Self {
+        match e {
+            ChangeError::Invalid(WsError::Forbidden) => ServiceError::Forbidden,
+            ChangeError::Invalid(e) => ServiceError::Error(e),
+            ChangeError::Database(e) => e.into(),
+            ChangeError::Mailbox => ServiceError::DatabaseConnectionLost,
+        }
+    }
+}
+
 impl Into<HttpResponse> for ServiceError {
     fn into(self) -> HttpResponse {
         match self {
             ServiceError::Unauthorized => HttpResponse::Unauthorized().json(ErrorResponse {
                 errors: vec![TOKEN_NOT_FOUND.to_owned()],
             }),
+            ServiceError::Forbidden => HttpResponse::Forbidden().json(ErrorResponse {
+                errors: vec![WsError::Forbidden.to_str().to_string()],
+            }),
+            ServiceError::TooManyRequests => HttpResponse::TooManyRequests().json(ErrorResponse {
+                errors: vec![WsError::TooManyRequests.to_str().to_string()],
+            }),
             ServiceError::DatabaseConnectionLost => {
                 HttpResponse::InternalServerError().json(ErrorResponse {
                     errors: vec![DATABASE_CONNECTION_FAILED.to_owned()],
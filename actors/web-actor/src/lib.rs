@@ -8,14 +8,18 @@ use database_actor::authorize_user::AuthorizeUser;
 use database_actor::DbExecutor;
 pub use errors::*;
 use jirs_data::User;
+use websocket_actor::rate_limit::{self, Check, Hit, LimitedAction, RateLimiter};
 
 use crate::middleware::authorize::token_from_headers;
 
+pub mod api;
 pub mod avatar;
 pub mod errors;
 pub mod handlers;
 pub mod middleware;
 
+/// User signed in with access token. Remote address which sent too many
+/// unknown tokens is rejected before token is checked
 pub async fn user_from_request(
     req: HttpRequest,
     db: &Data<Addr<DbExecutor>>,
@@ -24,6 +28,19 @@ pub async fn user_from_request(
         Ok(uuid) => uuid,
         _ => return Err(ServiceError::Unauthorized.into_http_response()),
     };
+    let limiter = req.app_data::<Data<Addr<RateLimiter>>>();
+    let remote = rate_limit::remote_addr(&req);
+    if let Some(limiter) = limiter {
+        let check = Check {
+            action: LimitedAction::AccessToken,
+            email: None,
+            remote: remote.clone(),
+        };
+        if !limiter.send(check).await.unwrap_or_default() {
+            warn!("too many unknown access tokens from {:?}", remote);
+            return Err(ServiceError::TooManyRequests.into_http_response());
+        }
+    }
     match db
         .send(AuthorizeUser {
             access_token: token,
@@ -31,7 +48,20 @@ pub async fn user_from_request(
         .await
     {
         Ok(Ok(user)) => Ok(user),
-        Ok(Err(_e)) => Err(HttpResponse::InternalServerError().body("Critical database error")),
-        _ => Err(ServiceError::Unauthorized.into_http_response()),
+        Ok(Err(e)) => {
+            debug!("{:?}", e);
+            if let Some(limiter) = limiter {
+                limiter.do_send(Hit {
+                    action: LimitedAction::AccessToken,
+                    email: None,
+                    remote,
+                });
+            }
+            Err(ServiceError::Unauthorized.into_http_response())
+        }
+        Err(e) => {
+            error!("{}", e);
+            Err(ServiceError::DatabaseConnectionLost.into_http_response())
+        }
     }
 }
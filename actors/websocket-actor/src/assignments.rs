@@ -0,0 +1,58 @@
+use database_actor::messages::{CreateMessage, CreateMessageReceiver};
+use futures::executor::block_on;
+use jirs_data::{IssueId, MessageType, UserId};
+
+use crate::changes::ChangeContext;
+
+/// Users present in new assignees who were not assigned before
+pub fn added_assignees(previous: &[UserId], current: &[UserId]) -> Vec<UserId> {
+    let mut added: Vec<UserId> = current
+        .iter()
+        .filter(|id| !previous.contains(id))
+        .copied()
+        .collect();
+    added.sort_unstable();
+    added.dedup();
+    added
+}
+
+impl ChangeContext<'_> {
+    /// Send message to each newly assigned user except current user who made
+    /// the change
+    pub(crate) fn notify_assignees(
+        &self,
+        issue_id: IssueId,
+        issue_title: &str,
+        previous: &[UserId],
+        current: &[UserId],
+    ) {
+        let sender = self.user;
+        for receiver_id in added_assignees(previous, current) {
+            if receiver_id == sender.id {
+                continue;
+            }
+            if let Ok(Ok(message)) = block_on(self.db.send(CreateMessage {
+                receiver: CreateMessageReceiver::Reference(receiver_id),
+                sender_id: sender.id,
+                summary: format!("You have been assigned to {}", issue_title),
+                description: format!("@<{}> assigned you to issue", sender.email),
+                message_type: MessageType::AssignedToIssue,
+                hyper_link: format!("/issues/{}", issue_id),
+            })) {
+                self.deliver_message(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_new_assignees_are_added() {
+        assert_eq!(added_assignees(&[1, 2], &[2, 3, 4]), vec![3, 4]);
+        assert_eq!(added_assignees(&[], &[3, 3, 1]), vec![1, 3]);
+        assert!(added_assignees(&[1, 2], &[1]).is_empty());
+    }
+}
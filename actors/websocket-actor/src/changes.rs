@@ -0,0 +1,445 @@
+use actix::Addr;
+use database_actor::issue_assignees::{LoadAssignees, LoadAssigneesIds};
+use database_actor::issue_custom_values::LoadIssueCustomValues;
+use database_actor::issues::FindProjectIssue;
+use database_actor::{DatabaseError, DbExecutor};
+use futures::executor::block_on;
+use highlight_actor::HighlightActor;
+use jirs_data::msg::WsError;
+use jirs_data::{
+    Comment, CommentId, CreateIssuePayload, Issue, IssueAssignee, IssueId, IssueType,
+    NumberOfDeleted, SprintState, User, UserId, UserProject, WsMsg,
+};
+use mail_actor::MailExecutor;
+
+use crate::handlers::{issues_with_assignees, UpdateIssueHandler};
+use crate::server::{InnerMsg, WsServer};
+use crate::WsResult;
+
+macro_rules! db_or_change_error {
+    ($s: ident, $msg: expr, $invalid: expr) => {
+        match block_on($s.db.send($msg)) {
+            Ok(Ok(r)) => r,
+            Ok(Err(e)) => {
+                log::error!("{:?}", e);
+                return Err(ChangeError::Invalid($invalid));
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                return Err(ChangeError::Mailbox);
+            }
+        }
+    };
+    ($s: ident, $msg: expr) => {
+        match block_on($s.db.send($msg)) {
+            Ok(Ok(r)) => r,
+            Ok(Err(e)) => {
+                log::error!("{:?}", e);
+                return Err(ChangeError::Database(e));
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                return Err(ChangeError::Mailbox);
+            }
+        }
+    };
+}
+
+/// Reason why issue or comment change was not saved
+#[derive(Debug)]
+pub enum ChangeError {
+    /// Change breaks project rules, user should see the reason
+    Invalid(WsError),
+    Database(DatabaseError),
+    /// Database actor did not answer
+    Mailbox,
+}
+
+impl ChangeError {
+    /// Rejected change is answered with error message, failures are already
+    /// logged and stay unanswered
+    pub fn into_ws_result(self) -> WsResult {
+        match self {
+            ChangeError::Invalid(e) => Ok(Some(WsMsg::Error(e))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Signed in project member with actors needed to validate issue and comment
+/// changes and to notify others about them.
+/// WebSocket handlers and JSON API both save changes through it so both apply
+/// the same rules.
+pub struct ChangeContext<'a> {
+    pub db: &'a Addr<DbExecutor>,
+    pub mail: &'a Addr<MailExecutor>,
+    pub ws: &'a Addr<WsServer>,
+    pub hi: &'a Addr<HighlightActor>,
+    pub user: &'a User,
+    pub user_project: &'a UserProject,
+}
+
+impl ChangeContext<'_> {
+    /// Send message to every connection which joined current project
+    pub fn broadcast(&self, msg: &WsMsg) {
+        self.ws.do_send(InnerMsg::BroadcastToChannel(
+            self.user_project.project_id,
+            msg.clone(),
+        ));
+    }
+
+    /// Validate and save single field change. Mentioned users, new assignees
+    /// and watchers are notified and updated issue is sent to project members
+    pub fn update_issue(&self, msg: UpdateIssueHandler) -> Result<Issue, ChangeError> {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.user_project;
+
+        let mut msg = msg.into_update_issue(project_id, user_id, self.hi);
+        if let Some(issue_status_id) = msg.issue_status_id {
+            self.check_transition(msg.issue_id, issue_status_id)
+                .map_err(ChangeError::Invalid)?;
+            self.check_wip_limit(msg.issue_id, issue_status_id)
+                .map_err(ChangeError::Invalid)?;
+        }
+        if let Some(Some(sprint_id)) = msg.sprint_id {
+            let sprint = db_or_change_error!(
+                self,
+                database_actor::sprints::FindSprint {
+                    project_id,
+                    sprint_id
+                },
+                WsError::InvalidSprint
+            );
+            if sprint.state == SprintState::Completed {
+                return Err(ChangeError::Invalid(WsError::InvalidSprint));
+            }
+        }
+        if let Some(label_ids) = msg.label_ids.as_mut() {
+            let labels =
+                db_or_change_error!(self, database_actor::labels::LoadLabels { project_id });
+            label_ids.retain(|id| labels.iter().any(|label| label.id == *id));
+        }
+        let custom_value_changed = match msg.custom_value.as_ref() {
+            Some((custom_field_id, value)) => {
+                let field = db_or_change_error!(
+                    self,
+                    database_actor::custom_fields::FindCustomField {
+                        project_id,
+                        custom_field_id: *custom_field_id,
+                    },
+                    WsError::InvalidCustomValue
+                );
+                let member_ids = self.project_member_ids()?;
+                if !value
+                    .as_ref()
+                    .map(|v| field.accepts(v, &member_ids))
+                    .unwrap_or(true)
+                {
+                    return Err(ChangeError::Invalid(WsError::InvalidCustomValue));
+                }
+                true
+            }
+            _ => false,
+        };
+
+        // only users added to description are notified about mention and
+        // watchers are notified only if status was really changed
+        let previous_issue = if msg.description_text.is_some() || msg.issue_status_id.is_some() {
+            Some(db_or_change_error!(
+                self,
+                FindProjectIssue {
+                    project_id,
+                    issue_id: msg.issue_id
+                }
+            ))
+        } else {
+            None
+        };
+        let previous_description = match (msg.description_text.as_ref(), previous_issue.as_ref()) {
+            (Some(_), Some(previous)) => {
+                Some(previous.description_text.clone().unwrap_or_default())
+            }
+            _ => None,
+        };
+        let new_status_id = msg.issue_status_id.filter(|status_id| {
+            previous_issue
+                .as_ref()
+                .map(|previous| previous.issue_status_id != *status_id)
+                .unwrap_or_default()
+        });
+        let previous_assignees: Option<Vec<UserId>> = if msg.user_ids.is_some() {
+            let assignees: Vec<IssueAssignee> = db_or_change_error!(
+                self,
+                LoadAssignees {
+                    issue_id: msg.issue_id
+                }
+            );
+            Some(assignees.into_iter().map(|a| a.user_id).collect())
+        } else {
+            None
+        };
+
+        let reporter_changed = msg.reporter_id.is_some();
+
+        let issue = db_or_change_error!(self, msg);
+        let mut issue: Issue = issue.into();
+        if let Some(previous) = previous_description {
+            self.notify_mentions(
+                issue.id,
+                Some(previous.as_str()),
+                issue.description_text.as_deref().unwrap_or_default(),
+                "issue description",
+            );
+        }
+
+        let assignees: Vec<IssueAssignee> =
+            db_or_change_error!(self, LoadAssignees { issue_id: issue.id });
+
+        for assignee in assignees {
+            issue.user_ids.push(assignee.user_id);
+        }
+        if let Some(issue_status_id) = new_status_id {
+            let change = self.status_change(issue_status_id);
+            self.notify_watchers(issue.id, change.as_str(), &[]);
+        }
+        if let Some(mut previous) = previous_assignees {
+            self.notify_assignees(issue.id, issue.title.as_str(), &previous, &issue.user_ids);
+            let mut current = issue.user_ids.clone();
+            current.sort_unstable();
+            previous.sort_unstable();
+            if current != previous {
+                self.notify_watchers(issue.id, "changed assignees", &[]);
+            }
+            self.watch_issue(issue.id, current);
+        }
+        if reporter_changed {
+            self.watch_issue(issue.id, vec![issue.reporter_id]);
+        }
+        issue.label_ids = db_or_change_error!(
+            self,
+            database_actor::issue_labels::LoadIssueLabelIds { issue_id: issue.id }
+        );
+        if custom_value_changed {
+            let values = db_or_change_error!(self, LoadIssueCustomValues { issue_id: issue.id });
+            self.broadcast(&WsMsg::IssueCustomValuesUpdated(issue.id, values));
+        }
+        self.broadcast(&WsMsg::IssueUpdated(issue.clone()));
+        self.broadcast_parent(issue.parent_id);
+        Ok(issue)
+    }
+
+    /// Validate and save new issue in current project. Assignees are notified
+    /// and watch issue with reporter. Created issue itself is not sent to
+    /// project members, caller decides who receives it
+    pub fn create_issue(&self, msg: CreateIssuePayload) -> Result<Issue, ChangeError> {
+        let project_id = self.user_project.project_id;
+        if msg.project_id != project_id {
+            return Err(ChangeError::Invalid(WsError::Forbidden));
+        }
+        if let Some(issue_id) = msg.parent_id {
+            let parent = db_or_change_error!(
+                self,
+                FindProjectIssue {
+                    project_id,
+                    issue_id
+                },
+                WsError::InvalidParentIssue
+            );
+            if parent.issue_type == IssueType::Subtask {
+                return Err(ChangeError::Invalid(WsError::InvalidParentIssue));
+            }
+        }
+        let issue_type = match msg.parent_id {
+            Some(_) => IssueType::Subtask,
+            _ => msg.issue_type,
+        };
+        if let Some(epic_id) = msg.epic_id {
+            let _ = db_or_change_error!(
+                self,
+                database_actor::epics::FindEpic {
+                    project_id,
+                    epic_id
+                },
+                WsError::InvalidEpic
+            );
+        }
+        // zero is replaced with first status of project
+        if msg.issue_status_id != 0 {
+            let statuses = db_or_change_error!(
+                self,
+                database_actor::issue_statuses::LoadIssueStatuses { project_id }
+            );
+            if !statuses.iter().any(|s| s.id == msg.issue_status_id) {
+                return Err(ChangeError::Invalid(WsError::InvalidIssueStatus));
+            }
+        }
+        let member_ids = self.project_member_ids()?;
+        if let Some(user_id) = std::iter::once(&msg.reporter_id)
+            .chain(msg.user_ids.iter())
+            .find(|id| !member_ids.contains(id))
+        {
+            return Err(ChangeError::Invalid(WsError::UserNotExists(*user_id)));
+        }
+        if !msg.custom_values.is_empty() {
+            let fields = db_or_change_error!(
+                self,
+                database_actor::custom_fields::LoadCustomFields { project_id }
+            );
+            let valid = msg.custom_values.iter().all(|(custom_field_id, value)| {
+                fields
+                    .iter()
+                    .any(|field| field.id == *custom_field_id && field.accepts(value, &member_ids))
+            });
+            if !valid {
+                return Err(ChangeError::Invalid(WsError::InvalidCustomValue));
+            }
+        }
+        let msg = database_actor::issues::CreateIssue {
+            title: msg.title,
+            issue_type,
+            issue_status_id: msg.issue_status_id,
+            priority: msg.priority,
+            description: msg.description,
+            description_text: msg.description_text,
+            estimate: msg.estimate,
+            // time spent is sum of worklogs
+            time_spent: None,
+            time_remaining: msg.time_remaining,
+            project_id,
+            reporter_id: msg.reporter_id,
+            user_ids: msg.user_ids,
+            epic_id: msg.epic_id,
+            parent_id: msg.parent_id,
+            custom_values: msg.custom_values,
+        };
+        let with_custom_values = !msg.custom_values.is_empty();
+        let issue = db_or_change_error!(self, msg);
+        let mut issue: Issue = issue.into();
+        // reporter is never saved as assignee
+        issue.user_ids = db_or_change_error!(self, LoadAssigneesIds { issue_id: issue.id });
+        self.notify_assignees(issue.id, issue.title.as_str(), &[], &issue.user_ids);
+        let mut watchers = issue.user_ids.clone();
+        watchers.push(issue.reporter_id);
+        self.watch_issue(issue.id, watchers);
+        if with_custom_values {
+            let values = db_or_change_error!(self, LoadIssueCustomValues { issue_id: issue.id });
+            self.broadcast(&WsMsg::IssueCustomValuesUpdated(issue.id, values));
+        }
+        self.broadcast_parent(issue.parent_id);
+        Ok(issue)
+    }
+
+    /// Save comment of current user. Mentioned users and watchers are notified,
+    /// author starts watching issue and comment is sent to project members
+    pub fn create_comment(&self, issue_id: IssueId, body: String) -> Result<Comment, ChangeError> {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.user_project;
+
+        let comment: Comment = db_or_change_error!(
+            self,
+            database_actor::comments::CreateComment {
+                project_id,
+                user_id,
+                issue_id,
+                body,
+            }
+        );
+        let mentioned = self.notify_mentions(issue_id, None, comment.body.as_str(), "comment");
+        self.notify_watchers(issue_id, "commented issue", &mentioned);
+        self.watch_issue(issue_id, vec![user_id]);
+        self.broadcast(&WsMsg::CommentCreated(comment.clone()));
+        Ok(comment)
+    }
+
+    /// Change comment of current user. Only users added to comment are
+    /// notified about mention
+    pub fn update_comment(
+        &self,
+        comment_id: CommentId,
+        body: String,
+    ) -> Result<Comment, ChangeError> {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.user_project;
+
+        let previous: Comment = db_or_change_error!(
+            self,
+            database_actor::comments::FindComment {
+                project_id,
+                comment_id
+            }
+        );
+        let comment: Comment = db_or_change_error!(
+            self,
+            database_actor::comments::UpdateComment {
+                project_id,
+                comment_id,
+                user_id,
+                body,
+            }
+        );
+        self.notify_mentions(
+            comment.issue_id,
+            Some(previous.body.as_str()),
+            comment.body.as_str(),
+            "comment",
+        );
+        self.broadcast(&WsMsg::CommentUpdated(comment.clone()));
+        Ok(comment)
+    }
+
+    /// Remove comment of current user and tell project members about it
+    pub fn delete_comment(&self, comment_id: CommentId) -> Result<NumberOfDeleted, ChangeError> {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.user_project;
+
+        let n = db_or_change_error!(
+            self,
+            database_actor::comments::DeleteComment {
+                project_id,
+                comment_id,
+                user_id,
+            }
+        );
+        self.broadcast(&WsMsg::CommentDeleted(comment_id, n));
+        Ok(n)
+    }
+
+    /// Ids of all current project members, used to validate user values
+    fn project_member_ids(&self) -> Result<Vec<UserId>, ChangeError> {
+        let users = db_or_change_error!(
+            self,
+            database_actor::users::LoadProjectUsers {
+                project_id: self.user_project.project_id
+            },
+            WsError::InvalidCustomValue
+        );
+        Ok(users.into_iter().map(|user| user.id).collect())
+    }
+
+    /// Send refreshed parent issue after one of its sub-tasks was changed
+    pub fn broadcast_parent(&self, parent_id: Option<IssueId>) {
+        let issue_id = match parent_id {
+            Some(id) => id,
+            _ => return,
+        };
+        if let Ok(Ok(parent)) =
+            block_on(self.db.send(database_actor::issues::LoadIssue { issue_id }))
+        {
+            if let Some(parent) = issues_with_assignees(self.db, vec![parent]).pop() {
+                self.broadcast(&WsMsg::IssueUpdated(parent));
+            }
+        }
+    }
+}
@@ -1,15 +1,21 @@
 use actix::AsyncContext;
-use database_actor::authorize_user::AuthorizeUser;
-use database_actor::tokens::{CreateBindToken, FindBindToken};
-use database_actor::users::LookupUser;
+use database_actor::tokens::{
+    CreateAccessToken, CreateBindToken, DeleteUserTokens, FindAccessToken, LoadUserSessions,
+    RefreshToken, UseBindToken,
+};
+use database_actor::user_credentials::VerifyCredentials;
+use database_actor::users::{FindUser, LookupUser};
+use database_actor::{DatabaseError, UserError};
 use futures::executor::block_on;
 use jirs_data::msg::WsError;
-use jirs_data::{Token, WsMsg};
+use jirs_data::{AuthMethods, Session, Token, WsMsg};
 use mail_actor::welcome::Welcome;
 
+use crate::rate_limit::LimitedAction;
+use crate::server::InnerMsg;
 use crate::{
     db_or_debug_and_return, db_or_debug_or_fallback, mail_or_debug_and_return, WebSocketActor,
-    WsHandler, WsResult,
+    WsHandler, WsMessageSender, WsResult,
 };
 
 pub struct Authenticate {
@@ -19,8 +25,11 @@ pub struct Authenticate {
 
 impl WsHandler<Authenticate> for WebSocketActor {
     fn handle_msg(&mut self, msg: Authenticate, _ctx: &mut Self::Context) -> WsResult {
+        if !jirs_config::auth::config().email_token {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
         let Authenticate { name, email } = msg;
-        // TODO check attempt number, allow only 5 times per day
+        self.check_rate_limit(LimitedAction::SignIn, Some(email.as_str()))?;
         let user = db_or_debug_and_return!(
             self,
             LookupUser { name, email },
@@ -41,16 +50,85 @@ impl WsHandler<Authenticate> for WebSocketActor {
     }
 }
 
+pub struct LoadAuthMethods;
+
+impl WsHandler<LoadAuthMethods> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadAuthMethods, _ctx: &mut Self::Context) -> WsResult {
+        let config = jirs_config::auth::config();
+        Ok(Some(WsMsg::AuthMethodsLoaded(AuthMethods {
+            email_token: config.email_token,
+            password: config.password,
+            totp: config.password && config.totp,
+        })))
+    }
+}
+
+pub struct PasswordSignIn {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+    pub totp: Option<String>,
+}
+
+impl WsHandler<PasswordSignIn> for WebSocketActor {
+    fn handle_msg(&mut self, msg: PasswordSignIn, _ctx: &mut Self::Context) -> WsResult {
+        if !jirs_config::auth::config().password {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        let PasswordSignIn {
+            name,
+            email,
+            password,
+            totp,
+        } = msg;
+        self.check_rate_limit(LimitedAction::PasswordSignIn, Some(email.as_str()))?;
+        let user = match block_on(self.db.send(VerifyCredentials {
+            name,
+            email: email.clone(),
+            password,
+            totp,
+        })) {
+            Ok(Ok(user)) => user,
+            Ok(Err(DatabaseError::User(UserError::TotpRequired))) => {
+                return Ok(Some(WsMsg::TotpRequired))
+            }
+            Ok(Err(DatabaseError::User(UserError::InvalidTotpCode))) => {
+                return Ok(Some(WsMsg::Error(WsError::InvalidTotpCode)))
+            }
+            Ok(Err(e)) => {
+                log::error!("{:?}", e);
+                return Ok(Some(WsMsg::Error(WsError::InvalidCredentials)));
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                return Ok(None);
+            }
+        };
+        self.clear_rate_limit(LimitedAction::PasswordSignIn, email.as_str());
+        let token = db_or_debug_and_return!(self, CreateAccessToken { user_id: user.id });
+        Ok(Some(WsMsg::BindTokenOk(
+            token.access_token,
+            token.refresh_token,
+        )))
+    }
+}
+
 pub struct CheckAuthToken {
     pub token: uuid::Uuid,
 }
 
 impl WsHandler<CheckAuthToken> for WebSocketActor {
     fn handle_msg(&mut self, msg: CheckAuthToken, ctx: &mut Self::Context) -> WsResult {
+        let token: Token = db_or_debug_and_return!(
+            self,
+            FindAccessToken { token: msg.token },
+            Ok(Some(WsMsg::AuthorizeExpired)),
+            Ok(Some(WsMsg::AuthorizeExpired))
+        );
         let user: jirs_data::User = db_or_debug_and_return!(
             self,
-            AuthorizeUser {
-                access_token: msg.token,
+            FindUser {
+                user_id: token.user_id,
             },
             Ok(Some(WsMsg::AuthorizeLoaded(Err(
                 "Invalid auth token".to_string()
@@ -65,10 +143,25 @@ impl WsHandler<CheckAuthToken> for WebSocketActor {
             crate::user_settings::default_user_setting(user.id)
         );
 
+        // connection stays signed in only as long as token used to open it
+        let expires_in = (token.access_expires_at - chrono::Utc::now().naive_utc())
+            .to_std()
+            .unwrap_or_default();
+        let expiry = ctx.run_later(expires_in, |act, ctx| {
+            act.token_expiry = None;
+            act.sign_out(ctx);
+            ctx.send_msg(&WsMsg::AuthorizeExpired);
+        });
+        if let Some(handle) = self.token_expiry.replace(expiry) {
+            ctx.cancel_future(handle);
+        }
+        self.current_token_id = Some(token.id);
         self.current_user = Some(user.clone());
         self.current_user_project = self.load_user_project().ok();
         self.current_project = self.load_project().ok();
 
+        self.addr
+            .do_send(InnerMsg::Connect(user.id, ctx.address().recipient()));
         block_on(self.join_channel(ctx.address().recipient()));
         Ok(Some(WsMsg::AuthorizeLoaded(Ok((user, setting)))))
     }
@@ -80,14 +173,76 @@ pub struct CheckBindToken {
 
 impl WsHandler<CheckBindToken> for WebSocketActor {
     fn handle_msg(&mut self, msg: CheckBindToken, _ctx: &mut Self::Context) -> WsResult {
+        if !jirs_config::auth::config().email_token {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        self.check_rate_limit(LimitedAction::BindToken, None)?;
         let token: Token = db_or_debug_and_return!(
             self,
-            FindBindToken {
+            UseBindToken {
                 token: msg.bind_token,
             },
             Ok(Some(WsMsg::BindTokenBad)),
             Ok(None)
         );
-        Ok(Some(WsMsg::BindTokenOk(token.access_token)))
+        Ok(Some(WsMsg::BindTokenOk(
+            token.access_token,
+            token.refresh_token,
+        )))
+    }
+}
+
+pub struct RefreshAccessToken {
+    pub refresh_token: uuid::Uuid,
+}
+
+impl WsHandler<RefreshAccessToken> for WebSocketActor {
+    fn handle_msg(&mut self, msg: RefreshAccessToken, _ctx: &mut Self::Context) -> WsResult {
+        self.check_rate_limit(LimitedAction::RefreshToken, None)?;
+        let token: Token = db_or_debug_and_return!(
+            self,
+            RefreshToken {
+                refresh_token: msg.refresh_token,
+            },
+            Ok(Some(WsMsg::AuthorizeExpired)),
+            Ok(None)
+        );
+        Ok(Some(WsMsg::TokenRefreshed(
+            token.access_token,
+            token.refresh_token,
+        )))
+    }
+}
+
+pub struct LoadSessions;
+
+impl WsHandler<LoadSessions> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadSessions, _ctx: &mut Self::Context) -> WsResult {
+        let user_id = self.require_user()?.id;
+        let tokens = db_or_debug_and_return!(self, LoadUserSessions { user_id });
+        let sessions = tokens
+            .into_iter()
+            .map(|token| Session {
+                id: token.id,
+                created_at: token.created_at,
+                updated_at: token.updated_at,
+                expires_at: token.refresh_expires_at,
+                current: Some(token.id) == self.current_token_id,
+            })
+            .collect();
+        Ok(Some(WsMsg::SessionsLoaded(sessions)))
+    }
+}
+
+/// Sign out user on every device, including current one
+pub struct RevokeAllSessions;
+
+impl WsHandler<RevokeAllSessions> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: RevokeAllSessions, _ctx: &mut Self::Context) -> WsResult {
+        let user_id = self.require_user()?.id;
+        let _ = db_or_debug_and_return!(self, DeleteUserTokens { user_id });
+        self.addr
+            .do_send(InnerMsg::SendToUser(user_id, WsMsg::AuthorizeExpired));
+        Ok(None)
     }
 }
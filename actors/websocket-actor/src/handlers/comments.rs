@@ -9,11 +9,12 @@ pub struct LoadIssueComments {
 
 impl WsHandler<LoadIssueComments> for WebSocketActor {
     fn handle_msg(&mut self, msg: LoadIssueComments, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
+        let project_id = self.require_role::<LoadIssueComments>()?.project_id;
 
         let comments = db_or_debug_and_return!(
             self,
             database_actor::comments::LoadIssueComments {
+                project_id,
                 issue_id: msg.issue_id,
             }
         );
@@ -23,47 +24,23 @@ impl WsHandler<LoadIssueComments> for WebSocketActor {
 }
 
 impl WsHandler<CreateCommentPayload> for WebSocketActor {
-    fn handle_msg(&mut self, mut msg: CreateCommentPayload, ctx: &mut Self::Context) -> WsResult {
-        use database_actor::comments::CreateComment;
-
-        let user_id = self.require_user()?.id;
-        if msg.user_id.is_none() {
-            msg.user_id = Some(user_id);
-        }
+    fn handle_msg(&mut self, msg: CreateCommentPayload, ctx: &mut Self::Context) -> WsResult {
+        self.require_role::<CreateCommentPayload>()?;
         let issue_id = msg.issue_id;
-        let _ = db_or_debug_and_return!(
-            self,
-            CreateComment {
-                user_id,
-                issue_id,
-                body: msg.body,
-            }
-        );
+        if let Err(e) = self.changes()?.create_comment(issue_id, msg.body) {
+            return e.into_ws_result();
+        }
         self.handle_msg(LoadIssueComments { issue_id }, ctx)
     }
 }
 
 impl WsHandler<UpdateCommentPayload> for WebSocketActor {
     fn handle_msg(&mut self, msg: UpdateCommentPayload, _ctx: &mut Self::Context) -> WsResult {
-        use database_actor::comments::UpdateComment;
-
-        let user_id = self.require_user()?.id;
-
-        let UpdateCommentPayload {
-            id: comment_id,
-            body,
-        } = msg;
-
-        let comment = db_or_debug_and_return!(
-            self,
-            UpdateComment {
-                comment_id,
-                user_id,
-                body,
-            }
-        );
-        self.broadcast(&WsMsg::CommentUpdated(comment));
-        Ok(None)
+        self.require_role::<UpdateCommentPayload>()?;
+        match self.changes()?.update_comment(msg.id, msg.body) {
+            Ok(_) => Ok(None),
+            Err(e) => e.into_ws_result(),
+        }
     }
 }
 
@@ -73,17 +50,10 @@ pub struct DeleteComment {
 
 impl WsHandler<DeleteComment> for WebSocketActor {
     fn handle_msg(&mut self, msg: DeleteComment, _ctx: &mut Self::Context) -> WsResult {
-        use database_actor::comments::DeleteComment;
-
-        let user_id = self.require_user()?.id;
-
-        let n = db_or_debug_and_return!(
-            self,
-            DeleteComment {
-                comment_id: msg.comment_id,
-                user_id,
-            }
-        );
-        Ok(Some(WsMsg::CommentDeleted(msg.comment_id, n)))
+        self.require_role::<DeleteComment>()?;
+        match self.changes()?.delete_comment(msg.comment_id) {
+            Ok(_) => Ok(None),
+            Err(e) => e.into_ws_result(),
+        }
     }
 }
@@ -0,0 +1,151 @@
+use database_actor::models::UserCredential;
+use database_actor::user_credentials::{
+    totp_url, ChangePassword, DisableTotp, EnableTotp, FindUserCredential, SetupTotp,
+};
+use database_actor::{DatabaseError, UserError};
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{CredentialsState, WsMsg};
+
+use crate::rate_limit::LimitedAction;
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+fn credentials_state(credential: &UserCredential) -> CredentialsState {
+    CredentialsState {
+        password: credential.password_hash.is_some(),
+        totp: credential.totp_enabled,
+    }
+}
+
+pub struct LoadCredentials;
+
+impl WsHandler<LoadCredentials> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadCredentials, _ctx: &mut Self::Context) -> WsResult {
+        let user_id = self.require_user()?.id;
+        // user without any credentials has no row
+        let state = match block_on(self.db.send(FindUserCredential { user_id })) {
+            Ok(Ok(credential)) => credentials_state(&credential),
+            _ => CredentialsState::default(),
+        };
+        Ok(Some(WsMsg::CredentialsLoaded(state)))
+    }
+}
+
+pub struct UpdatePassword {
+    pub password: String,
+    pub current_password: Option<String>,
+    pub totp: Option<String>,
+}
+
+impl WsHandler<UpdatePassword> for WebSocketActor {
+    fn handle_msg(&mut self, msg: UpdatePassword, _ctx: &mut Self::Context) -> WsResult {
+        if !jirs_config::auth::config().password {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        let (user_id, email) = self.require_user().map(|u| (u.id, u.email.clone()))?;
+        if msg.password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Ok(Some(WsMsg::Error(WsError::PasswordTooShort)));
+        }
+        self.check_rate_limit(LimitedAction::PasswordChange, Some(email.as_str()))?;
+        let credential = match block_on(self.db.send(ChangePassword {
+            user_id,
+            current_password: msg.current_password,
+            totp: msg.totp,
+            password: msg.password,
+        })) {
+            Ok(Ok(credential)) => credential,
+            Ok(Err(DatabaseError::User(UserError::InvalidTotpCode))) => {
+                return Ok(Some(WsMsg::Error(WsError::InvalidTotpCode)))
+            }
+            Ok(Err(DatabaseError::User(UserError::InvalidCredentials))) => {
+                return Ok(Some(WsMsg::Error(WsError::InvalidCredentials)))
+            }
+            Ok(Err(e)) => {
+                log::error!("{:?}", e);
+                return Ok(None);
+            }
+            Err(e) => {
+                log::error!("{:?}", e);
+                return Ok(None);
+            }
+        };
+        self.clear_rate_limit(LimitedAction::PasswordChange, email.as_str());
+        Ok(Some(WsMsg::CredentialsLoaded(credentials_state(
+            &credential,
+        ))))
+    }
+}
+
+pub struct StartTotpSetup;
+
+impl WsHandler<StartTotpSetup> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: StartTotpSetup, _ctx: &mut Self::Context) -> WsResult {
+        let config = jirs_config::auth::config();
+        if !config.password || !config.totp {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        let (user_id, email) = self.require_user().map(|u| (u.id, u.email.clone()))?;
+        let credential = db_or_debug_and_return!(self, SetupTotp { user_id });
+        let secret = credential.totp_secret.unwrap_or_default();
+        let url = totp_url(config.totp_issuer.as_str(), email.as_str(), secret.as_str());
+        Ok(Some(WsMsg::TotpSetup(secret, url)))
+    }
+}
+
+pub struct EnableTwoFactor {
+    pub code: String,
+}
+
+impl WsHandler<EnableTwoFactor> for WebSocketActor {
+    fn handle_msg(&mut self, msg: EnableTwoFactor, _ctx: &mut Self::Context) -> WsResult {
+        let config = jirs_config::auth::config();
+        if !config.password || !config.totp {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        let (user_id, email) = self.require_user().map(|u| (u.id, u.email.clone()))?;
+        self.check_rate_limit(LimitedAction::TwoFactor, Some(email.as_str()))?;
+        let credential = db_or_debug_and_return!(
+            self,
+            EnableTotp {
+                user_id,
+                code: msg.code,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidTotpCode))),
+            Ok(None)
+        );
+        self.clear_rate_limit(LimitedAction::TwoFactor, email.as_str());
+        Ok(Some(WsMsg::CredentialsLoaded(credentials_state(
+            &credential,
+        ))))
+    }
+}
+
+pub struct DisableTwoFactor {
+    pub code: String,
+}
+
+impl WsHandler<DisableTwoFactor> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DisableTwoFactor, _ctx: &mut Self::Context) -> WsResult {
+        let config = jirs_config::auth::config();
+        if !config.password || !config.totp {
+            return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)));
+        }
+        let (user_id, email) = self.require_user().map(|u| (u.id, u.email.clone()))?;
+        self.check_rate_limit(LimitedAction::TwoFactor, Some(email.as_str()))?;
+        let credential = db_or_debug_and_return!(
+            self,
+            DisableTotp {
+                user_id,
+                code: msg.code,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidTotpCode))),
+            Ok(None)
+        );
+        self.clear_rate_limit(LimitedAction::TwoFactor, email.as_str());
+        Ok(Some(WsMsg::CredentialsLoaded(credentials_state(
+            &credential,
+        ))))
+    }
+}
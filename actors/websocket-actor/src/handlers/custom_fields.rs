@@ -0,0 +1,143 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{CustomFieldId, CustomFieldType, NameString, WsMsg};
+
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+/// Trim options and drop empty and repeated ones
+fn clean_options(options: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = vec![];
+    for option in options {
+        let option = option.trim().to_string();
+        if !option.is_empty() && !cleaned.contains(&option) {
+            cleaned.push(option);
+        }
+    }
+    cleaned
+}
+
+pub struct LoadCustomFields;
+
+impl WsHandler<LoadCustomFields> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadCustomFields, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadCustomFields>()?.project_id;
+        let fields = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::LoadCustomFields { project_id }
+        );
+        Ok(Some(WsMsg::CustomFieldsLoaded(fields)))
+    }
+}
+
+pub struct CreateCustomField {
+    pub name: NameString,
+    pub field_type: CustomFieldType,
+    pub options: Vec<String>,
+}
+
+impl WsHandler<CreateCustomField> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateCustomField, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateCustomField>()?.project_id;
+        let name = msg.name.trim().to_string();
+        let options = if msg.field_type.requires_options() {
+            clean_options(msg.options)
+        } else {
+            vec![]
+        };
+        if name.is_empty() || (msg.field_type.requires_options() && options.is_empty()) {
+            return Ok(Some(WsMsg::Error(WsError::InvalidCustomField)));
+        }
+        let position = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::LoadCustomFields { project_id }
+        )
+        .len() as i32;
+        let field = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::CreateCustomField {
+                project_id,
+                name,
+                field_type: msg.field_type,
+                options,
+                position,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidCustomField))),
+            Ok(None)
+        );
+        self.broadcast(&WsMsg::CustomFieldCreated(field));
+        Ok(None)
+    }
+}
+
+pub struct UpdateCustomField {
+    pub custom_field_id: CustomFieldId,
+    pub name: NameString,
+    pub options: Vec<String>,
+}
+
+impl WsHandler<UpdateCustomField> for WebSocketActor {
+    fn handle_msg(&mut self, msg: UpdateCustomField, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<UpdateCustomField>()?.project_id;
+        let field = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::FindCustomField {
+                project_id,
+                custom_field_id: msg.custom_field_id,
+            }
+        );
+        let name = msg.name.trim().to_string();
+        let options = if field.field_type.requires_options() {
+            clean_options(msg.options)
+        } else {
+            vec![]
+        };
+        if name.is_empty() || (field.field_type.requires_options() && options.is_empty()) {
+            return Ok(Some(WsMsg::Error(WsError::InvalidCustomField)));
+        }
+        let field = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::UpdateCustomField {
+                project_id,
+                custom_field_id: msg.custom_field_id,
+                name,
+                options,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidCustomField))),
+            Ok(None)
+        );
+        self.broadcast(&WsMsg::CustomFieldUpdated(field));
+        Ok(None)
+    }
+}
+
+pub struct DeleteCustomField {
+    pub custom_field_id: CustomFieldId,
+}
+
+impl WsHandler<DeleteCustomField> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DeleteCustomField, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<DeleteCustomField>()?.project_id;
+        let n = db_or_debug_and_return!(
+            self,
+            database_actor::custom_fields::DeleteCustomField {
+                project_id,
+                custom_field_id: msg.custom_field_id,
+            }
+        );
+        self.broadcast(&WsMsg::CustomFieldDeleted(msg.custom_field_id, n));
+        Ok(None)
+    }
+}
+
+pub struct LoadIssueCustomValues;
+
+impl WsHandler<LoadIssueCustomValues> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadIssueCustomValues, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadIssueCustomValues>()?.project_id;
+        let values = db_or_debug_and_return!(
+            self,
+            database_actor::issue_custom_values::LoadProjectIssueCustomValues { project_id }
+        );
+        Ok(Some(WsMsg::IssueCustomValuesLoaded(values)))
+    }
+}
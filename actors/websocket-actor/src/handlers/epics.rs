@@ -9,7 +9,7 @@ pub struct LoadEpics;
 
 impl WsHandler<LoadEpics> for WebSocketActor {
     fn handle_msg(&mut self, _msg: LoadEpics, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<LoadEpics>()?.project_id;
         let epics = db_or_debug_and_return!(self, database_actor::epics::LoadEpics { project_id });
         Ok(Some(WsMsg::EpicsLoaded(epics)))
     }
@@ -32,7 +32,7 @@ impl WsHandler<CreateEpic> for WebSocketActor {
             user_id,
             project_id,
             ..
-        } = self.require_user_project()?;
+        } = self.require_role::<CreateEpic>()?;
         let epic = db_or_debug_and_return!(
             self,
             database_actor::epics::CreateEpic {
@@ -54,7 +54,7 @@ pub struct UpdateEpicName {
 
 impl WsHandler<UpdateEpicName> for WebSocketActor {
     fn handle_msg(&mut self, msg: UpdateEpicName, _ctx: &mut Self::Context) -> WsResult {
-        let UserProject { project_id, .. } = self.require_user_project()?;
+        let UserProject { project_id, .. } = self.require_role::<UpdateEpicName>()?;
         let epic = db_or_debug_and_return!(
             self,
             database_actor::epics::UpdateEpicName {
@@ -74,7 +74,7 @@ pub struct UpdateEpicStartsAt {
 
 impl WsHandler<UpdateEpicStartsAt> for WebSocketActor {
     fn handle_msg(&mut self, msg: UpdateEpicStartsAt, _ctx: &mut Self::Context) -> WsResult {
-        let UserProject { project_id, .. } = self.require_user_project()?;
+        let UserProject { project_id, .. } = self.require_role::<UpdateEpicStartsAt>()?;
         let epic = db_or_debug_and_return!(
             self,
             database_actor::epics::UpdateEpicStartsAt {
@@ -94,7 +94,7 @@ pub struct UpdateEpicEndsAt {
 
 impl WsHandler<UpdateEpicEndsAt> for WebSocketActor {
     fn handle_msg(&mut self, msg: UpdateEpicEndsAt, _ctx: &mut Self::Context) -> WsResult {
-        let UserProject { project_id, .. } = self.require_user_project()?;
+        let UserProject { project_id, .. } = self.require_role::<UpdateEpicEndsAt>()?;
         let epic = db_or_debug_and_return!(
             self,
             database_actor::epics::UpdateEpicEndsAt {
@@ -114,11 +114,16 @@ pub struct DeleteEpic {
 impl WsHandler<DeleteEpic> for WebSocketActor {
     fn handle_msg(&mut self, msg: DeleteEpic, _ctx: &mut Self::Context) -> WsResult {
         let DeleteEpic { epic_id } = msg;
-        let UserProject { user_id, .. } = self.require_user_project()?;
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = self.require_role::<DeleteEpic>()?;
         let n = db_or_debug_and_return!(
             self,
             database_actor::epics::DeleteEpic {
                 user_id: *user_id,
+                project_id: *project_id,
                 epic_id,
             }
         );
@@ -133,9 +138,11 @@ pub struct TransformEpic {
 
 impl WsHandler<TransformEpic> for WebSocketActor {
     fn handle_msg(&mut self, msg: TransformEpic, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<TransformEpic>()?.project_id;
         let epic: jirs_data::Epic = db_or_debug_and_return!(
             self,
             database_actor::epics::FindEpic {
+                project_id,
                 epic_id: msg.epic_id
             }
         );
@@ -154,13 +161,16 @@ impl WsHandler<TransformEpic> for WebSocketActor {
                 project_id: epic.project_id,
                 reporter_id: epic.user_id,
                 user_ids: vec![epic.user_id],
-                epic_id: None
+                epic_id: None,
+                parent_id: None,
+                custom_values: vec![],
             }
         );
         let n = db_or_debug_and_return!(
             self,
             database_actor::epics::DeleteEpic {
                 user_id: epic.user_id,
+                project_id,
                 epic_id: epic.id
             }
         );
@@ -1,11 +1,12 @@
 use database_actor::invitations;
 use database_actor::messages::CreateMessageReceiver;
 use futures::executor::block_on;
+use jirs_data::msg::WsError;
 use jirs_data::{
     EmailString, InvitationId, InvitationToken, MessageType, UserRole, UsernameString, WsMsg,
 };
 
-use crate::server::InnerMsg;
+use crate::rate_limit::LimitedAction;
 use crate::{
     db_or_debug_and_return, mail_or_debug_and_return, WebSocketActor, WsHandler, WsMessageSender,
     WsResult,
@@ -15,6 +16,7 @@ pub struct ListInvitation;
 
 impl WsHandler<ListInvitation> for WebSocketActor {
     fn handle_msg(&mut self, _msg: ListInvitation, _ctx: &mut Self::Context) -> WsResult {
+        self.require_role::<ListInvitation>()?;
         let user_id = match self.current_user.as_ref().map(|u| u.id) {
             Some(id) => id,
             _ => return Ok(None),
@@ -32,13 +34,11 @@ pub struct CreateInvitation {
 
 impl WsHandler<CreateInvitation> for WebSocketActor {
     fn handle_msg(&mut self, msg: CreateInvitation, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = match self.current_user_project.as_ref() {
-            Some(up) => up.project_id,
-            _ => return Ok(None),
-        };
+        let project_id = self.require_role::<CreateInvitation>()?.project_id;
         let (user_id, inviter_name) = self.require_user().map(|u| (u.id, u.name.clone()))?;
 
         let CreateInvitation { email, name, role } = msg;
+        self.check_rate_limit(LimitedAction::Invitation, Some(email.as_str()))?;
         let invitation = db_or_debug_and_return!(
             self,
             database_actor::invitations::CreateInvitation {
@@ -71,10 +71,7 @@ impl WsHandler<CreateInvitation> for WebSocketActor {
             message_type: MessageType::ReceivedInvitation,
             hyper_link: format!("#{}", invitation.bind_token),
         })) {
-            self.addr.do_send(InnerMsg::SendToUser(
-                message.receiver_id,
-                WsMsg::MessageUpdated(message),
-            ));
+            self.changes()?.deliver_message(message);
         }
 
         Ok(Some(WsMsg::InvitationSendSuccess))
@@ -87,9 +84,13 @@ pub struct DeleteInvitation {
 
 impl WsHandler<DeleteInvitation> for WebSocketActor {
     fn handle_msg(&mut self, msg: DeleteInvitation, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
+        let project_id = self.require_role::<DeleteInvitation>()?.project_id;
         let DeleteInvitation { id } = msg;
-        let _ = db_or_debug_and_return!(self, invitations::DeleteInvitation { id });
+        let n = db_or_debug_and_return!(self, invitations::DeleteInvitation { id, project_id });
+        // invitation from other project is not visible for current member
+        if n == 0 {
+            return Err(WsMsg::Error(WsError::Forbidden));
+        }
         Ok(None)
     }
 }
@@ -100,9 +101,12 @@ pub struct RevokeInvitation {
 
 impl WsHandler<RevokeInvitation> for WebSocketActor {
     fn handle_msg(&mut self, msg: RevokeInvitation, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
+        let project_id = self.require_role::<RevokeInvitation>()?.project_id;
         let RevokeInvitation { id } = msg;
-        let _ = db_or_debug_and_return!(self, invitations::RevokeInvitation { id });
+        let n = db_or_debug_and_return!(self, invitations::RevokeInvitation { id, project_id });
+        if n == 0 {
+            return Err(WsMsg::Error(WsError::Forbidden));
+        }
         Ok(Some(WsMsg::InvitationRevokeSuccess(id)))
     }
 }
@@ -144,6 +148,9 @@ impl WsHandler<AcceptInvitation> for WebSocketActor {
             );
         }
 
-        Ok(Some(WsMsg::InvitationAcceptSuccess(token.access_token)))
+        Ok(Some(WsMsg::InvitationAcceptSuccess(
+            token.access_token,
+            token.refresh_token,
+        )))
     }
 }
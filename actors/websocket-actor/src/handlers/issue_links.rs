@@ -0,0 +1,79 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{IssueId, IssueLinkId, IssueLinkType, WsMsg};
+
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadIssueLinks;
+
+impl WsHandler<LoadIssueLinks> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadIssueLinks, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadIssueLinks>()?.project_id;
+        let links = db_or_debug_and_return!(
+            self,
+            database_actor::issue_links::LoadProjectIssueLinks { project_id }
+        );
+        Ok(Some(WsMsg::IssueLinksLoaded(links)))
+    }
+}
+
+pub struct CreateIssueLink {
+    pub link_type: IssueLinkType,
+    pub source_id: IssueId,
+    pub target_id: IssueId,
+}
+
+impl WsHandler<CreateIssueLink> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateIssueLink, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateIssueLink>()?.project_id;
+        if msg.source_id == msg.target_id {
+            return Ok(Some(WsMsg::Error(WsError::InvalidIssueLink)));
+        }
+        for issue_id in [msg.source_id, msg.target_id].iter().copied() {
+            let _ = db_or_debug_and_return!(
+                self,
+                database_actor::issues::FindProjectIssue {
+                    project_id,
+                    issue_id
+                },
+                Ok(Some(WsMsg::Error(WsError::InvalidIssueLink))),
+                Ok(None)
+            );
+        }
+
+        let CreateIssueLink {
+            link_type,
+            source_id,
+            target_id,
+        } = msg;
+        let link = db_or_debug_and_return!(
+            self,
+            database_actor::issue_links::CreateIssueLink {
+                link_type,
+                source_id,
+                target_id,
+            }
+        );
+        self.broadcast(&WsMsg::IssueLinkCreated(link));
+        Ok(None)
+    }
+}
+
+pub struct DeleteIssueLink {
+    pub link_id: IssueLinkId,
+}
+
+impl WsHandler<DeleteIssueLink> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DeleteIssueLink, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<DeleteIssueLink>()?.project_id;
+        let n = db_or_debug_and_return!(
+            self,
+            database_actor::issue_links::DeleteIssueLink {
+                project_id,
+                link_id: msg.link_id,
+            }
+        );
+        self.broadcast(&WsMsg::IssueLinkDeleted(msg.link_id, n));
+        Ok(None)
+    }
+}
@@ -1,14 +1,16 @@
 use database_actor::issue_statuses;
 use futures::executor::block_on;
-use jirs_data::{IssueStatusId, Position, TitleString, WsMsg};
+use jirs_data::msg::WsError;
+use jirs_data::{IssueId, IssueStatusId, Position, TitleString, WsMsg};
 
+use crate::changes::ChangeContext;
 use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
 
 pub struct LoadIssueStatuses;
 
 impl WsHandler<LoadIssueStatuses> for WebSocketActor {
     fn handle_msg(&mut self, _msg: LoadIssueStatuses, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<LoadIssueStatuses>()?.project_id;
 
         let v = db_or_debug_and_return!(self, issue_statuses::LoadIssueStatuses { project_id });
         Ok(Some(WsMsg::IssueStatusesLoaded(v)))
@@ -22,7 +24,7 @@ pub struct CreateIssueStatus {
 
 impl WsHandler<CreateIssueStatus> for WebSocketActor {
     fn handle_msg(&mut self, msg: CreateIssueStatus, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<CreateIssueStatus>()?.project_id;
 
         let CreateIssueStatus { position, name } = msg;
         let issue_status = db_or_debug_and_return!(
@@ -43,7 +45,7 @@ pub struct DeleteIssueStatus {
 
 impl WsHandler<DeleteIssueStatus> for WebSocketActor {
     fn handle_msg(&mut self, msg: DeleteIssueStatus, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<DeleteIssueStatus>()?.project_id;
 
         let DeleteIssueStatus { issue_status_id } = msg;
         let n = db_or_debug_and_return!(
@@ -65,7 +67,7 @@ pub struct UpdateIssueStatus {
 
 impl WsHandler<UpdateIssueStatus> for WebSocketActor {
     fn handle_msg(&mut self, msg: UpdateIssueStatus, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<UpdateIssueStatus>()?.project_id;
 
         let UpdateIssueStatus {
             issue_status_id,
@@ -88,3 +90,86 @@ impl WsHandler<UpdateIssueStatus> for WebSocketActor {
         Ok(msg)
     }
 }
+
+pub struct UpdateIssueStatusWipLimit {
+    pub issue_status_id: IssueStatusId,
+    pub wip_limit: Option<i32>,
+    pub wip_strict: bool,
+}
+
+impl WsHandler<UpdateIssueStatusWipLimit> for WebSocketActor {
+    fn handle_msg(&mut self, msg: UpdateIssueStatusWipLimit, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<UpdateIssueStatusWipLimit>()?.project_id;
+
+        let UpdateIssueStatusWipLimit {
+            issue_status_id,
+            wip_limit,
+            wip_strict,
+        } = msg;
+        if matches!(wip_limit, Some(limit) if limit <= 0) {
+            return Ok(Some(WsMsg::Error(WsError::InvalidWipLimit)));
+        }
+        let issue_status = db_or_debug_and_return!(
+            self,
+            issue_statuses::UpdateIssueStatusWipLimit {
+                issue_status_id,
+                project_id,
+                wip_limit,
+                wip_strict,
+            }
+        );
+        let msg = Some(WsMsg::IssueStatusUpdated(issue_status));
+        if let Some(ws_msg) = msg.as_ref() {
+            self.broadcast(ws_msg)
+        }
+        Ok(msg)
+    }
+}
+
+impl ChangeContext<'_> {
+    /// Check strict work in progress limit before moving issue to given
+    /// status. Rejected move is returned as error.
+    pub(crate) fn check_wip_limit(
+        &self,
+        issue_id: IssueId,
+        to_status_id: IssueStatusId,
+    ) -> Result<(), WsError> {
+        let project_id = self.user_project.project_id;
+        let rejected = || Err(WsError::WipLimitExceeded);
+
+        let statuses = db_or_debug_and_return!(
+            self,
+            issue_statuses::LoadIssueStatuses { project_id },
+            rejected(),
+            rejected()
+        );
+        let status = match statuses.iter().find(|is| is.id == to_status_id) {
+            Some(status) if status.wip_strict && status.wip_limit.is_some() => status,
+            _ => return Ok(()),
+        };
+        let issue = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id
+            },
+            rejected(),
+            rejected()
+        );
+        if issue.issue_status_id == to_status_id {
+            return Ok(());
+        }
+        let count = db_or_debug_and_return!(
+            self,
+            issue_statuses::CountStatusIssues {
+                issue_status_id: to_status_id
+            },
+            rejected(),
+            rejected()
+        );
+        if status.is_over_wip_limit(count as usize + 1) {
+            return rejected();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use futures::executor::block_on;
+use jirs_data::{IssueId, UserProject, WsMsg};
+
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadIssueWatchers {
+    pub issue_id: IssueId,
+}
+
+impl WsHandler<LoadIssueWatchers> for WebSocketActor {
+    fn handle_msg(&mut self, msg: LoadIssueWatchers, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadIssueWatchers>()?.project_id;
+        let _ = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id: msg.issue_id
+            }
+        );
+
+        let user_ids = db_or_debug_and_return!(
+            self,
+            database_actor::issue_watchers::LoadIssueWatcherIds {
+                issue_id: msg.issue_id
+            }
+        );
+        Ok(Some(WsMsg::IssueWatchersLoaded(msg.issue_id, user_ids)))
+    }
+}
+
+pub struct WatchIssue {
+    pub issue_id: IssueId,
+    pub watch: bool,
+}
+
+impl WsHandler<WatchIssue> for WebSocketActor {
+    fn handle_msg(&mut self, msg: WatchIssue, ctx: &mut Self::Context) -> WsResult {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.require_role::<WatchIssue>()?;
+        let issue_id = msg.issue_id;
+        let _ = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id
+            }
+        );
+
+        if msg.watch {
+            let _ = db_or_debug_and_return!(
+                self,
+                database_actor::issue_watchers::WatchIssue {
+                    issue_id,
+                    user_ids: vec![user_id],
+                }
+            );
+        } else {
+            let _ = db_or_debug_and_return!(
+                self,
+                database_actor::issue_watchers::UnwatchIssue { issue_id, user_id }
+            );
+        }
+        self.handle_msg(LoadIssueWatchers { issue_id }, ctx)
+    }
+}
@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 
+use actix::Addr;
 use database_actor::issue_assignees::LoadAssignees;
 use database_actor::issues::{LoadProjectIssues, UpdateIssue};
+use database_actor::user_settings::FindUserSetting;
+use database_actor::DbExecutor;
 use futures::executor::block_on;
+use highlight_actor::HighlightActor;
+use jirs_data::msg::WsError;
+use jirs_data::search::IssueSearchQuery;
 use jirs_data::{
-    CreateIssuePayload, IssueAssignee, IssueFieldId, IssueId, IssueStatusId, ListPosition,
-    PayloadVariant, WsMsg,
+    BoardGrouping, CreateIssuePayload, EpicId, IssueFieldId, IssueId, IssueStatusId, ListPosition,
+    PayloadVariant, ProjectId, UserId, UserProject, WsMsg,
 };
 
-use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsMessageSender, WsResult};
 
 pub struct UpdateIssueHandler {
     pub id: i32,
@@ -16,18 +22,28 @@ pub struct UpdateIssueHandler {
     pub payload: PayloadVariant,
 }
 
-impl WsHandler<UpdateIssueHandler> for WebSocketActor {
-    fn handle_msg(&mut self, msg: UpdateIssueHandler, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
-
+impl UpdateIssueHandler {
+    /// Translate single field change into database update message limited to
+    /// given project.
+    /// Markdown description is rendered to html with highlighted code blocks.
+    /// Changes are recorded in issue history as made by given user.
+    /// Time spent is ignored, it's sum of issue worklogs.
+    pub fn into_update_issue(
+        self,
+        project_id: ProjectId,
+        user_id: UserId,
+        hi: &Addr<HighlightActor>,
+    ) -> UpdateIssue {
         let UpdateIssueHandler {
             id,
             field_id,
             payload,
-        } = msg;
+        } = self;
 
         let mut msg = UpdateIssue {
+            project_id,
             issue_id: id,
+            changed_by: Some(user_id),
             ..Default::default()
         };
         match (field_id, payload) {
@@ -38,59 +54,7 @@ impl WsHandler<UpdateIssueHandler> for WebSocketActor {
                 msg.title = Some(s);
             }
             (IssueFieldId::Description, PayloadVariant::String(s)) => {
-                let html: String = {
-                    use pulldown_cmark::*;
-                    let parser = pulldown_cmark::Parser::new(s.as_str());
-                    enum ParseState {
-                        Code(highlight_actor::TextHighlightCode),
-                        Other,
-                    }
-                    let mut state = ParseState::Other;
-
-                    let parser = parser.flat_map(|event| match event {
-                        Event::Text(s) => {
-                            if let ParseState::Code(h) = &mut state {
-                                h.code.push_str(s.as_ref());
-                                return vec![];
-                            }
-                            vec![Event::Text(s)]
-                        }
-                        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(name))) => {
-                            state = ParseState::Code(highlight_actor::TextHighlightCode {
-                                lang: name.to_string(),
-                                code: String::new(),
-                            });
-                            vec![Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(name)))]
-                        }
-                        Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
-                            let ev = if let ParseState::Code(h) = &mut state {
-                                let mut msg = highlight_actor::TextHighlightCode {
-                                    code: String::new(),
-                                    lang: String::new(),
-                                };
-                                std::mem::swap(h, &mut msg);
-                                let highlighted =
-                                    match futures::executor::block_on(self.hi.send(msg)) {
-                                        Ok(Ok(res)) => res,
-                                        _ => s.to_string(),
-                                    };
-                                vec![
-                                    Event::Html(highlighted.into()),
-                                    Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(lang))),
-                                ]
-                            } else {
-                                vec![]
-                            };
-                            state = ParseState::Other;
-                            ev
-                        }
-                        _ => vec![event],
-                    });
-                    let mut buff = String::new();
-                    let _ = html::push_html(&mut buff, parser);
-                    buff
-                };
-                msg.description = Some(html);
+                msg.description = Some(markdown_to_html(hi, s.as_str()));
                 msg.description_text = Some(s);
             }
             (IssueFieldId::IssueStatusId, PayloadVariant::I32(s)) => {
@@ -111,53 +75,96 @@ impl WsHandler<UpdateIssueHandler> for WebSocketActor {
             (IssueFieldId::Estimate, PayloadVariant::OptionI32(o)) => {
                 msg.estimate = o;
             }
-            (IssueFieldId::TimeSpent, PayloadVariant::OptionI32(o)) => {
-                msg.time_spent = o;
-            }
             (IssueFieldId::TimeRemaining, PayloadVariant::OptionI32(o)) => {
                 msg.time_remaining = o;
             }
             (IssueFieldId::EpicName, PayloadVariant::OptionI32(o)) => {
                 msg.epic_id = Some(o);
             }
+            (IssueFieldId::Sprint, PayloadVariant::OptionI32(o)) => {
+                msg.sprint_id = Some(o);
+            }
+            (IssueFieldId::Labels, PayloadVariant::VecI32(v)) => {
+                msg.label_ids = Some(v);
+            }
+            (IssueFieldId::CustomField(custom_field_id), PayloadVariant::CustomValue(v)) => {
+                msg.custom_value = Some((custom_field_id, v));
+            }
             _ => (),
         };
+        msg
+    }
+}
 
-        let issue = db_or_debug_and_return!(self, msg);
-        let mut issue: jirs_data::Issue = issue.into();
-
-        let assignees: Vec<IssueAssignee> =
-            db_or_debug_and_return!(self, LoadAssignees { issue_id: issue.id });
+pub fn markdown_to_html(hi: &Addr<HighlightActor>, s: &str) -> String {
+    use pulldown_cmark::*;
+    let parser = pulldown_cmark::Parser::new(s);
+    enum ParseState {
+        Code(highlight_actor::TextHighlightCode),
+        Other,
+    }
+    let mut state = ParseState::Other;
 
-        for assignee in assignees {
-            issue.user_ids.push(assignee.user_id);
+    let parser = parser.flat_map(|event| match event {
+        Event::Text(s) => {
+            if let ParseState::Code(h) = &mut state {
+                h.code.push_str(s.as_ref());
+                return vec![];
+            }
+            vec![Event::Text(s)]
+        }
+        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(name))) => {
+            state = ParseState::Code(highlight_actor::TextHighlightCode {
+                lang: name.to_string(),
+                code: String::new(),
+            });
+            vec![Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(name)))]
         }
-        self.broadcast(&WsMsg::IssueUpdated(issue));
+        Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+            let ev = if let ParseState::Code(h) = &mut state {
+                let mut msg = highlight_actor::TextHighlightCode {
+                    code: String::new(),
+                    lang: String::new(),
+                };
+                std::mem::swap(h, &mut msg);
+                let highlighted = match futures::executor::block_on(hi.send(msg)) {
+                    Ok(Ok(res)) => res,
+                    _ => s.to_string(),
+                };
+                vec![
+                    Event::Html(highlighted.into()),
+                    Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(lang))),
+                ]
+            } else {
+                vec![]
+            };
+            state = ParseState::Other;
+            ev
+        }
+        _ => vec![event],
+    });
+    let mut buff = String::new();
+    let _ = html::push_html(&mut buff, parser);
+    buff
+}
 
-        Ok(None)
+impl WsHandler<UpdateIssueHandler> for WebSocketActor {
+    fn handle_msg(&mut self, msg: UpdateIssueHandler, _ctx: &mut Self::Context) -> WsResult {
+        self.require_role::<UpdateIssueHandler>()?;
+        match self.changes()?.update_issue(msg) {
+            Ok(_) => Ok(None),
+            Err(e) => e.into_ws_result(),
+        }
     }
 }
 
 impl WsHandler<CreateIssuePayload> for WebSocketActor {
     fn handle_msg(&mut self, msg: CreateIssuePayload, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
-        let msg = database_actor::issues::CreateIssue {
-            title: msg.title,
-            issue_type: msg.issue_type,
-            issue_status_id: msg.issue_status_id,
-            priority: msg.priority,
-            description: msg.description,
-            description_text: msg.description_text,
-            estimate: msg.estimate,
-            time_spent: msg.time_spent,
-            time_remaining: msg.time_remaining,
-            project_id: msg.project_id,
-            reporter_id: msg.reporter_id,
-            user_ids: msg.user_ids,
-            epic_id: msg.epic_id,
-        };
-        let issue = db_or_debug_and_return!(self, msg);
-        Ok(Some(WsMsg::IssueCreated(issue.into())))
+        self.require_role::<CreateIssuePayload>()?;
+        match self.changes()?.create_issue(msg) {
+            Ok(issue) => Ok(Some(WsMsg::IssueCreated(issue))),
+            Err(e) => e.into_ws_result(),
+        }
     }
 }
 
@@ -167,11 +174,29 @@ pub struct DeleteIssue {
 
 impl WsHandler<DeleteIssue> for WebSocketActor {
     fn handle_msg(&mut self, msg: DeleteIssue, _ctx: &mut Self::Context) -> WsResult {
-        self.require_user()?;
+        let project_id = self.require_role::<DeleteIssue>()?.project_id;
+        let issue = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id: msg.id
+            }
+        );
+        let subtasks = db_or_debug_and_return!(
+            self,
+            database_actor::issues::LoadSubtasks { parent_id: msg.id }
+        );
         let n = db_or_debug_and_return!(
             self,
-            database_actor::issues::DeleteIssue { issue_id: msg.id }
+            database_actor::issues::DeleteIssue {
+                project_id,
+                issue_id: msg.id
+            }
         );
+        for subtask in subtasks {
+            self.broadcast(&WsMsg::IssueDeleted(subtask.id, 1));
+        }
+        self.changes()?.broadcast_parent(issue.parent_id);
         Ok(Some(WsMsg::IssueDeleted(msg.id, n)))
     }
 }
@@ -180,55 +205,163 @@ pub struct LoadIssues;
 
 impl WsHandler<LoadIssues> for WebSocketActor {
     fn handle_msg(&mut self, _msg: LoadIssues, _ctx: &mut Self::Context) -> WsResult {
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<LoadIssues>()?.project_id;
 
         let v = db_or_debug_and_return!(self, LoadProjectIssues { project_id });
-        let issues: Vec<jirs_data::Issue> = v.into_iter().map(|i| i.into()).collect();
-        let mut issue_map = HashMap::new();
-        let mut queue = vec![];
-        for issue in issues {
-            let f = self.db.send(LoadAssignees { issue_id: issue.id });
-            queue.push(f);
-            issue_map.insert(issue.id, issue);
-        }
-        for f in queue {
-            if let Ok(Ok(assignees)) = block_on(f) {
-                for assignee in assignees {
-                    if let Some(issue) = issue_map.get_mut(&assignee.issue_id) {
-                        issue.user_ids.push(assignee.user_id);
-                    }
-                }
-            };
-        }
-        let mut issues = vec![];
-        for (_, issue) in issue_map {
-            issues.push(issue);
-        }
-        issues.sort_by(|a, b| a.list_position.cmp(&b.list_position));
+        let issues = issues_with_assignees(&self.db, v);
 
         Ok(Some(WsMsg::ProjectIssuesLoaded(issues)))
     }
 }
 
-pub struct SyncIssueListPosition(pub Vec<(IssueId, ListPosition, IssueStatusId, Option<IssueId>)>);
+pub struct SearchIssues {
+    pub query: String,
+}
+
+impl WsHandler<SearchIssues> for WebSocketActor {
+    fn handle_msg(&mut self, msg: SearchIssues, _ctx: &mut Self::Context) -> WsResult {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.require_role::<SearchIssues>()?;
+        let query = match msg.query.parse::<IssueSearchQuery>() {
+            Ok(query) => query,
+            Err(e) => return Ok(Some(WsMsg::Error(WsError::InvalidSearchQuery(e)))),
+        };
+
+        let v = db_or_debug_and_return!(
+            self,
+            database_actor::issues::SearchIssues {
+                project_id,
+                user_id,
+                query,
+            }
+        );
+        let issues = issues_with_assignees(&self.db, v);
+
+        Ok(Some(WsMsg::IssueSearchResult(issues)))
+    }
+}
+
+pub struct LoadIssueHistory {
+    pub issue_id: IssueId,
+}
+
+impl WsHandler<LoadIssueHistory> for WebSocketActor {
+    fn handle_msg(&mut self, msg: LoadIssueHistory, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadIssueHistory>()?.project_id;
+        let _ = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id: msg.issue_id
+            }
+        );
+
+        let v = db_or_debug_and_return!(
+            self,
+            database_actor::issue_history::LoadIssueHistory {
+                issue_id: msg.issue_id
+            }
+        );
+
+        Ok(Some(WsMsg::IssueHistoryLoaded(msg.issue_id, v)))
+    }
+}
+
+/// Fill assignees and labels of loaded issues, result is sorted by list position
+pub fn issues_with_assignees(
+    db: &Addr<DbExecutor>,
+    v: Vec<database_actor::models::Issue>,
+) -> Vec<jirs_data::Issue> {
+    let issues: Vec<jirs_data::Issue> = v.into_iter().map(|i| i.into()).collect();
+    let mut issue_map = HashMap::new();
+    let mut queue = vec![];
+    for issue in issues {
+        let f = db.send(LoadAssignees { issue_id: issue.id });
+        queue.push(f);
+        issue_map.insert(issue.id, issue);
+    }
+    for f in queue {
+        if let Ok(Ok(assignees)) = block_on(f) {
+            for assignee in assignees {
+                if let Some(issue) = issue_map.get_mut(&assignee.issue_id) {
+                    issue.user_ids.push(assignee.user_id);
+                }
+            }
+        };
+    }
+    for (issue_id, issue) in issue_map.iter_mut() {
+        let msg = database_actor::issue_labels::LoadIssueLabelIds {
+            issue_id: *issue_id,
+        };
+        if let Ok(Ok(label_ids)) = block_on(db.send(msg)) {
+            issue.label_ids = label_ids;
+        }
+    }
+    let mut issues = vec![];
+    for (_, issue) in issue_map {
+        issues.push(issue);
+    }
+    issues.sort_by(|a, b| a.list_position.cmp(&b.list_position));
+    issues
+}
+
+/// Epic of issue is changed only when board is grouped by epic, otherwise
+/// epic lane is not visible and issue can't be moved to other epic.
+pub struct SyncIssueListPosition(pub Vec<(IssueId, ListPosition, IssueStatusId, Option<EpicId>)>);
 
 impl WsHandler<SyncIssueListPosition> for WebSocketActor {
     fn handle_msg(&mut self, msg: SyncIssueListPosition, ctx: &mut Self::Context) -> WsResult {
-        let _project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<SyncIssueListPosition>()?.project_id;
+        let user_id = self.require_user()?.id;
+        let changes = self.changes()?;
+        let previous_statuses: HashMap<IssueId, IssueStatusId> =
+            match block_on(self.db.send(LoadProjectIssues { project_id })) {
+                Ok(Ok(issues)) => issues
+                    .into_iter()
+                    .map(|issue| (issue.id, issue.issue_status_id))
+                    .collect(),
+                _ => HashMap::new(),
+            };
+        let grouping = match block_on(self.db.send(FindUserSetting { user_id })) {
+            Ok(Ok(setting)) => setting.board_grouping,
+            _ => BoardGrouping::default(),
+        };
+        let mut moved = vec![];
         for (issue_id, list_position, status_id, epic_id) in msg.0 {
+            // rejected issue stays in its column, reloaded list reverts it
+            if let Err(e) = changes
+                .check_transition(issue_id, status_id)
+                .and_then(|_| changes.check_wip_limit(issue_id, status_id))
+            {
+                ctx.send_msg(&WsMsg::Error(e));
+                continue;
+            }
             crate::actor_or_debug_and_ignore!(
                 self,
                 db,
                 database_actor::issues::UpdateIssue {
+                    project_id,
                     issue_id,
                     list_position: Some(list_position),
                     issue_status_id: Some(status_id),
-                    epic_id: Some(epic_id),
+                    epic_id: Some(epic_id).filter(|_| grouping == BoardGrouping::Epic),
+                    changed_by: Some(user_id),
                     ..Default::default()
                 },
-                |_| {}
+                |_| {
+                    if previous_statuses.get(&issue_id) != Some(&status_id) {
+                        moved.push((issue_id, status_id));
+                    }
+                }
             );
         }
+        for (issue_id, status_id) in moved {
+            let change = changes.status_change(status_id);
+            changes.notify_watchers(issue_id, change.as_str(), &[]);
+        }
 
         self.handle_msg(LoadIssues, ctx)
     }
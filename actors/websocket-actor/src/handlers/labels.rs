@@ -0,0 +1,71 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{LabelId, NameString, WsMsg};
+
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadLabels;
+
+impl WsHandler<LoadLabels> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadLabels, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadLabels>()?.project_id;
+        let labels =
+            db_or_debug_and_return!(self, database_actor::labels::LoadLabels { project_id });
+        Ok(Some(WsMsg::LabelsLoaded(labels)))
+    }
+}
+
+pub struct CreateLabel {
+    pub name: NameString,
+    pub color: String,
+}
+
+/// Accept only hex css colors like `#fff` or `#2d9cdb`
+fn is_valid_color(color: &str) -> bool {
+    let hex = match color.strip_prefix('#') {
+        Some(hex) => hex,
+        _ => return false,
+    };
+    (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl WsHandler<CreateLabel> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateLabel, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateLabel>()?.project_id;
+        let name = msg.name.trim().to_string();
+        if name.is_empty() || !is_valid_color(msg.color.as_str()) {
+            return Ok(Some(WsMsg::Error(WsError::InvalidLabel)));
+        }
+        let label = db_or_debug_and_return!(
+            self,
+            database_actor::labels::CreateLabel {
+                project_id,
+                name,
+                color: msg.color,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidLabel))),
+            Ok(None)
+        );
+        self.broadcast(&WsMsg::LabelCreated(label));
+        Ok(None)
+    }
+}
+
+pub struct DeleteLabel {
+    pub label_id: LabelId,
+}
+
+impl WsHandler<DeleteLabel> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DeleteLabel, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<DeleteLabel>()?.project_id;
+        let n = db_or_debug_and_return!(
+            self,
+            database_actor::labels::DeleteLabel {
+                project_id,
+                label_id: msg.label_id,
+            }
+        );
+        self.broadcast(&WsMsg::LabelDeleted(msg.label_id, n));
+        Ok(None)
+    }
+}
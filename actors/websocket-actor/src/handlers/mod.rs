@@ -1,24 +1,41 @@
 pub use auth::*;
 pub use comments::*;
+pub use custom_fields::*;
 pub use epics::*;
 pub use hi::*;
 pub use invitations::*;
+pub use issue_links::*;
 pub use issue_statuses::*;
+pub use issue_watchers::*;
 pub use issues::*;
+pub use labels::*;
 pub use messages::*;
 pub use projects::*;
+pub use reports::*;
+pub use sprints::*;
 pub use user_projects::*;
 pub use users::*;
+pub use workflow_transitions::*;
+pub use worklogs::*;
 
 pub mod auth;
 pub mod comments;
+pub mod credentials;
+pub mod custom_fields;
 pub mod epics;
 pub mod hi;
 pub mod invitations;
+pub mod issue_links;
 pub mod issue_statuses;
+pub mod issue_watchers;
 pub mod issues;
+pub mod labels;
 pub mod messages;
 pub mod projects;
+pub mod reports;
+pub mod sprints;
 pub mod user_projects;
 pub mod user_settings;
 pub mod users;
+pub mod workflow_transitions;
+pub mod worklogs;
@@ -10,7 +10,7 @@ impl WsHandler<UpdateProjectPayload> for WebSocketActor {
             user_id,
             project_id,
             ..
-        } = self.require_user_project()?;
+        } = self.require_role::<UpdateProjectPayload>()?;
         let _ = db_or_debug_and_return!(
             self,
             database_actor::projects::UpdateProject {
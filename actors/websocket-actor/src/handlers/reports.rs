@@ -0,0 +1,53 @@
+use futures::executor::block_on;
+use jirs_data::{EndsAt, EpicId, StartsAt, WsMsg};
+
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadEpicBurndown {
+    pub epic_id: EpicId,
+}
+
+impl WsHandler<LoadEpicBurndown> for WebSocketActor {
+    fn handle_msg(&mut self, msg: LoadEpicBurndown, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadEpicBurndown>()?.project_id;
+        let points = db_or_debug_and_return!(
+            self,
+            database_actor::reports::LoadEpicBurndown {
+                project_id,
+                epic_id: msg.epic_id,
+            }
+        );
+        Ok(Some(WsMsg::EpicBurndownLoaded(msg.epic_id, points)))
+    }
+}
+
+pub struct LoadVelocity;
+
+impl WsHandler<LoadVelocity> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadVelocity, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadVelocity>()?.project_id;
+        let points =
+            db_or_debug_and_return!(self, database_actor::reports::LoadVelocity { project_id });
+        Ok(Some(WsMsg::VelocityLoaded(points)))
+    }
+}
+
+pub struct LoadCumulativeFlow {
+    pub from: StartsAt,
+    pub to: EndsAt,
+}
+
+impl WsHandler<LoadCumulativeFlow> for WebSocketActor {
+    fn handle_msg(&mut self, msg: LoadCumulativeFlow, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadCumulativeFlow>()?.project_id;
+        let points = db_or_debug_and_return!(
+            self,
+            database_actor::reports::LoadCumulativeFlow {
+                project_id,
+                from: msg.from,
+                to: msg.to,
+            }
+        );
+        Ok(Some(WsMsg::CumulativeFlowLoaded(points)))
+    }
+}
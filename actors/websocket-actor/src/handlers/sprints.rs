@@ -0,0 +1,128 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{
+    DescriptionString, EndsAt, NameString, SprintId, SprintState, StartsAt, UserProject, WsMsg,
+};
+
+use crate::handlers::issues_with_assignees;
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadSprints;
+
+impl WsHandler<LoadSprints> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadSprints, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadSprints>()?.project_id;
+        let sprints =
+            db_or_debug_and_return!(self, database_actor::sprints::LoadSprints { project_id });
+        Ok(Some(WsMsg::SprintsLoaded(sprints)))
+    }
+}
+
+pub struct CreateSprint {
+    pub name: NameString,
+    pub goal: Option<DescriptionString>,
+}
+
+impl WsHandler<CreateSprint> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateSprint, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateSprint>()?.project_id;
+        let CreateSprint { name, goal } = msg;
+        let sprint = db_or_debug_and_return!(
+            self,
+            database_actor::sprints::CreateSprint {
+                project_id,
+                name,
+                goal,
+            }
+        );
+        self.broadcast(&WsMsg::SprintCreated(sprint));
+        Ok(None)
+    }
+}
+
+pub struct StartSprint {
+    pub sprint_id: SprintId,
+    pub starts_at: StartsAt,
+    pub ends_at: EndsAt,
+}
+
+impl WsHandler<StartSprint> for WebSocketActor {
+    fn handle_msg(&mut self, msg: StartSprint, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<StartSprint>()?.project_id;
+        if msg.ends_at <= msg.starts_at {
+            return Ok(Some(WsMsg::Error(WsError::InvalidSprint)));
+        }
+        let sprints =
+            db_or_debug_and_return!(self, database_actor::sprints::LoadSprints { project_id });
+        if sprints.iter().any(|s| s.state == SprintState::Active) {
+            return Ok(Some(WsMsg::Error(WsError::SprintAlreadyActive)));
+        }
+        match sprints.iter().find(|s| s.id == msg.sprint_id) {
+            Some(sprint) if sprint.state == SprintState::Future => {}
+            _ => return Ok(Some(WsMsg::Error(WsError::InvalidSprint))),
+        };
+
+        let sprint = db_or_debug_and_return!(
+            self,
+            database_actor::sprints::StartSprint {
+                project_id,
+                sprint_id: msg.sprint_id,
+                starts_at: msg.starts_at,
+                ends_at: msg.ends_at,
+            }
+        );
+        self.broadcast(&WsMsg::SprintUpdated(sprint));
+        Ok(None)
+    }
+}
+
+pub struct CompleteSprint {
+    pub sprint_id: SprintId,
+    pub move_to: Option<SprintId>,
+}
+
+impl WsHandler<CompleteSprint> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CompleteSprint, _ctx: &mut Self::Context) -> WsResult {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.require_role::<CompleteSprint>()?;
+        let CompleteSprint { sprint_id, move_to } = msg;
+
+        let sprints =
+            db_or_debug_and_return!(self, database_actor::sprints::LoadSprints { project_id });
+        match sprints.iter().find(|s| s.id == sprint_id) {
+            Some(sprint) if sprint.state == SprintState::Active => {}
+            Some(_) => return Ok(Some(WsMsg::Error(WsError::SprintNotActive))),
+            None => return Ok(Some(WsMsg::Error(WsError::InvalidSprint))),
+        };
+        if let Some(target_id) = move_to {
+            let is_future = sprints
+                .iter()
+                .any(|s| s.id == target_id && s.state == SprintState::Future);
+            if !is_future {
+                return Ok(Some(WsMsg::Error(WsError::InvalidSprint)));
+            }
+        }
+
+        let sprint = db_or_debug_and_return!(
+            self,
+            database_actor::sprints::CompleteSprint {
+                project_id,
+                sprint_id,
+                move_to,
+                completed_by: user_id,
+            }
+        );
+        self.broadcast(&WsMsg::SprintUpdated(sprint));
+
+        let v = db_or_debug_and_return!(
+            self,
+            database_actor::issues::LoadProjectIssues { project_id }
+        );
+        let issues = issues_with_assignees(&self.db, v);
+        self.broadcast(&WsMsg::ProjectIssuesLoaded(issues));
+        Ok(None)
+    }
+}
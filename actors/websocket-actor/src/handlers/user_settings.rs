@@ -1,5 +1,5 @@
 use futures::executor::block_on;
-use jirs_data::{TextEditorMode, UserId, UserSetting, WsMsg};
+use jirs_data::{BoardGrouping, NotificationMode, TextEditorMode, UserId, UserSetting, WsMsg};
 
 use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
 
@@ -8,6 +8,8 @@ pub fn default_user_setting(user_id: UserId) -> UserSetting {
         id: 0,
         user_id,
         text_editor_mode: Default::default(),
+        board_grouping: Default::default(),
+        notification_mode: Default::default(),
     }
 }
 
@@ -28,3 +30,39 @@ impl WsHandler<SetTextEditorMode> for WebSocketActor {
         Ok(Some(WsMsg::UserSettingUpdated(setting)))
     }
 }
+
+pub struct SetBoardGrouping {
+    pub grouping: BoardGrouping,
+}
+
+impl WsHandler<SetBoardGrouping> for WebSocketActor {
+    fn handle_msg(&mut self, msg: SetBoardGrouping, _ctx: &mut Self::Context) -> WsResult {
+        let user_id = self.require_user()?.id;
+        let setting = db_or_debug_and_return!(
+            self,
+            database_actor::user_settings::UpdateBoardGrouping {
+                user_id,
+                grouping: msg.grouping
+            }
+        );
+        Ok(Some(WsMsg::UserSettingUpdated(setting)))
+    }
+}
+
+pub struct SetNotificationMode {
+    pub mode: NotificationMode,
+}
+
+impl WsHandler<SetNotificationMode> for WebSocketActor {
+    fn handle_msg(&mut self, msg: SetNotificationMode, _ctx: &mut Self::Context) -> WsResult {
+        let user_id = self.require_user()?.id;
+        let setting = db_or_debug_and_return!(
+            self,
+            database_actor::user_settings::UpdateNotificationMode {
+                user_id,
+                mode: msg.mode
+            }
+        );
+        Ok(Some(WsMsg::UserSettingUpdated(setting)))
+    }
+}
@@ -1,9 +1,12 @@
 use database_actor::users::Register as DbRegister;
 use database_actor::{self};
 use futures::executor::block_on;
+use jirs_data::msg::WsError;
 use jirs_data::{UserId, UserProject, UserRole, WsMsg};
 
 use crate::handlers::auth::Authenticate;
+use crate::handlers::credentials::MIN_PASSWORD_LENGTH;
+use crate::rate_limit::LimitedAction;
 use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
 
 pub struct LoadProjectUsers;
@@ -12,7 +15,7 @@ impl WsHandler<LoadProjectUsers> for WebSocketActor {
     fn handle_msg(&mut self, _msg: LoadProjectUsers, _ctx: &mut Self::Context) -> WsResult {
         use database_actor::users::LoadProjectUsers as Msg;
 
-        let project_id = self.require_user_project()?.project_id;
+        let project_id = self.require_role::<LoadProjectUsers>()?.project_id;
         let v = db_or_debug_and_return!(self, Msg { project_id });
         Ok(Some(WsMsg::ProjectUsersLoaded(v)))
     }
@@ -21,11 +24,32 @@ impl WsHandler<LoadProjectUsers> for WebSocketActor {
 pub struct Register {
     pub name: String,
     pub email: String,
+    pub password: Option<String>,
 }
 
 impl WsHandler<Register> for WebSocketActor {
     fn handle_msg(&mut self, msg: Register, ctx: &mut Self::Context) -> WsResult {
-        let Register { name, email } = msg;
+        let Register {
+            name,
+            email,
+            password,
+        } = msg;
+        self.check_rate_limit(LimitedAction::SignUp, Some(email.as_str()))?;
+
+        let config = jirs_config::auth::config();
+        match password.as_ref() {
+            Some(_) if !config.password => {
+                return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)))
+            }
+            Some(password) if password.chars().count() < MIN_PASSWORD_LENGTH => {
+                return Ok(Some(WsMsg::Error(WsError::PasswordTooShort)))
+            }
+            None if !config.email_token => {
+                return Ok(Some(WsMsg::Error(WsError::AuthMethodDisabled)))
+            }
+            _ => (),
+        }
+
         let _ = db_or_debug_and_return!(
             self,
             DbRegister {
@@ -33,15 +57,18 @@ impl WsHandler<Register> for WebSocketActor {
                 email: email.clone(),
                 project_id: None,
                 role: UserRole::Owner,
+                password,
             },
             Ok(Some(WsMsg::SignUpPairTaken)),
             Ok(None)
         );
 
-        match self.handle_msg(Authenticate { name, email }, ctx) {
-            Ok(_) => (),
-            Err(e) => return Ok(Some(e)),
-        };
+        if config.email_token {
+            match self.handle_msg(Authenticate { name, email }, ctx) {
+                Ok(_) => (),
+                Err(e) => return Ok(Some(e)),
+            };
+        }
 
         Ok(Some(WsMsg::SignUpSuccess))
     }
@@ -96,7 +123,7 @@ impl WsHandler<RemoveInvitedUser> for WebSocketActor {
             user_id: inviter_id,
             project_id,
             ..
-        } = self.require_user_project()?.clone();
+        } = self.require_role::<RemoveInvitedUser>()?.clone();
         let _ = db_or_debug_and_return!(
             self,
             database_actor::user_projects::RemoveInvitedUser {
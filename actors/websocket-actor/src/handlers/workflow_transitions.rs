@@ -0,0 +1,127 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{IssueId, IssueStatusId, UserRole, WorkflowTransitionId, WsMsg};
+
+use crate::changes::ChangeContext;
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadWorkflowTransitions;
+
+impl WsHandler<LoadWorkflowTransitions> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadWorkflowTransitions, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadWorkflowTransitions>()?.project_id;
+        let transitions = db_or_debug_and_return!(
+            self,
+            database_actor::workflow_transitions::LoadWorkflowTransitions { project_id }
+        );
+        Ok(Some(WsMsg::WorkflowTransitionsLoaded(transitions)))
+    }
+}
+
+pub struct CreateWorkflowTransition {
+    pub from_status_id: IssueStatusId,
+    pub to_status_id: IssueStatusId,
+    pub required_role: Option<UserRole>,
+    pub require_assignee: bool,
+    pub require_estimate: bool,
+}
+
+impl WsHandler<CreateWorkflowTransition> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateWorkflowTransition, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateWorkflowTransition>()?.project_id;
+        let statuses = db_or_debug_and_return!(
+            self,
+            database_actor::issue_statuses::LoadIssueStatuses { project_id }
+        );
+        let in_project = |id: IssueStatusId| statuses.iter().any(|status| status.id == id);
+        if msg.from_status_id == msg.to_status_id
+            || !in_project(msg.from_status_id)
+            || !in_project(msg.to_status_id)
+        {
+            return Ok(Some(WsMsg::Error(WsError::InvalidWorkflowTransition)));
+        }
+        let transition = db_or_debug_and_return!(
+            self,
+            database_actor::workflow_transitions::CreateWorkflowTransition {
+                project_id,
+                from_status_id: msg.from_status_id,
+                to_status_id: msg.to_status_id,
+                required_role: msg.required_role,
+                require_assignee: msg.require_assignee,
+                require_estimate: msg.require_estimate,
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidWorkflowTransition))),
+            Ok(None)
+        );
+        self.broadcast(&WsMsg::WorkflowTransitionCreated(transition));
+        Ok(None)
+    }
+}
+
+pub struct DeleteWorkflowTransition {
+    pub workflow_transition_id: WorkflowTransitionId,
+}
+
+impl WsHandler<DeleteWorkflowTransition> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DeleteWorkflowTransition, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<DeleteWorkflowTransition>()?.project_id;
+        let n = db_or_debug_and_return!(
+            self,
+            database_actor::workflow_transitions::DeleteWorkflowTransition {
+                project_id,
+                workflow_transition_id: msg.workflow_transition_id,
+            }
+        );
+        self.broadcast(&WsMsg::WorkflowTransitionDeleted(
+            msg.workflow_transition_id,
+            n,
+        ));
+        Ok(None)
+    }
+}
+
+impl ChangeContext<'_> {
+    /// Check project workflow before moving issue to given status. Rejected
+    /// move is returned as error.
+    pub(crate) fn check_transition(
+        &self,
+        issue_id: IssueId,
+        to_status_id: IssueStatusId,
+    ) -> Result<(), WsError> {
+        let (project_id, role) = (self.user_project.project_id, self.user_project.role);
+        let rejected = || Err(WsError::TransitionNotAllowed);
+
+        let transitions = db_or_debug_and_return!(
+            self,
+            database_actor::workflow_transitions::LoadWorkflowTransitions { project_id },
+            rejected(),
+            rejected()
+        );
+        if transitions.is_empty() {
+            return Ok(());
+        }
+        let issue = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id
+            },
+            rejected(),
+            rejected()
+        );
+        let assignees = db_or_debug_and_return!(
+            self,
+            database_actor::issue_assignees::LoadAssignees { issue_id },
+            rejected(),
+            rejected()
+        );
+        jirs_data::check_transition(
+            &transitions,
+            issue.issue_status_id,
+            to_status_id,
+            role,
+            !assignees.is_empty(),
+            issue.estimate.is_some(),
+        )
+    }
+}
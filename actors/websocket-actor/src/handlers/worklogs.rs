@@ -0,0 +1,160 @@
+use futures::executor::block_on;
+use jirs_data::msg::WsError;
+use jirs_data::{IssueId, StartsAt, UserProject, WorklogId, WsMsg};
+
+use crate::handlers::issues_with_assignees;
+use crate::{db_or_debug_and_return, WebSocketActor, WsHandler, WsResult};
+
+pub struct LoadWorklogs;
+
+impl WsHandler<LoadWorklogs> for WebSocketActor {
+    fn handle_msg(&mut self, _msg: LoadWorklogs, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<LoadWorklogs>()?.project_id;
+        let worklogs = db_or_debug_and_return!(
+            self,
+            database_actor::worklogs::LoadProjectWorklogs { project_id }
+        );
+        Ok(Some(WsMsg::WorklogsLoaded(worklogs)))
+    }
+}
+
+/// Trim comment and drop it when nothing left
+fn clean_comment(comment: Option<String>) -> Option<String> {
+    comment
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+pub struct CreateWorklog {
+    pub issue_id: IssueId,
+    pub duration: i32,
+    pub started_at: StartsAt,
+    pub comment: Option<String>,
+}
+
+impl WsHandler<CreateWorklog> for WebSocketActor {
+    fn handle_msg(&mut self, msg: CreateWorklog, _ctx: &mut Self::Context) -> WsResult {
+        let project_id = self.require_role::<CreateWorklog>()?.project_id;
+        let user_id = self.require_user()?.id;
+        if msg.duration <= 0 {
+            return Ok(Some(WsMsg::Error(WsError::InvalidWorklog)));
+        }
+        let _ = db_or_debug_and_return!(
+            self,
+            database_actor::issues::FindProjectIssue {
+                project_id,
+                issue_id: msg.issue_id
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidWorklog))),
+            Ok(None)
+        );
+        let worklog = db_or_debug_and_return!(
+            self,
+            database_actor::worklogs::CreateWorklog {
+                project_id,
+                issue_id: msg.issue_id,
+                user_id,
+                duration: msg.duration,
+                started_at: msg.started_at,
+                comment: clean_comment(msg.comment),
+            }
+        );
+        let issue_id = worklog.issue_id;
+        self.broadcast(&WsMsg::WorklogCreated(worklog));
+        self.broadcast_time_spent(issue_id);
+        Ok(None)
+    }
+}
+
+pub struct UpdateWorklog {
+    pub worklog_id: WorklogId,
+    pub duration: i32,
+    pub started_at: StartsAt,
+    pub comment: Option<String>,
+}
+
+impl WsHandler<UpdateWorklog> for WebSocketActor {
+    fn handle_msg(&mut self, msg: UpdateWorklog, _ctx: &mut Self::Context) -> WsResult {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.require_role::<UpdateWorklog>()?;
+        if msg.duration <= 0 {
+            return Ok(Some(WsMsg::Error(WsError::InvalidWorklog)));
+        }
+        let worklog = db_or_debug_and_return!(
+            self,
+            database_actor::worklogs::UpdateWorklog {
+                project_id,
+                worklog_id: msg.worklog_id,
+                user_id,
+                duration: msg.duration,
+                started_at: msg.started_at,
+                comment: clean_comment(msg.comment),
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidWorklog))),
+            Ok(None)
+        );
+        let issue_id = worklog.issue_id;
+        self.broadcast(&WsMsg::WorklogUpdated(worklog));
+        self.broadcast_time_spent(issue_id);
+        Ok(None)
+    }
+}
+
+pub struct DeleteWorklog {
+    pub worklog_id: WorklogId,
+}
+
+impl WsHandler<DeleteWorklog> for WebSocketActor {
+    fn handle_msg(&mut self, msg: DeleteWorklog, _ctx: &mut Self::Context) -> WsResult {
+        let UserProject {
+            user_id,
+            project_id,
+            ..
+        } = *self.require_role::<DeleteWorklog>()?;
+        let worklog = db_or_debug_and_return!(
+            self,
+            database_actor::worklogs::FindWorklog {
+                project_id,
+                worklog_id: msg.worklog_id
+            },
+            Ok(Some(WsMsg::Error(WsError::InvalidWorklog))),
+            Ok(None)
+        );
+        if worklog.user_id != user_id {
+            return Ok(Some(WsMsg::Error(WsError::InvalidWorklog)));
+        }
+        let n = db_or_debug_and_return!(
+            self,
+            database_actor::worklogs::DeleteWorklog {
+                project_id,
+                worklog_id: msg.worklog_id,
+                user_id,
+            }
+        );
+        self.broadcast(&WsMsg::WorklogDeleted(msg.worklog_id, n));
+        self.broadcast_time_spent(worklog.issue_id);
+        Ok(None)
+    }
+}
+
+impl WebSocketActor {
+    /// Send issue with recalculated time spent and its parent
+    fn broadcast_time_spent(&self, issue_id: IssueId) {
+        let changes = match self.changes() {
+            Ok(changes) => changes,
+            _ => return,
+        };
+        if let Ok(Ok(issue)) =
+            block_on(self.db.send(database_actor::issues::LoadIssue { issue_id }))
+        {
+            let parent_id = issue.parent_id;
+            if let Some(issue) = issues_with_assignees(&self.db, vec![issue]).pop() {
+                changes.broadcast(&WsMsg::IssueUpdated(issue));
+            }
+            changes.broadcast_parent(parent_id);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 #[macro_use]
 extern crate log;
 
-use actix::{Actor, ActorContext, Addr, AsyncContext, Handler, Recipient, StreamHandler};
+use actix::{
+    Actor, ActorContext, Addr, AsyncContext, Handler, Recipient, SpawnHandle, StreamHandler,
+};
 use actix_web::web::{self, Data};
 use actix_web::{get, Error, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
@@ -9,16 +11,26 @@ use database_actor::projects::LoadCurrentProject;
 use database_actor::user_projects::CurrentUserProject;
 use database_actor::DbExecutor;
 use futures::executor::block_on;
+use jirs_data::msg::WsError;
 use jirs_data::{Project, User, UserProject, WsMsg};
 use log::*;
 use mail_actor::MailExecutor;
 
+use crate::changes::ChangeContext;
 use crate::handlers::*;
+use crate::rate_limit::{Clear, Hit, LimitedAction, RateLimiter};
 use crate::server::{InnerMsg, WsServer};
 
+pub mod assignments;
+pub mod changes;
 pub mod handlers;
+pub mod mentions;
+pub mod notifications;
+pub mod permissions;
 pub mod prelude;
+pub mod rate_limit;
 pub mod server;
+pub mod watchers;
 
 pub type WsResult = std::result::Result<Option<WsMsg>, WsMsg>;
 
@@ -31,6 +43,11 @@ struct WebSocketActor {
     mail: Data<Addr<MailExecutor>>,
     addr: Addr<WsServer>,
     hi: Data<Addr<highlight_actor::HighlightActor>>,
+    limiter: Addr<RateLimiter>,
+    remote_addr: Option<String>,
+    current_token_id: Option<jirs_data::TokenId>,
+    /// Signs out connection when access token expires
+    token_expiry: Option<SpawnHandle>,
     current_user: Option<jirs_data::User>,
     current_user_project: Option<jirs_data::UserProject>,
     current_project: Option<jirs_data::Project>,
@@ -51,6 +68,9 @@ impl Handler<InnerMsg> for WebSocketActor {
 
     fn handle(&mut self, msg: InnerMsg, ctx: &mut <Self as Actor>::Context) -> Self::Result {
         if let InnerMsg::Transfer(msg) = msg {
+            if msg == WsMsg::AuthorizeExpired {
+                self.sign_out(ctx);
+            }
             ctx.send_msg(&msg)
         };
     }
@@ -71,8 +91,13 @@ impl WebSocketActor {
         msg: WsMsg,
         ctx: &mut <WebSocketActor as Actor>::Context,
     ) -> WsResult {
-        if msg != WsMsg::Ping && msg != WsMsg::Pong {
-            debug!("incoming message: {:?}", msg);
+        match msg {
+            WsMsg::Ping | WsMsg::Pong => (),
+            // never write passwords to logs
+            WsMsg::PasswordSignInRequest(..) | WsMsg::PasswordUpdate(..) => {
+                debug!("incoming message: password request")
+            }
+            _ => debug!("incoming message: {:?}", msg),
         }
 
         let msg = match msg {
@@ -94,6 +119,32 @@ impl WebSocketActor {
                 self.handle_msg(SyncIssueListPosition(sync), ctx)?
             }
             WsMsg::ProjectIssuesLoad => self.handle_msg(LoadIssues, ctx)?,
+            WsMsg::IssueSearch(query) => self.handle_msg(SearchIssues { query }, ctx)?,
+            WsMsg::IssueHistoryLoad(issue_id) => {
+                self.handle_msg(LoadIssueHistory { issue_id }, ctx)?
+            }
+
+            // issue watchers
+            WsMsg::IssueWatchersLoad(issue_id) => {
+                self.handle_msg(LoadIssueWatchers { issue_id }, ctx)?
+            }
+            WsMsg::IssueWatch(issue_id, watch) => {
+                self.handle_msg(WatchIssue { issue_id, watch }, ctx)?
+            }
+
+            // issue links
+            WsMsg::IssueLinksLoad => self.handle_msg(issue_links::LoadIssueLinks, ctx)?,
+            WsMsg::IssueLinkCreate(link_type, source_id, target_id) => self.handle_msg(
+                issue_links::CreateIssueLink {
+                    link_type,
+                    source_id,
+                    target_id,
+                },
+                ctx,
+            )?,
+            WsMsg::IssueLinkDelete(link_id) => {
+                self.handle_msg(issue_links::DeleteIssueLink { link_id }, ctx)?
+            }
 
             // issue statuses
             WsMsg::IssueStatusesLoad => self.handle_msg(LoadIssueStatuses, ctx)?,
@@ -108,6 +159,15 @@ impl WebSocketActor {
                 },
                 ctx,
             )?,
+            WsMsg::IssueStatusWipLimitUpdate(issue_status_id, wip_limit, wip_strict) => self
+                .handle_msg(
+                    UpdateIssueStatusWipLimit {
+                        issue_status_id,
+                        wip_limit,
+                        wip_strict,
+                    },
+                    ctx,
+                )?,
             WsMsg::IssueStatusCreate(name, position) => {
                 self.handle_msg(CreateIssueStatus { name, position }, ctx)?
             }
@@ -133,12 +193,48 @@ impl WebSocketActor {
             WsMsg::AuthenticateRequest(email, name) => {
                 self.handle_msg(Authenticate { name, email }, ctx)?
             }
+            WsMsg::RefreshToken(refresh_token) => {
+                self.handle_msg(RefreshAccessToken { refresh_token }, ctx)?
+            }
+            WsMsg::AuthMethodsLoad => self.handle_msg(LoadAuthMethods, ctx)?,
+            WsMsg::PasswordSignInRequest(email, name, password, totp) => self.handle_msg(
+                PasswordSignIn {
+                    name,
+                    email,
+                    password,
+                    totp,
+                },
+                ctx,
+            )?,
+
+            // credentials
+            WsMsg::CredentialsLoad => self.handle_msg(credentials::LoadCredentials, ctx)?,
+            WsMsg::PasswordUpdate(password, current_password, totp) => self.handle_msg(
+                credentials::UpdatePassword {
+                    password,
+                    current_password,
+                    totp,
+                },
+                ctx,
+            )?,
+            WsMsg::TotpSetupRequest => self.handle_msg(credentials::StartTotpSetup, ctx)?,
+            WsMsg::TotpEnable(code) => {
+                self.handle_msg(credentials::EnableTwoFactor { code }, ctx)?
+            }
+            WsMsg::TotpDisable(code) => {
+                self.handle_msg(credentials::DisableTwoFactor { code }, ctx)?
+            }
+
+            // sessions
+            WsMsg::SessionsLoad => self.handle_msg(LoadSessions, ctx)?,
+            WsMsg::SessionsRevokeAll => self.handle_msg(RevokeAllSessions, ctx)?,
 
             // register
-            WsMsg::SignUpRequest(email, username) => self.handle_msg(
+            WsMsg::SignUpRequest(email, username, password) => self.handle_msg(
                 Register {
                     name: username,
                     email,
+                    password,
                 },
                 ctx,
             )?,
@@ -153,6 +249,12 @@ impl WebSocketActor {
             WsMsg::UserSettingSetEditorMode(mode) => {
                 self.handle_msg(user_settings::SetTextEditorMode { mode }, ctx)?
             }
+            WsMsg::UserSettingSetBoardGrouping(grouping) => {
+                self.handle_msg(user_settings::SetBoardGrouping { grouping }, ctx)?
+            }
+            WsMsg::UserSettingSetNotificationMode(mode) => {
+                self.handle_msg(user_settings::SetNotificationMode { mode }, ctx)?
+            }
 
             // comments
             WsMsg::IssueCommentsLoad(issue_id) => {
@@ -212,6 +314,117 @@ impl WebSocketActor {
                 ctx,
             )?,
 
+            // labels
+            WsMsg::LabelsLoad => self.handle_msg(labels::LoadLabels, ctx)?,
+            WsMsg::LabelCreate(name, color) => {
+                self.handle_msg(labels::CreateLabel { name, color }, ctx)?
+            }
+            WsMsg::LabelDelete(label_id) => {
+                self.handle_msg(labels::DeleteLabel { label_id }, ctx)?
+            }
+
+            // custom fields
+            WsMsg::CustomFieldsLoad => self.handle_msg(custom_fields::LoadCustomFields, ctx)?,
+            WsMsg::CustomFieldCreate(name, field_type, options) => self.handle_msg(
+                custom_fields::CreateCustomField {
+                    name,
+                    field_type,
+                    options,
+                },
+                ctx,
+            )?,
+            WsMsg::CustomFieldUpdate(custom_field_id, name, options) => self.handle_msg(
+                custom_fields::UpdateCustomField {
+                    custom_field_id,
+                    name,
+                    options,
+                },
+                ctx,
+            )?,
+            WsMsg::CustomFieldDelete(custom_field_id) => {
+                self.handle_msg(custom_fields::DeleteCustomField { custom_field_id }, ctx)?
+            }
+            WsMsg::IssueCustomValuesLoad => {
+                self.handle_msg(custom_fields::LoadIssueCustomValues, ctx)?
+            }
+
+            // worklogs
+            WsMsg::WorklogsLoad => self.handle_msg(worklogs::LoadWorklogs, ctx)?,
+            WsMsg::WorklogCreate(issue_id, duration, started_at, comment) => self.handle_msg(
+                worklogs::CreateWorklog {
+                    issue_id,
+                    duration,
+                    started_at,
+                    comment,
+                },
+                ctx,
+            )?,
+            WsMsg::WorklogUpdate(worklog_id, duration, started_at, comment) => self.handle_msg(
+                worklogs::UpdateWorklog {
+                    worklog_id,
+                    duration,
+                    started_at,
+                    comment,
+                },
+                ctx,
+            )?,
+            WsMsg::WorklogDelete(worklog_id) => {
+                self.handle_msg(worklogs::DeleteWorklog { worklog_id }, ctx)?
+            }
+
+            // workflow
+            WsMsg::WorkflowTransitionsLoad => {
+                self.handle_msg(workflow_transitions::LoadWorkflowTransitions, ctx)?
+            }
+            WsMsg::WorkflowTransitionCreate(
+                from_status_id,
+                to_status_id,
+                required_role,
+                require_assignee,
+                require_estimate,
+            ) => self.handle_msg(
+                workflow_transitions::CreateWorkflowTransition {
+                    from_status_id,
+                    to_status_id,
+                    required_role,
+                    require_assignee,
+                    require_estimate,
+                },
+                ctx,
+            )?,
+            WsMsg::WorkflowTransitionDelete(workflow_transition_id) => self.handle_msg(
+                workflow_transitions::DeleteWorkflowTransition {
+                    workflow_transition_id,
+                },
+                ctx,
+            )?,
+
+            // reports
+            WsMsg::EpicBurndownLoad(epic_id) => {
+                self.handle_msg(reports::LoadEpicBurndown { epic_id }, ctx)?
+            }
+            WsMsg::VelocityLoad => self.handle_msg(reports::LoadVelocity, ctx)?,
+            WsMsg::CumulativeFlowLoad(from, to) => {
+                self.handle_msg(reports::LoadCumulativeFlow { from, to }, ctx)?
+            }
+
+            // sprints
+            WsMsg::SprintsLoad => self.handle_msg(sprints::LoadSprints, ctx)?,
+            WsMsg::SprintCreate(name, goal) => {
+                self.handle_msg(sprints::CreateSprint { name, goal }, ctx)?
+            }
+            WsMsg::SprintStart(sprint_id, starts_at, ends_at) => self.handle_msg(
+                sprints::StartSprint {
+                    sprint_id,
+                    starts_at,
+                    ends_at,
+                },
+                ctx,
+            )?,
+            WsMsg::SprintComplete(sprint_id, move_to) => {
+                self.handle_msg(sprints::CompleteSprint { sprint_id, move_to }, ctx)?
+            }
+
             // hi
             WsMsg::HighlightCode(lang, code) => {
                 self.handle_msg(hi::HighlightCode(lang, code), ctx)?
@@ -251,6 +464,31 @@ impl WebSocketActor {
         };
     }
 
+    /// Forget signed in user and leave project channel
+    fn sign_out(&mut self, ctx: &mut <WebSocketActor as Actor>::Context) {
+        if let Some(handle) = self.token_expiry.take() {
+            ctx.cancel_future(handle);
+        }
+        if let Some(user) = self.current_user.as_ref() {
+            self.addr
+                .do_send(InnerMsg::Disconnect(user.id, ctx.address().recipient()));
+        }
+        if let (Some(user), Some(up)) = (
+            self.current_user.as_ref(),
+            self.current_user_project.as_ref(),
+        ) {
+            self.addr.do_send(InnerMsg::Leave(
+                up.project_id,
+                user.id,
+                ctx.address().recipient(),
+            ));
+        }
+        self.current_token_id = None;
+        self.current_user = None;
+        self.current_user_project = None;
+        self.current_project = None;
+    }
+
     fn require_user(&self) -> Result<&User, WsMsg> {
         self.current_user
             .as_ref()
@@ -265,6 +503,18 @@ impl WebSocketActor {
             .ok_or_else(|| WsMsg::AuthorizeExpired)
     }
 
+    /// Issue and comment changes are made by signed in project member
+    fn changes(&self) -> Result<ChangeContext<'_>, WsMsg> {
+        Ok(ChangeContext {
+            db: self.db.get_ref(),
+            mail: self.mail.get_ref(),
+            ws: &self.addr,
+            hi: self.hi.get_ref(),
+            user: self.require_user()?,
+            user_project: self.require_user_project()?,
+        })
+    }
+
     fn load_user_project(&self) -> Result<UserProject, WsMsg> {
         let user_id = self.require_user()?.id;
         match block_on(self.db.send(CurrentUserProject { user_id })) {
@@ -294,6 +544,33 @@ impl WebSocketActor {
             }
         }
     }
+
+    fn check_rate_limit(&self, action: LimitedAction, email: Option<&str>) -> Result<(), WsMsg> {
+        let hit = Hit {
+            action,
+            email: email.map(|s| s.to_string()),
+            remote: self.remote_addr.clone(),
+        };
+        match block_on(self.limiter.send(hit)) {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                warn!("too many {:?} requests from {:?}", action, self.remote_addr);
+                Err(WsMsg::Error(WsError::TooManyRequests))
+            }
+            Err(e) => {
+                error!("{}", e);
+                Err(WsMsg::Error(WsError::TooManyRequests))
+            }
+        }
+    }
+
+    /// Successful attempt does not count against e-mail address
+    fn clear_rate_limit(&self, action: LimitedAction, email: &str) {
+        self.limiter.do_send(Clear {
+            action,
+            email: email.to_string(),
+        });
+    }
 }
 
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WebSocketActor {
@@ -325,16 +602,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WebSocketActor {
 
     fn finished(&mut self, ctx: &mut <Self as Actor>::Context) {
         info!("Disconnected");
-        if let (Some(user), Some(up)) = (
-            self.current_user.as_ref(),
-            self.current_user_project.as_ref(),
-        ) {
-            self.addr.do_send(InnerMsg::Leave(
-                up.project_id,
-                user.id,
-                ctx.address().recipient(),
-            ));
-        }
+        self.sign_out(ctx);
         ctx.stop()
     }
 }
@@ -354,12 +622,18 @@ pub async fn index(
     mail: Data<Addr<MailExecutor>>,
     ws_server: Data<Addr<WsServer>>,
     hi: Data<Addr<highlight_actor::HighlightActor>>,
+    limiter: Data<Addr<RateLimiter>>,
 ) -> Result<HttpResponse, Error> {
+    let remote_addr = rate_limit::remote_addr(&req);
     ws::start(
         WebSocketActor {
             db,
             mail,
             hi,
+            limiter: limiter.get_ref().clone(),
+            remote_addr,
+            current_token_id: None,
+            token_expiry: None,
             current_user: None,
             current_user_project: None,
             current_project: None,
@@ -0,0 +1,143 @@
+use database_actor::issues::FindProjectIssue;
+use database_actor::messages::{CreateMessage, CreateMessageReceiver};
+use database_actor::users::LoadProjectUsers;
+use futures::executor::block_on;
+use jirs_data::{IssueId, MessageType, User, UserId};
+
+use crate::changes::ChangeContext;
+
+/// Project members mentioned in text as `@name`
+pub fn mentioned_users(text: &str, users: &[User]) -> Vec<UserId> {
+    let names: Vec<(UserId, &str)> = users
+        .iter()
+        .map(|user| (user.id, user.name.as_str()))
+        .collect();
+    mentioned(text, names)
+}
+
+/// Longer names are matched first so `@Anna Smith` does not mention `Anna`
+/// as well
+fn mentioned(text: &str, mut names: Vec<(UserId, &str)>) -> Vec<UserId> {
+    let mut text = text.to_lowercase();
+    names.retain(|(_, name)| !name.trim().is_empty());
+    names.sort_by_key(|(_, name)| std::cmp::Reverse(name.chars().count()));
+
+    let mut ids = vec![];
+    for (id, name) in names {
+        let needle = format!("@{}", name.to_lowercase());
+        let mut found = false;
+        let mut from = 0;
+        while let Some(start) = text[from..].find(needle.as_str()).map(|pos| from + pos) {
+            let end = start + needle.len();
+            let before = text[..start].chars().last();
+            let after = text[end..].chars().next();
+            if before.map(is_name_char).unwrap_or_default()
+                || after.map(is_name_char).unwrap_or_default()
+            {
+                // `@` is single byte so next char starts right after it
+                from = start + 1;
+                continue;
+            }
+            found = true;
+            text.replace_range(start..end, " ".repeat(needle.len()).as_str());
+            from = end;
+        }
+        if found {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl ChangeContext<'_> {
+    /// Send message to each project member mentioned in text who was not
+    /// mentioned in previous version of this text. Returns users who received
+    /// message
+    pub(crate) fn notify_mentions(
+        &self,
+        issue_id: IssueId,
+        previous: Option<&str>,
+        text: &str,
+        place: &str,
+    ) -> Vec<UserId> {
+        let sender = self.user;
+        let project_id = self.user_project.project_id;
+        let users = match block_on(self.db.send(LoadProjectUsers { project_id })) {
+            Ok(Ok(users)) => users,
+            _ => return vec![],
+        };
+        let already_mentioned: Vec<UserId> = previous
+            .map(|previous| mentioned_users(previous, &users))
+            .unwrap_or_default();
+        let receivers: Vec<UserId> = mentioned_users(text, &users)
+            .into_iter()
+            .filter(|id| *id != sender.id && !already_mentioned.contains(id))
+            .collect();
+        if receivers.is_empty() {
+            return vec![];
+        }
+        let issue = match block_on(self.db.send(FindProjectIssue {
+            project_id,
+            issue_id,
+        })) {
+            Ok(Ok(issue)) => issue,
+            _ => return vec![],
+        };
+
+        let mut notified = vec![];
+        for receiver_id in receivers {
+            if let Ok(Ok(message)) = block_on(self.db.send(CreateMessage {
+                receiver: CreateMessageReceiver::Reference(receiver_id),
+                sender_id: sender.id,
+                summary: format!("You have been mentioned in {}", issue.title),
+                description: format!("@<{}> mentioned you in {}", sender.email, place),
+                message_type: MessageType::Mention,
+                hyper_link: format!("/issues/{}", issue_id),
+            })) {
+                self.deliver_message(message);
+                notified.push(receiver_id);
+            }
+        }
+        notified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str, names: &[(UserId, &str)]) -> Vec<UserId> {
+        let mut ids = mentioned(text, names.to_vec());
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn find_mentioned_names() {
+        let users = [(1, "foo"), (2, "Bar"), (3, "baz")];
+        assert_eq!(
+            names("hello @foo and @bar, see @nobody", &users),
+            vec![1, 2]
+        );
+        assert!(names("no mentions", &users).is_empty());
+    }
+
+    #[test]
+    fn ignore_emails_and_longer_words() {
+        let users = [(1, "foo")];
+        assert!(names("write to me@foo", &users).is_empty());
+        assert!(names("@foobar", &users).is_empty());
+        assert_eq!(names("(@foo)", &users), vec![1]);
+    }
+
+    #[test]
+    fn prefer_longest_name() {
+        let users = [(1, "Anna"), (2, "Anna Smith")];
+        assert_eq!(names("ping @Anna Smith", &users), vec![2]);
+        assert_eq!(names("ping @Anna Smith and @anna", &users), vec![1, 2]);
+    }
+}
@@ -0,0 +1,133 @@
+use actix::Addr;
+use database_actor::messages::{LoadMessageDigests, MarkMessagesNotified, MessageDigest};
+use database_actor::user_settings::FindUserSetting;
+use database_actor::users::FindUser;
+use database_actor::DbExecutor;
+use futures::executor::block_on;
+use jirs_data::{Message, MessageId, MessageType, NotificationMode, User, WsMsg};
+use mail_actor::notification::{Digest, NotificationItem, NotificationKind, Notify};
+use mail_actor::MailExecutor;
+
+use crate::changes::ChangeContext;
+use crate::server::InnerMsg;
+
+/// Invitations have their own e-mail and are not repeated as notification
+fn notification_kind(message_type: MessageType) -> Option<NotificationKind> {
+    match message_type {
+        MessageType::ReceivedInvitation => None,
+        MessageType::AssignedToIssue => Some(NotificationKind::Assigned),
+        MessageType::Mention => Some(NotificationKind::Mentioned),
+        MessageType::WatchedIssueUpdated => Some(NotificationKind::Watched),
+    }
+}
+
+fn notification_item(message: &Message, sender: &User) -> Option<NotificationItem> {
+    Some(NotificationItem {
+        kind: notification_kind(message.message_type)?,
+        sender_name: sender.name.clone(),
+        summary: message.summary.clone(),
+        description: message.description.clone(),
+        link: Some(message.hyper_link.clone()).filter(|link| link.starts_with('/')),
+    })
+}
+
+impl ChangeContext<'_> {
+    /// Show created message to receiver and send it by e-mail if receiver
+    /// wants to be notified immediately
+    pub(crate) fn deliver_message(&self, message: Message) {
+        self.ws.do_send(InnerMsg::SendToUser(
+            message.receiver_id,
+            WsMsg::MessageUpdated(message.clone()),
+        ));
+        if let Err(e) = self.notify_immediately(&message) {
+            error!("{}", e);
+        }
+    }
+
+    fn notify_immediately(&self, message: &Message) -> Result<(), String> {
+        if notification_kind(message.message_type).is_none() {
+            return Ok(());
+        }
+        let mode = match block_on(self.db.send(FindUserSetting {
+            user_id: message.receiver_id,
+        })) {
+            Ok(Ok(setting)) => setting.notification_mode,
+            _ => NotificationMode::default(),
+        };
+        if mode != NotificationMode::Immediate {
+            return Ok(());
+        }
+
+        let find_user = |user_id| match block_on(self.db.send(FindUser { user_id })) {
+            Ok(Ok(user)) => Ok(user),
+            Ok(Err(e)) => Err(format!("{:?}", e)),
+            Err(e) => Err(format!("{}", e)),
+        };
+        let receiver = find_user(message.receiver_id)?;
+        let sender = find_user(message.sender_id)?;
+        let item = match notification_item(message, &sender) {
+            Some(item) => item,
+            _ => return Ok(()),
+        };
+        block_on(self.mail.send(Notify {
+            email: receiver.email,
+            receiver_name: receiver.name,
+            item,
+        }))
+        .map_err(|e| format!("{}", e))??;
+        self.db.do_send(MarkMessagesNotified {
+            message_ids: vec![message.id],
+        });
+        Ok(())
+    }
+}
+
+/// Send single e-mail with all collected messages to each user who chose daily
+/// digest. Resolves to number of sent e-mails
+pub async fn send_digests(
+    db: &Addr<DbExecutor>,
+    mail: &Addr<MailExecutor>,
+) -> Result<usize, String> {
+    let digests = match db.send(LoadMessageDigests).await {
+        Ok(Ok(digests)) => digests,
+        Ok(Err(e)) => return Err(format!("{:?}", e)),
+        Err(e) => return Err(format!("{}", e)),
+    };
+
+    let mut sent = 0;
+    for MessageDigest { receiver, messages } in digests {
+        let (message_ids, items): (Vec<MessageId>, Vec<NotificationItem>) = messages
+            .iter()
+            .filter_map(|(message, sender)| {
+                notification_item(message, sender).map(|item| (message.id, item))
+            })
+            .unzip();
+        if items.is_empty() {
+            continue;
+        }
+        match mail
+            .send(Digest {
+                email: receiver.email,
+                receiver_name: receiver.name,
+                items,
+            })
+            .await
+        {
+            Ok(Ok(())) => sent += 1,
+            Ok(Err(e)) => {
+                error!("{}", e);
+                continue;
+            }
+            Err(e) => {
+                error!("{}", e);
+                continue;
+            }
+        }
+        match db.send(MarkMessagesNotified { message_ids }).await {
+            Ok(Ok(_)) => (),
+            Ok(Err(e)) => error!("{:?}", e),
+            Err(e) => error!("{}", e),
+        }
+    }
+    Ok(sent)
+}
@@ -0,0 +1,320 @@
+use jirs_data::msg::WsError;
+use jirs_data::{
+    CreateCommentPayload, CreateIssuePayload, UpdateCommentPayload, UpdateProjectPayload,
+    UserProject, UserRole, WsMsg,
+};
+
+use crate::handlers::*;
+use crate::WebSocketActor;
+
+/// Minimal role in current project required to handle message
+pub trait RequiredRole {
+    const ROLE: UserRole;
+}
+
+macro_rules! required_roles {
+    ($($role: ident => [$($handler: ty),+ $(,)?]),+ $(,)?) => {
+        $($(
+            impl RequiredRole for $handler {
+                const ROLE: UserRole = UserRole::$role;
+            }
+        )+)+
+    };
+}
+
+// Handlers not bound to project, like authentication, messages or user
+// settings, are not listed and only require signed in user.
+required_roles! {
+    User => [
+        // issues
+        LoadIssues,
+        SearchIssues,
+        LoadIssueHistory,
+        CreateIssuePayload,
+        UpdateIssueHandler,
+        SyncIssueListPosition,
+        // issue watchers
+        LoadIssueWatchers,
+        WatchIssue,
+        // issue links
+        LoadIssueLinks,
+        CreateIssueLink,
+        DeleteIssueLink,
+        // issue statuses
+        LoadIssueStatuses,
+        // comments
+        LoadIssueComments,
+        CreateCommentPayload,
+        UpdateCommentPayload,
+        DeleteComment,
+        // users
+        LoadProjectUsers,
+        // epics
+        LoadEpics,
+        CreateEpic,
+        UpdateEpicName,
+        UpdateEpicStartsAt,
+        UpdateEpicEndsAt,
+        // labels
+        LoadLabels,
+        // custom fields
+        LoadCustomFields,
+        LoadIssueCustomValues,
+        // worklogs
+        LoadWorklogs,
+        CreateWorklog,
+        UpdateWorklog,
+        DeleteWorklog,
+        // workflow
+        LoadWorkflowTransitions,
+        // reports
+        LoadEpicBurndown,
+        LoadVelocity,
+        LoadCumulativeFlow,
+        // sprints
+        LoadSprints,
+    ],
+    Manager => [
+        // issues
+        DeleteIssue,
+        // issue statuses
+        CreateIssueStatus,
+        UpdateIssueStatus,
+        UpdateIssueStatusWipLimit,
+        DeleteIssueStatus,
+        // projects
+        UpdateProjectPayload,
+        // invitations
+        ListInvitation,
+        CreateInvitation,
+        DeleteInvitation,
+        RevokeInvitation,
+        // epics
+        DeleteEpic,
+        TransformEpic,
+        // labels
+        CreateLabel,
+        DeleteLabel,
+        // custom fields
+        CreateCustomField,
+        UpdateCustomField,
+        DeleteCustomField,
+        // workflow
+        CreateWorkflowTransition,
+        DeleteWorkflowTransition,
+        // sprints
+        CreateSprint,
+        StartSprint,
+        CompleteSprint,
+    ],
+    Owner => [
+        // users
+        RemoveInvitedUser,
+    ],
+}
+
+/// Check if member with given role can handle message
+pub fn is_allowed<Msg: RequiredRole>(role: UserRole) -> bool {
+    role >= Msg::ROLE
+}
+
+impl WebSocketActor {
+    /// Current user project if user has role required by message handler
+    pub(crate) fn require_role<Msg: RequiredRole>(&self) -> Result<&UserProject, WsMsg> {
+        let user_project = self.require_user_project()?;
+        if is_allowed::<Msg>(user_project.role) {
+            Ok(user_project)
+        } else {
+            Err(WsMsg::Error(WsError::Forbidden))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix::{Actor, Addr, Handler, Message, SyncArbiter};
+    use actix_web::error::PayloadError;
+    use actix_web::web::{Bytes, Data};
+    use actix_web_actors::ws;
+    use chrono::NaiveDate;
+    use futures::StreamExt;
+    use jirs_data::UserRole::*;
+    use jirs_data::{CustomFieldType, IssueType};
+
+    use super::*;
+    use crate::rate_limit::RateLimiter;
+    use crate::server::WsServer;
+    use crate::{WsHandler, WsResult};
+
+    /// Passes message to handler inside running actor context
+    struct Probe<Msg>(Msg);
+
+    impl<Msg> Message for Probe<Msg> {
+        type Result = WsResult;
+    }
+
+    impl<Msg: 'static> Handler<Probe<Msg>> for WebSocketActor
+    where
+        WebSocketActor: WsHandler<Msg>,
+    {
+        type Result = WsResult;
+
+        fn handle(&mut self, msg: Probe<Msg>, ctx: &mut Self::Context) -> Self::Result {
+            self.handle_msg(msg.0, ctx)
+        }
+    }
+
+    fn member(role: UserRole) -> Addr<WebSocketActor> {
+        let at = NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let actor = WebSocketActor {
+            db: Data::new(SyncArbiter::start(1, database_actor::DbExecutor::default)),
+            mail: Data::new(SyncArbiter::start(1, || mail_actor::MailExecutor {
+                transport: mail_actor::MailTransport::Memory,
+                config: jirs_config::mail::Configuration::read(),
+            })),
+            addr: WsServer::start_default(),
+            hi: Data::new(SyncArbiter::start(
+                1,
+                highlight_actor::HighlightActor::default,
+            )),
+            limiter: RateLimiter::start_default(),
+            remote_addr: None,
+            current_token_id: None,
+            token_expiry: None,
+            current_user: Some(jirs_data::User {
+                id: 1,
+                name: "member".to_string(),
+                email: "member@example.com".to_string(),
+                avatar_url: None,
+                created_at: at,
+                updated_at: at,
+            }),
+            current_user_project: Some(UserProject {
+                id: 1,
+                user_id: 1,
+                project_id: 1,
+                is_default: true,
+                is_current: true,
+                role,
+                created_at: at,
+                updated_at: at,
+            }),
+            current_project: None,
+        };
+        let (addr, out) = ws::WebsocketContext::create_with_addr(
+            actor,
+            futures::stream::pending::<Result<Bytes, PayloadError>>(),
+        );
+        let mut out = Box::pin(out);
+        actix::spawn(async move { while out.next().await.is_some() {} });
+        addr
+    }
+
+    macro_rules! assert_forbidden {
+        ($($test: ident: $role: ident => $msg: expr),+ $(,)?) => {
+            $(
+                #[actix_rt::test]
+                async fn $test() {
+                    let res = member($role).send(Probe($msg)).await.unwrap();
+                    assert_eq!(res, Err(WsMsg::Error(WsError::Forbidden)));
+                }
+            )+
+        };
+    }
+
+    assert_forbidden! {
+        delete_issue: User => DeleteIssue { id: 1 },
+        create_issue_status: User => CreateIssueStatus {
+            position: 0,
+            name: "Review".to_string(),
+        },
+        update_issue_status: User => UpdateIssueStatus {
+            issue_status_id: 1,
+            position: 0,
+            name: "Review".to_string(),
+        },
+        update_issue_status_wip_limit: User => UpdateIssueStatusWipLimit {
+            issue_status_id: 1,
+            wip_limit: Some(3),
+            wip_strict: true,
+        },
+        delete_issue_status: User => DeleteIssueStatus { issue_status_id: 1 },
+        update_project: User => UpdateProjectPayload {
+            id: 1,
+            name: Some("Renamed".to_string()),
+            url: None,
+            description: None,
+            category: None,
+            time_tracking: None,
+        },
+        remove_invited_user: Manager => RemoveInvitedUser { user_id: 2 },
+        list_invitation: User => ListInvitation,
+        create_invitation: User => CreateInvitation {
+            email: "invited@example.com".to_string(),
+            name: "invited".to_string(),
+            role: User,
+        },
+        delete_invitation: User => DeleteInvitation { id: 1 },
+        revoke_invitation: User => RevokeInvitation { id: 1 },
+        delete_epic: User => DeleteEpic { epic_id: 1 },
+        transform_epic: User => TransformEpic {
+            epic_id: 1,
+            issue_type: IssueType::Story,
+        },
+        create_label: User => CreateLabel {
+            name: "backend".to_string(),
+            color: "#2d9cdb".to_string(),
+        },
+        delete_label: User => DeleteLabel { label_id: 1 },
+        create_custom_field: User => CreateCustomField {
+            name: "Team".to_string(),
+            field_type: CustomFieldType::Text,
+            options: vec![],
+        },
+        update_custom_field: User => UpdateCustomField {
+            custom_field_id: 1,
+            name: "Team".to_string(),
+            options: vec![],
+        },
+        delete_custom_field: User => DeleteCustomField { custom_field_id: 1 },
+        create_workflow_transition: User => CreateWorkflowTransition {
+            from_status_id: 1,
+            to_status_id: 2,
+            required_role: None,
+            require_assignee: false,
+            require_estimate: false,
+        },
+        delete_workflow_transition: User => DeleteWorkflowTransition {
+            workflow_transition_id: 1,
+        },
+        create_sprint: User => CreateSprint {
+            name: "Sprint 1".to_string(),
+            goal: None,
+        },
+        start_sprint: User => StartSprint {
+            sprint_id: 1,
+            starts_at: NaiveDate::from_ymd(2021, 1, 4).and_hms(0, 0, 0),
+            ends_at: NaiveDate::from_ymd(2021, 1, 18).and_hms(0, 0, 0),
+        },
+        complete_sprint: User => CompleteSprint {
+            sprint_id: 1,
+            move_to: None,
+        },
+    }
+
+    #[test]
+    fn minimal_roles() {
+        let allowed = |f: fn(UserRole) -> bool| -> Vec<UserRole> {
+            UserRole::default()
+                .into_iter()
+                .filter(|role| f(*role))
+                .collect()
+        };
+        assert_eq!(
+            allowed(is_allowed::<LoadIssues>),
+            vec![User, Manager, Owner]
+        );
+        assert_eq!(allowed(is_allowed::<DeleteIssue>), vec![Manager, Owner]);
+        assert_eq!(allowed(is_allowed::<RemoveInvitedUser>), vec![Owner]);
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use actix::{Actor, AsyncContext, Context};
+use actix_web::HttpRequest;
+
+/// Requests which can be used to flood mailbox or guess tokens and passwords
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LimitedAction {
+    SignIn,
+    SignUp,
+    BindToken,
+    RefreshToken,
+    Invitation,
+    PasswordSignIn,
+    PasswordChange,
+    TwoFactor,
+    /// Unknown access token sent to JSON API
+    AccessToken,
+}
+
+/// Client address used as remote subject of limits
+pub fn remote_addr(req: &HttpRequest) -> Option<String> {
+    jirs_config::rate_limit::config()
+        .remote_header
+        .as_ref()
+        .and_then(|name| req.headers().get(name.as_str()))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| req.peer_addr().map(|addr| addr.ip().to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Subject {
+    Email(String),
+    Remote(String),
+}
+
+/// Register attempt, resolves to `false` if any of given subjects already used
+/// all attempts
+#[derive(actix::Message, Debug)]
+#[rtype(result = "bool")]
+pub struct Hit {
+    pub action: LimitedAction,
+    pub email: Option<String>,
+    pub remote: Option<String>,
+}
+
+/// Same as `Hit` but attempt is not registered
+#[derive(actix::Message, Debug)]
+#[rtype(result = "bool")]
+pub struct Check {
+    pub action: LimitedAction,
+    pub email: Option<String>,
+    pub remote: Option<String>,
+}
+
+/// Forget attempts of e-mail after successful one so only failed attempts
+/// are counted against it
+#[derive(actix::Message, Debug)]
+#[rtype(result = "()")]
+pub struct Clear {
+    pub action: LimitedAction,
+    pub email: String,
+}
+
+pub struct RateLimiter {
+    enabled: bool,
+    window: Duration,
+    email_attempts: usize,
+    remote_attempts: usize,
+    attempts: HashMap<(LimitedAction, Subject), VecDeque<Instant>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        let config = jirs_config::rate_limit::config();
+        Self {
+            enabled: config.enabled,
+            window: Duration::from_secs(config.window),
+            email_attempts: config.email_attempts,
+            remote_attempts: config.remote_attempts,
+            attempts: HashMap::new(),
+        }
+    }
+}
+
+impl Actor for RateLimiter {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(Duration::from_secs(60), |act, _ctx| {
+            act.prune(Instant::now())
+        });
+    }
+}
+
+impl actix::Handler<Hit> for RateLimiter {
+    type Result = bool;
+
+    fn handle(&mut self, msg: Hit, _ctx: &mut Self::Context) -> Self::Result {
+        self.hit(msg, Instant::now())
+    }
+}
+
+impl actix::Handler<Check> for RateLimiter {
+    type Result = bool;
+
+    fn handle(&mut self, msg: Check, _ctx: &mut Self::Context) -> Self::Result {
+        self.check(msg, Instant::now())
+    }
+}
+
+impl actix::Handler<Clear> for RateLimiter {
+    type Result = ();
+
+    fn handle(&mut self, msg: Clear, _ctx: &mut Self::Context) -> Self::Result {
+        self.clear(msg)
+    }
+}
+
+impl RateLimiter {
+    fn hit(&mut self, msg: Hit, now: Instant) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let Hit {
+            action,
+            email,
+            remote,
+        } = msg;
+        let subjects = self.subjects(email, remote);
+        if self.exceeded(action, &subjects, now) {
+            return false;
+        }
+
+        let window = self.window;
+        for (subject, _) in subjects {
+            let list = self.attempts.entry((action, subject)).or_default();
+            while list
+                .front()
+                .map(|at| now.duration_since(*at) >= window)
+                .unwrap_or_default()
+            {
+                list.pop_front();
+            }
+            list.push_back(now);
+        }
+        true
+    }
+
+    fn check(&self, msg: Check, now: Instant) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let Check {
+            action,
+            email,
+            remote,
+        } = msg;
+        let subjects = self.subjects(email, remote);
+        !self.exceeded(action, &subjects, now)
+    }
+
+    fn clear(&mut self, msg: Clear) {
+        let Clear { action, email } = msg;
+        self.attempts
+            .remove(&(action, Subject::Email(email.trim().to_lowercase())));
+    }
+
+    fn subjects(&self, email: Option<String>, remote: Option<String>) -> Vec<(Subject, usize)> {
+        email
+            .map(|email| {
+                (
+                    Subject::Email(email.trim().to_lowercase()),
+                    self.email_attempts,
+                )
+            })
+            .into_iter()
+            .chain(remote.map(|remote| (Subject::Remote(remote), self.remote_attempts)))
+            .collect()
+    }
+
+    fn exceeded(&self, action: LimitedAction, subjects: &[(Subject, usize)], now: Instant) -> bool {
+        subjects.iter().any(|(subject, limit)| {
+            self.attempts
+                .get(&(action, subject.clone()))
+                .map(|list| {
+                    list.iter()
+                        .filter(|at| now.duration_since(**at) < self.window)
+                        .count()
+                        >= *limit
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let window = self.window;
+        self.attempts.retain(|_, list| {
+            list.retain(|at| now.duration_since(*at) < window);
+            !list.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter() -> RateLimiter {
+        RateLimiter {
+            enabled: true,
+            window: Duration::from_secs(10),
+            email_attempts: 2,
+            remote_attempts: 3,
+            attempts: HashMap::new(),
+        }
+    }
+
+    fn sign_in(email: &str, remote: &str) -> Hit {
+        Hit {
+            action: LimitedAction::SignIn,
+            email: Some(email.to_string()),
+            remote: Some(remote.to_string()),
+        }
+    }
+
+    #[test]
+    fn limit_attempts_per_email() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("FOO@example.com", "10.0.0.2"), now));
+        assert!(!limiter.hit(sign_in("foo@example.com", "10.0.0.3"), now));
+        assert!(limiter.hit(sign_in("bar@example.com", "10.0.0.3"), now));
+    }
+
+    #[test]
+    fn limit_attempts_per_remote() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.hit(sign_in("a@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("b@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("c@example.com", "10.0.0.1"), now));
+        assert!(!limiter.hit(sign_in("d@example.com", "10.0.0.1"), now));
+    }
+
+    #[test]
+    fn count_actions_separately() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(
+            Hit {
+                action: LimitedAction::SignUp,
+                email: Some("foo@example.com".to_string()),
+                remote: Some("10.0.0.1".to_string()),
+            },
+            now
+        ));
+    }
+
+    #[test]
+    fn accept_after_window_passed() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        let later = now + Duration::from_secs(10);
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), later));
+
+        limiter.prune(later + Duration::from_secs(10));
+        assert!(limiter.attempts.is_empty());
+    }
+
+    #[test]
+    fn check_without_registering_attempt() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        let check = || Check {
+            action: LimitedAction::SignIn,
+            email: Some("foo@example.com".to_string()),
+            remote: None,
+        };
+        assert!(limiter.check(check(), now));
+        assert!(limiter.check(check(), now));
+        assert!(limiter.check(check(), now));
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.2"), now));
+        assert!(!limiter.check(check(), now));
+    }
+
+    #[test]
+    fn clear_email_attempts_after_success() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        limiter.clear(Clear {
+            action: LimitedAction::SignIn,
+            email: "FOO@example.com".to_string(),
+        });
+        assert!(limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+        // attempts from remote are still counted
+        assert!(!limiter.hit(sign_in("foo@example.com", "10.0.0.1"), now));
+    }
+}
@@ -6,6 +6,10 @@ use jirs_data::{ProjectId, UserId, WsMsg};
 #[derive(actix::Message, Debug)]
 #[rtype(result = "()")]
 pub enum InnerMsg {
+    /// Signed in connection, it receives messages sent to user even when it
+    /// didn't join any project channel
+    Connect(UserId, Recipient<InnerMsg>),
+    Disconnect(UserId, Recipient<InnerMsg>),
     Join(ProjectId, UserId, Recipient<InnerMsg>),
     Leave(ProjectId, UserId, Recipient<InnerMsg>),
     BroadcastToChannel(ProjectId, WsMsg),
@@ -15,6 +19,7 @@ pub enum InnerMsg {
 
 pub struct WsServer {
     sessions: HashMap<UserId, Vec<Recipient<InnerMsg>>>,
+    connections: HashMap<UserId, Vec<Recipient<InnerMsg>>>,
     rooms: HashMap<ProjectId, HashMap<UserId, i32>>,
 }
 
@@ -22,6 +27,7 @@ impl Default for WsServer {
     fn default() -> Self {
         Self {
             sessions: HashMap::new(),
+            connections: HashMap::new(),
             rooms: HashMap::new(),
         }
     }
@@ -41,6 +47,23 @@ impl actix::Handler<InnerMsg> for WsServer {
     fn handle(&mut self, msg: InnerMsg, _ctx: &mut <Self as Actor>::Context) -> Self::Result {
         debug!("receive {:?}", msg);
         match msg {
+            InnerMsg::Connect(user_id, recipient) => {
+                let v = self
+                    .connections
+                    .entry(user_id)
+                    .or_insert_with(Default::default);
+                if !v.contains(&recipient) {
+                    v.push(recipient);
+                }
+            }
+            InnerMsg::Disconnect(user_id, recipient) => {
+                if let Some(v) = self.connections.get_mut(&user_id) {
+                    v.retain(|r| r != &recipient);
+                    if v.is_empty() {
+                        self.connections.remove(&user_id);
+                    }
+                }
+            }
             InnerMsg::Join(project_id, user_id, recipient) => {
                 let v = self
                     .sessions
@@ -76,7 +99,7 @@ impl actix::Handler<InnerMsg> for WsServer {
                 }
             }
             InnerMsg::SendToUser(user_id, msg) => {
-                if let Some(v) = self.sessions.get(&user_id) {
+                if let Some(v) = self.connections.get(&user_id) {
                     self.send_to_recipients(v, &msg);
                 }
             }
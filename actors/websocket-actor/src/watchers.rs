@@ -0,0 +1,71 @@
+use database_actor::issue_statuses::LoadIssueStatuses;
+use database_actor::issue_watchers::{LoadIssueWatcherIds, WatchIssue};
+use database_actor::issues::FindProjectIssue;
+use database_actor::messages::{CreateMessage, CreateMessageReceiver};
+use futures::executor::block_on;
+use jirs_data::{IssueId, IssueStatusId, MessageType, UserId};
+
+use crate::changes::ChangeContext;
+
+impl ChangeContext<'_> {
+    /// Add users to issue watchers. Failure is only logged because it must not
+    /// reject change which caused it
+    pub(crate) fn watch_issue(&self, issue_id: IssueId, user_ids: Vec<UserId>) {
+        match block_on(self.db.send(WatchIssue { issue_id, user_ids })) {
+            Ok(Ok(_)) => (),
+            Ok(Err(e)) => error!("{:?}", e),
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    /// Send message about issue change to each watcher except current user
+    /// who made the change and users who already got message about it
+    pub(crate) fn notify_watchers(&self, issue_id: IssueId, change: &str, skip: &[UserId]) {
+        let sender = self.user;
+        let project_id = self.user_project.project_id;
+        let receivers: Vec<UserId> = match block_on(self.db.send(LoadIssueWatcherIds { issue_id }))
+        {
+            Ok(Ok(ids)) => ids
+                .into_iter()
+                .filter(|id| *id != sender.id && !skip.contains(id))
+                .collect(),
+            _ => return,
+        };
+        if receivers.is_empty() {
+            return;
+        }
+        let issue = match block_on(self.db.send(FindProjectIssue {
+            project_id,
+            issue_id,
+        })) {
+            Ok(Ok(issue)) => issue,
+            _ => return,
+        };
+
+        for receiver_id in receivers {
+            if let Ok(Ok(message)) = block_on(self.db.send(CreateMessage {
+                receiver: CreateMessageReceiver::Reference(receiver_id),
+                sender_id: sender.id,
+                summary: format!("{} was updated", issue.title),
+                description: format!("@<{}> {}", sender.email, change),
+                message_type: MessageType::WatchedIssueUpdated,
+                hyper_link: format!("/issues/{}", issue_id),
+            })) {
+                self.deliver_message(message);
+            }
+        }
+    }
+
+    /// Describe status change for watchers using status name
+    pub(crate) fn status_change(&self, issue_status_id: IssueStatusId) -> String {
+        let project_id = self.user_project.project_id;
+        match block_on(self.db.send(LoadIssueStatuses { project_id })) {
+            Ok(Ok(statuses)) => statuses
+                .into_iter()
+                .find(|status| status.id == issue_status_id)
+                .map(|status| format!("moved issue to {}", status.name))
+                .unwrap_or_else(|| "changed issue status".to_string()),
+            _ => "changed issue status".to_string(),
+        }
+    }
+}
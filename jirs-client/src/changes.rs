@@ -1,7 +1,12 @@
-use jirs_data::{EpicId, IssueStatusId, WsMsg};
+use jirs_data::{
+    BoardGrouping, CustomFieldId, EpicId, IssueId, IssueStatusId, LabelId, SprintId,
+    WorkflowTransitionId, WsMsg,
+};
 use seed::prelude::WebSocketMessage;
 
 use crate::components::styled_md_editor::MdEditorMode as TabMode;
+use crate::pages::project_page::model::BoardLane;
+use crate::pages::reports_page::model::TimesheetGrouping;
 use crate::FieldId;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -9,7 +14,11 @@ pub enum FieldChange {
     LinkCopied(FieldId, bool),
     TabChanged(FieldId, TabMode),
     ToggleCommentForm(FieldId, bool),
+    ToggleActivity(FieldId, bool),
+    ToggleWatch(FieldId, bool),
     EditComment(FieldId, i32),
+    MentionQuery(FieldId, Option<String>),
+    MentionSelected(FieldId, String),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -19,8 +28,10 @@ pub enum BoardPageChange {
     IssueDragStopped(EpicId),
     DragLeave(EpicId),
     ChangePosition(EpicId),
-    IssueDragOverStatus(IssueStatusId),
+    IssueDragOverStatus(IssueStatusId, BoardLane),
     IssueDropZone(IssueStatusId),
+    // swimlanes
+    GroupingChanged(BoardGrouping),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -43,12 +54,30 @@ pub enum ProjectPageChange {
     // edit issue status name
     EditIssueStatusName(Option<IssueStatusId>),
     SubmitIssueStatusForm,
+    // labels
+    SubmitLabelForm,
+    DeleteLabel(LabelId),
+    // custom fields
+    SubmitCustomFieldForm,
+    DeleteCustomField(CustomFieldId),
+    // workflow
+    ToggleTransitionRequireAssignee,
+    ToggleTransitionRequireEstimate,
+    SubmitTransitionForm,
+    DeleteTransition(WorkflowTransitionId),
+    // work in progress limits
+    ToggleWipStrict(IssueStatusId),
+    SubmitWipLimit(IssueStatusId),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[repr(C)]
 pub enum ProfilePageChange {
     SubmitForm,
+    SignOutEverywhere,
+    SubmitPassword,
+    StartTotpSetup,
+    SubmitTotpCode,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -61,6 +90,15 @@ pub enum InvitationPageChange {
 pub enum ReportsPageChange {
     DayHovered(Option<chrono::NaiveDate>),
     DaySelected(Option<chrono::NaiveDate>),
+    TimesheetGroupingChanged(TimesheetGrouping),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BacklogPageChange {
+    CreateSprint,
+    StartSprint(SprintId),
+    CompleteSprint(SprintId),
+    MoveIssue(IssueId, Option<SprintId>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -71,6 +109,7 @@ pub enum PageChanged {
     Board(BoardPageChange),
     Invitation(InvitationPageChange),
     Reports(ReportsPageChange),
+    Backlog(BacklogPageChange),
 }
 
 #[derive(Debug)]
@@ -9,8 +9,10 @@ pub mod styled_form;
 pub mod styled_icon;
 pub mod styled_image_input;
 pub mod styled_input;
+pub mod styled_label_chip;
 pub mod styled_link;
 pub mod styled_md_editor;
+pub mod styled_mention;
 pub mod styled_modal;
 pub mod styled_rte;
 pub mod styled_select;
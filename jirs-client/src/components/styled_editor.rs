@@ -1,4 +1,4 @@
-use jirs_data::TextEditorMode;
+use jirs_data::{TextEditorMode, User};
 use seed::prelude::*;
 use seed::*;
 
@@ -70,7 +70,7 @@ impl StyledEditorState {
 impl EditorMode {
     pub fn update(&mut self, msg: &Msg, orders: &mut impl Orders<Msg>) {
         match self {
-            EditorMode::Md(state) => state.update(msg),
+            EditorMode::Md(state) => state.update(msg, orders),
             EditorMode::Rte(state) => state.update(msg, orders),
         };
     }
@@ -78,10 +78,10 @@ impl EditorMode {
 
 /// Build project description text area with styled field wrapper
 #[inline(always)]
-pub fn render_styled_editor(state: &StyledEditorState) -> Node<Msg> {
+pub fn render_styled_editor(state: &StyledEditorState, users: &[User]) -> Node<Msg> {
     let editor = match &state.state {
-        EditorMode::Md(state) => render_md(state),
-        EditorMode::Rte(state) => render_rte(state),
+        EditorMode::Md(state) => render_md(state, users),
+        EditorMode::Rte(state) => render_rte(state, users),
     };
     let switcher = render_mode_switcher(state);
     div![switcher, editor]
@@ -130,7 +130,7 @@ fn editor_mode_checkbox_option<'l>(
 
 /// Build project description text area with styled field wrapper
 #[inline(always)]
-fn render_md(state: &StyledMdEditorState) -> Node<Msg> {
+fn render_md(state: &StyledMdEditorState, users: &[User]) -> Node<Msg> {
     StyledMdEditor {
         id: Some(state.id.clone()),
         initial_text: state.initial_text.as_str(),
@@ -138,19 +138,23 @@ fn render_md(state: &StyledMdEditorState) -> Node<Msg> {
         html: state.html.as_str(),
         mode: state.mode.clone(),
         update_event: Ev::Change,
+        users,
+        mention: state.mention.as_deref(),
     }
     .render()
 }
 
 /// Build project description text area with styled field wrapper
 #[inline(always)]
-fn render_rte(state: &StyledRteState) -> Node<Msg> {
+fn render_rte(state: &StyledRteState, users: &[User]) -> Node<Msg> {
     let id = state.field_id.clone();
     StyledRte {
         field_id: id,
         table_tooltip: Some(&state.table_tooltip),
         code_tooltip: Some(&state.code_tooltip),
         identifier: Some(state.identifier),
+        users,
+        mention: state.mention.as_deref(),
     }
     .render()
 }
@@ -12,6 +12,7 @@ pub enum Icon {
     Bug,
     Task,
     Story,
+    Subtask,
     Epic,
 
     DoubleLeft,
@@ -105,7 +106,9 @@ impl Icon {
     #[inline(always)]
     pub fn to_color(self) -> Option<String> {
         match self {
-            Icon::Bug | Icon::Task | Icon::Story | Icon::Epic => Some(format!("var(--{})", self)),
+            Icon::Bug | Icon::Task | Icon::Story | Icon::Subtask | Icon::Epic => {
+                Some(format!("var(--{})", self))
+            }
             _ => None,
         }
     }
@@ -117,6 +120,7 @@ impl Icon {
             Icon::Stopwatch => "stopwatch",
             Icon::Task => "task",
             Icon::Story => "story",
+            Icon::Subtask => "subtask",
             Icon::ArrowDown => "arrowDown",
             Icon::ArrowLeftCircle => "arrowLeftCircle",
             Icon::ArrowUp => "arrowUp",
@@ -222,6 +226,7 @@ impl From<IssueType> for Icon {
             Task => Icon::Task,
             Bug => Icon::Bug,
             Story => Icon::Story,
+            Subtask => Icon::Subtask,
         }
     }
 }
@@ -0,0 +1,46 @@
+use jirs_data::Label;
+use seed::prelude::*;
+use seed::*;
+
+use crate::Msg;
+
+pub struct StyledLabelChip<'l> {
+    pub label: &'l Label,
+    pub active: bool,
+    pub on_click: Option<EventHandler<Msg>>,
+    pub children: Vec<Node<Msg>>,
+    pub class_list: &'l str,
+}
+
+impl<'l> StyledLabelChip<'l> {
+    #[inline(always)]
+    pub fn new(label: &'l Label) -> Self {
+        Self {
+            label,
+            active: false,
+            on_click: None,
+            children: vec![],
+            class_list: "",
+        }
+    }
+
+    #[inline(always)]
+    pub fn render(self) -> Node<Msg> {
+        let StyledLabelChip {
+            label,
+            active,
+            on_click,
+            children,
+            class_list,
+        } = self;
+
+        span![
+            C!["styledLabelChip", class_list, IF![active => "isActive"]],
+            style![St::BackgroundColor => label.color.as_str()],
+            attrs![At::Title => label.name.as_str()],
+            on_click,
+            label.name.as_str(),
+            children,
+        ]
+    }
+}
@@ -1,6 +1,10 @@
+use jirs_data::User;
 use seed::prelude::*;
 use seed::*;
 
+use crate::components::styled_mention::{
+    complete_mention, mention_query, split_at_cursor, StyledMentionList,
+};
 use crate::components::styled_textarea::StyledTextarea;
 use crate::{FieldChange, FieldId, Msg};
 
@@ -17,6 +21,7 @@ pub struct StyledMdEditorState {
     pub mode: MdEditorMode,
     pub initial_text: String,
     pub html: String,
+    pub mention: Option<String>,
 }
 
 impl StyledMdEditorState {
@@ -32,19 +37,53 @@ impl StyledMdEditorState {
             mode,
             initial_text: text.into(),
             html: html.into(),
+            mention: None,
         }
     }
 
-    pub fn update(&mut self, msg: &Msg) {
+    pub fn update(&mut self, msg: &Msg, orders: &mut impl Orders<Msg>) {
         match msg {
             Msg::ModalChanged(FieldChange::TabChanged(field_id, new_mode))
                 if &self.id == field_id =>
             {
                 self.mode = new_mode.clone();
             }
+            Msg::ModalChanged(FieldChange::MentionQuery(field_id, query))
+                if &self.id == field_id =>
+            {
+                self.mention = query.clone();
+            }
+            Msg::ModalChanged(FieldChange::MentionSelected(field_id, name))
+                if &self.id == field_id =>
+            {
+                self.mention = None;
+                if let Some(value) = self.insert_mention(name.as_str()) {
+                    orders.send_msg(Msg::StrInputChanged(self.id.clone(), value));
+                }
+            }
             _ => {}
         };
     }
+
+    /// Replace typed mention query in text area with selected name and move
+    /// cursor after it
+    fn insert_mention(&self, name: &str) -> Option<String> {
+        let text_area = seed::document()
+            .query_selector(format!("#styledTextArea-{} textarea", self.id).as_str())
+            .ok()??
+            .dyn_into::<web_sys::HtmlTextAreaElement>()
+            .ok()?;
+        let cursor = text_area.selection_start().ok()??;
+        let (before, after) = split_at_cursor(text_area.value().as_str(), cursor);
+        let before = complete_mention(before.as_str(), name)?;
+        let cursor = before.encode_utf16().count() as u32;
+        let value = format!("{}{}", before, after);
+        text_area.set_value(value.as_str());
+        if let Err(e) = text_area.set_selection_range(cursor, cursor) {
+            error!(e);
+        }
+        Some(value)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +94,8 @@ pub struct StyledMdEditor<'l> {
     pub html: &'l str,
     pub mode: MdEditorMode,
     pub update_event: Ev,
+    pub users: &'l [User],
+    pub mention: Option<&'l str>,
 }
 
 impl<'l> Default for StyledMdEditor<'l> {
@@ -67,6 +108,8 @@ impl<'l> Default for StyledMdEditor<'l> {
             html: "",
             mode: MdEditorMode::Editor,
             update_event: Ev::Cached,
+            users: &[],
+            mention: None,
         }
     }
 }
@@ -81,6 +124,8 @@ impl<'l> StyledMdEditor<'l> {
             html,
             mode,
             update_event,
+            users,
+            mention,
         } = self;
 
         let id = id.expect("Styled Editor requires ID");
@@ -91,6 +136,17 @@ impl<'l> StyledMdEditor<'l> {
         let view_id = format!("view-{}", id);
         let name = format!("styled-editor-{}", id);
 
+        let on_key_up = mention_handler(id.clone());
+        let mention_list = StyledMentionList {
+            field_id: id.clone(),
+            query: mention,
+            users,
+            on_select: |field_id, name| {
+                Msg::ModalChanged(FieldChange::MentionSelected(field_id, name))
+            },
+        }
+        .render();
+
         let text_area = StyledTextarea {
             id: Some(id),
             height: 40,
@@ -120,12 +176,14 @@ impl<'l> StyledMdEditor<'l> {
                 "Editor",
                 on_editor_clicked
             ],
+            on_key_up,
             seed::input![
                 id![editor_id.as_str()],
                 C!["editorRadio"],
                 attrs![At::Type => "radio"; At::Name => name.as_str(); At::Checked => true],
             ],
             text_area,
+            mention_list,
             seed::input![
                 id![view_id.as_str()],
                 C!["viewRadio"],
@@ -148,3 +206,24 @@ fn click_handler(field_id: FieldId, new_mode: MdEditorMode) -> EventHandler<Msg>
         Msg::ModalChanged(FieldChange::TabChanged(field_id, new_mode))
     })
 }
+
+#[inline(always)]
+fn mention_handler(field_id: FieldId) -> EventHandler<Msg> {
+    keyboard_ev(Ev::KeyUp, move |ev| {
+        let query = match ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+        {
+            Some(text_area) if ev.key() != "Escape" => text_area
+                .selection_start()
+                .ok()
+                .flatten()
+                .and_then(|cursor| {
+                    let (before, _) = split_at_cursor(text_area.value().as_str(), cursor);
+                    mention_query(before.as_str()).map(|query| query.to_string())
+                }),
+            _ => None,
+        };
+        Msg::ModalChanged(FieldChange::MentionQuery(field_id, query))
+    })
+}
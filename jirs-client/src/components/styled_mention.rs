@@ -0,0 +1,102 @@
+use jirs_data::User;
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_avatar::StyledAvatar;
+use crate::{FieldId, Msg};
+
+const MAX_SUGGESTIONS: usize = 5;
+const MAX_QUERY_LENGTH: usize = 40;
+
+/// Text typed after `@` right before cursor. `None` if user is not writing
+/// mention
+pub fn mention_query(before_cursor: &str) -> Option<&str> {
+    let at = before_cursor.rfind('@')?;
+    let query = &before_cursor[(at + 1)..];
+    let starts_word = before_cursor[..at]
+        .chars()
+        .last()
+        .map(|c| c.is_whitespace())
+        .unwrap_or(true);
+    if !starts_word || query.contains('\n') || query.chars().count() > MAX_QUERY_LENGTH {
+        return None;
+    }
+    Some(query)
+}
+
+/// Replace mention query before cursor with full user name. Returns new text
+/// before cursor
+pub fn complete_mention(before_cursor: &str, name: &str) -> Option<String> {
+    let query = mention_query(before_cursor)?;
+    let start = before_cursor.len() - query.len() - 1;
+    Some(format!("{}@{} ", &before_cursor[..start], name))
+}
+
+/// Split text at cursor position. Browser selection offsets are counted in
+/// UTF-16 code units
+pub fn split_at_cursor(text: &str, cursor: u32) -> (String, String) {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    let cursor = (cursor as usize).min(units.len());
+    (
+        String::from_utf16_lossy(&units[..cursor]),
+        String::from_utf16_lossy(&units[cursor..]),
+    )
+}
+
+#[derive(Debug)]
+pub struct StyledMentionList<'l> {
+    pub field_id: FieldId,
+    pub query: Option<&'l str>,
+    pub users: &'l [User],
+    pub on_select: fn(FieldId, String) -> Msg,
+}
+
+impl<'l> StyledMentionList<'l> {
+    pub fn render(self) -> Node<Msg> {
+        let StyledMentionList {
+            field_id,
+            query,
+            users,
+            on_select,
+        } = self;
+        let query = match query {
+            Some(query) => query.to_lowercase(),
+            _ => return Node::Empty,
+        };
+
+        let suggestions: Vec<Node<Msg>> = users
+            .iter()
+            .enumerate()
+            .filter(|(_, user)| user.name.to_lowercase().starts_with(query.as_str()))
+            .take(MAX_SUGGESTIONS)
+            .map(|(index, user)| {
+                let avatar = StyledAvatar {
+                    avatar_url: user.avatar_url.as_deref(),
+                    size: 16,
+                    name: user.name.as_str(),
+                    user_index: index,
+                    ..StyledAvatar::default()
+                }
+                .render();
+                let field_id = field_id.clone();
+                let name = user.name.clone();
+                // mouse down keeps focus in editor
+                let handler = mouse_ev(Ev::MouseDown, move |ev| {
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    on_select(field_id, name)
+                });
+                li![
+                    C!["suggestion"],
+                    avatar,
+                    span![C!["name"], user.name.as_str()],
+                    handler
+                ]
+            })
+            .collect();
+        if suggestions.is_empty() {
+            return Node::Empty;
+        }
+        ul![C!["styledMentionList"], suggestions]
+    }
+}
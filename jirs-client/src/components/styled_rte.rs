@@ -1,8 +1,10 @@
+use jirs_data::User;
 use seed::prelude::*;
 use seed::*;
 
 use crate::components::styled_button::{ButtonVariant, StyledButton};
 use crate::components::styled_icon::{Icon, StyledIcon};
+use crate::components::styled_mention::{mention_query, split_at_cursor, StyledMentionList};
 use crate::components::styled_select::{SelectVariant, StyledSelect, StyledSelectState};
 use crate::components::styled_select_child::StyledSelectOption;
 use crate::components::styled_tooltip::{StyledTooltip, TooltipVariant};
@@ -84,6 +86,10 @@ pub enum RteMsg {
     CodeChanged(String),
     InjectCode,
 
+    // mention
+    MentionQuery(Option<String>),
+    InsertMention(String),
+
     RequestFocus(uuid::Uuid),
 }
 
@@ -139,6 +145,9 @@ impl RteMsg {
             RteMsg::InsertCode(_) => None,
             RteMsg::CodeChanged(_) => None,
             RteMsg::InjectCode => None,
+            // mention
+            RteMsg::MentionQuery(_) => None,
+            RteMsg::InsertMention(_) => None,
 
             // indent
             RteMsg::ChangeIndent(RteIndentMsg::Increase) => Some(ExecCommand::new("indent")),
@@ -244,6 +253,7 @@ pub struct StyledRteState {
     pub code_tooltip: StyledRteCodeState,
     range: Option<web_sys::Range>,
     pub identifier: uuid::Uuid,
+    pub mention: Option<String>,
 }
 
 impl StyledRteState {
@@ -259,6 +269,7 @@ impl StyledRteState {
             code_tooltip: StyledRteCodeState::new(field_id),
             range: None,
             identifier: uuid::Uuid::new_v4(),
+            mention: None,
         }
     }
 
@@ -368,6 +379,48 @@ impl StyledRteState {
                     }
                     self.schedule_focus(orders);
                 }
+                // mention
+                RteMsg::MentionQuery(query) => {
+                    if query.is_some() {
+                        self.store_range();
+                    }
+                    self.mention = query.clone();
+                }
+                RteMsg::InsertMention(name) => {
+                    let query = match self.mention.take() {
+                        Some(query) => query,
+                        _ => return,
+                    };
+                    if self.restore_range().is_err() {
+                        return;
+                    }
+                    let r = match self.range.as_ref() {
+                        Some(r) => r,
+                        _ => return,
+                    };
+                    // select typed `@` with query and replace it with name
+                    let typed = query.encode_utf16().count() as u32 + 1;
+                    let (container, offset) = match (r.end_container(), r.end_offset()) {
+                        (Ok(container), Ok(offset)) if offset >= typed => (container, offset),
+                        _ => return,
+                    };
+                    if let Err(e) = r.set_start(&container, offset - typed) {
+                        error!(e);
+                        return;
+                    }
+                    if let Err(e) = r.delete_contents() {
+                        error!(e);
+                        return;
+                    }
+                    let text =
+                        seed::html_document().create_text_node(format!("@{} ", name).as_str());
+                    if let Err(e) = r.insert_node(&text) {
+                        error!(e);
+                        return;
+                    }
+                    r.collapse_with_to_start(false);
+                    self.schedule_focus(orders);
+                }
                 _ => log::error!("unknown rte command {:?}", m),
             },
         };
@@ -418,6 +471,8 @@ pub struct StyledRte<'component> {
     pub table_tooltip: Option<&'component StyledRteTableState>,
     pub identifier: Option<uuid::Uuid>,
     pub code_tooltip: Option<&'component StyledRteCodeState>,
+    pub users: &'component [User],
+    pub mention: Option<&'component str>,
 }
 
 impl<'component> Default for StyledRte<'component> {
@@ -427,6 +482,8 @@ impl<'component> Default for StyledRte<'component> {
             table_tooltip: None,
             identifier: None,
             code_tooltip: None,
+            users: &[],
+            mention: None,
         }
     }
 }
@@ -590,6 +647,31 @@ impl<'outer> StyledRte<'outer> {
             })
         };
 
+        let mention_handler = {
+            let field_id = self.field_id.clone();
+            keyboard_ev(Ev::KeyUp, move |ev| {
+                let query = match seed::html_document().get_selection() {
+                    Ok(Some(selection)) if ev.key() != "Escape" => selection
+                        .anchor_node()
+                        .and_then(|node| node.text_content())
+                        .and_then(|text| {
+                            let (before, _) =
+                                split_at_cursor(text.as_str(), selection.anchor_offset());
+                            mention_query(before.as_str()).map(|query| query.to_string())
+                        }),
+                    _ => None,
+                };
+                Msg::Rte(field_id, RteMsg::MentionQuery(query))
+            })
+        };
+        let mention_list = StyledMentionList {
+            field_id: self.field_id.clone(),
+            query: self.mention,
+            users: self.users,
+            on_select: |field_id, name| Msg::Rte(field_id, RteMsg::InsertMention(name)),
+        }
+        .render();
+
         div![
             C!["styledRte"],
             attrs![At::Id => id],
@@ -611,8 +693,10 @@ impl<'outer> StyledRte<'outer> {
                 div![
                     C!["editor", self.field_id.to_str()],
                     attrs![At::ContentEditable => true],
+                    mention_handler,
                     // capture_change
                 ],
+                mention_list,
             ]
         ]
     }
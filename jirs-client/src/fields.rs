@@ -4,11 +4,20 @@ use jirs_data::{
 };
 
 pub type AvatarFilterActive = bool;
+pub type LabelFilterActive = bool;
 
 #[derive(Clone, Debug, PartialOrd, PartialEq, Hash)]
 pub enum EditIssueModalSection {
     Issue(IssueFieldId),
     Comment(CommentFieldId),
+    Activity,
+    Watch,
+    LinkType,
+    LinkTarget,
+    SubtaskTitle,
+    WorklogDuration,
+    WorklogStartedAt,
+    WorklogComment,
 }
 
 #[derive(Clone, Debug, PartialOrd, PartialEq, Hash)]
@@ -100,6 +109,14 @@ pub enum FieldId {
     // project boards
     TextFilterBoard,
     CopyButtonLabel,
+    // backlog
+    SprintName,
+    // reports
+    TimesheetFrom,
+    TimesheetTo,
+    BurndownEpic,
+    CumulativeFlowFrom,
+    CumulativeFlowTo,
 
     ProjectSettings(ProjectFieldId),
     Rte(RteField),
@@ -125,6 +142,14 @@ impl FieldId {
                     "timeRemainingIssueEditModal"
                 }
                 EditIssueModalSection::Comment(CommentFieldId::Body) => "editIssue-commentBody",
+                EditIssueModalSection::Activity => "editIssue-activity",
+                EditIssueModalSection::Watch => "editIssue-watch",
+                EditIssueModalSection::LinkType => "editIssue-linkType",
+                EditIssueModalSection::LinkTarget => "editIssue-linkTarget",
+                EditIssueModalSection::SubtaskTitle => "editIssue-subtaskTitle",
+                EditIssueModalSection::WorklogDuration => "editIssue-worklogDuration",
+                EditIssueModalSection::WorklogStartedAt => "editIssue-worklogStartedAt",
+                EditIssueModalSection::WorklogComment => "editIssue-worklogComment",
                 EditIssueModalSection::Issue(IssueFieldId::ListPosition) => {
                     "editIssue-listPosition"
                 }
@@ -133,6 +158,11 @@ impl FieldId {
                     "editIssue-epicStartsAt"
                 }
                 EditIssueModalSection::Issue(IssueFieldId::EpicEndsAt) => "editIssue-epicEndsAt",
+                EditIssueModalSection::Issue(IssueFieldId::Sprint) => "editIssue-sprint",
+                EditIssueModalSection::Issue(IssueFieldId::Labels) => "editIssue-labels",
+                EditIssueModalSection::Issue(IssueFieldId::CustomField(_)) => {
+                    "editIssue-customField"
+                }
             },
             FieldId::AddIssueModal(sub) => match sub {
                 IssueFieldId::Type => "issueTypeAddIssueModal",
@@ -149,9 +179,18 @@ impl FieldId {
                 IssueFieldId::EpicName => "addIssueModal-epicName",
                 IssueFieldId::EpicStartsAt => "addIssueModal-epicStartsAt",
                 IssueFieldId::EpicEndsAt => "addIssueModal-epicEndsAt",
+                IssueFieldId::Sprint => "addIssueModal-sprint",
+                IssueFieldId::Labels => "addIssueModal-labels",
+                IssueFieldId::CustomField(_) => "addIssueModal-customField",
             },
             FieldId::TextFilterBoard => "textFilterBoard",
             FieldId::CopyButtonLabel => "copyButtonLabel",
+            FieldId::SprintName => "backlog-sprintName",
+            FieldId::TimesheetFrom => "reports-timesheetFrom",
+            FieldId::TimesheetTo => "reports-timesheetTo",
+            FieldId::BurndownEpic => "reports-burndownEpic",
+            FieldId::CumulativeFlowFrom => "reports-cumulativeFlowFrom",
+            FieldId::CumulativeFlowTo => "reports-cumulativeFlowTo",
             FieldId::ProjectSettings(sub) => match sub {
                 ProjectFieldId::Name => "projectSettings-name",
                 ProjectFieldId::Url => "projectSettings-url",
@@ -160,15 +199,27 @@ impl FieldId {
                 ProjectFieldId::TimeTracking => "projectSettings-timeTracking",
                 ProjectFieldId::IssueStatusName => "projectSettings-issueStatusName",
                 ProjectFieldId::DescriptionMode => "projectSettings-descriptionMode",
+                ProjectFieldId::LabelName => "projectSettings-labelName",
+                ProjectFieldId::LabelColor => "projectSettings-labelColor",
+                ProjectFieldId::CustomFieldName => "projectSettings-customFieldName",
+                ProjectFieldId::CustomFieldType => "projectSettings-customFieldType",
+                ProjectFieldId::CustomFieldOptions => "projectSettings-customFieldOptions",
+                ProjectFieldId::TransitionFrom => "projectSettings-transitionFrom",
+                ProjectFieldId::TransitionTo => "projectSettings-transitionTo",
+                ProjectFieldId::TransitionRole => "projectSettings-transitionRole",
+                ProjectFieldId::WipLimit(_) => "projectSettings-wipLimit",
             },
             FieldId::SignIn(sub) => match sub {
                 SignInFieldId::Email => "login-email",
                 SignInFieldId::Username => "login-username",
                 SignInFieldId::Token => "login-token",
+                SignInFieldId::Password => "login-password",
+                SignInFieldId::Totp => "login-totp",
             },
             FieldId::SignUp(sub) => match sub {
                 SignUpFieldId::Username => "signUp-email",
                 SignUpFieldId::Email => "signUp-username",
+                SignUpFieldId::Password => "signUp-password",
             },
             FieldId::Invite(sub) => match sub {
                 InviteFieldId::Token => "invite-token",
@@ -180,6 +231,10 @@ impl FieldId {
                 UsersFieldId::Avatar => "users-avatar",
                 UsersFieldId::CurrentProject => "users-currentProject",
                 UsersFieldId::TextEditorMode => "users-textEditorMode",
+                UsersFieldId::NotificationMode => "users-notificationMode",
+                UsersFieldId::Password => "users-password",
+                UsersFieldId::CurrentPassword => "users-currentPassword",
+                UsersFieldId::TotpCode => "users-totpCode",
             },
             FieldId::Profile(sub) => match sub {
                 UsersFieldId::Username => "profile-username",
@@ -188,6 +243,10 @@ impl FieldId {
                 UsersFieldId::Avatar => "profile-avatar",
                 UsersFieldId::CurrentProject => "profile-currentProject",
                 UsersFieldId::TextEditorMode => "profile-textEditorMode",
+                UsersFieldId::NotificationMode => "profile-notificationMode",
+                UsersFieldId::Password => "profile-password",
+                UsersFieldId::CurrentPassword => "profile-currentPassword",
+                UsersFieldId::TotpCode => "profile-totpCode",
             },
             FieldId::EditEpic(sub) => match sub {
                 EpicFieldId::Name => "epicEpic-name",
@@ -202,6 +261,12 @@ impl FieldId {
 
 impl std::fmt::Display for FieldId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.to_str())
+        match self {
+            FieldId::AddIssueModal(IssueFieldId::CustomField(id))
+            | FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::CustomField(
+                id,
+            ))) => write!(f, "{}-{}", self.to_str(), id),
+            _ => f.write_str(self.to_str()),
+        }
     }
 }
@@ -39,8 +39,15 @@ mod ws;
 #[repr(C)]
 pub enum ResourceKind {
     Issue,
+    IssueLink,
     IssueStatus,
+    Label,
+    CustomField,
+    IssueCustomValue,
+    WorkflowTransition,
+    Worklog,
     Epic,
+    Sprint,
     Project,
     User,
     UserSetting,
@@ -85,6 +92,7 @@ pub enum Msg {
     AuthTokenStored,
     AuthTokenErased,
     SignInRequest,
+    PasswordSignInRequest,
     BindClientRequest,
 
     // users
@@ -98,6 +106,7 @@ pub enum Msg {
 
     // project
     ProjectAvatarFilterChanged(UserId, AvatarFilterActive),
+    ProjectLabelFilterChanged(LabelId, LabelFilterActive),
     ProjectToggleOnlyMy,
     ProjectToggleRecentlyUpdated,
     ProjectClearFilters,
@@ -111,7 +120,9 @@ pub enum Msg {
 
     // issues
     AddIssue,
+    AddSubtask,
     DeleteIssue(EpicId),
+    DeleteIssueLink(IssueLinkId),
 
     // epics
     AddEpic,
@@ -126,6 +137,12 @@ pub enum Msg {
     SaveComment,
     DeleteComment(CommentId),
 
+    // worklogs
+    SaveWorklog,
+    /// Load worklog into time tracking form, `None` clears form
+    EditWorklog(Option<WorklogId>),
+    DeleteWorklog(WorklogId),
+
     // profile
     AvatarUpdateFetched(String),
 
@@ -256,6 +273,7 @@ fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>) {
         Page::Users => pages::users_page::update(msg, model, orders),
         Page::Profile => pages::profile_page::update(msg, model, orders),
         Page::Reports => pages::reports_page::update(msg, model, orders),
+        Page::Backlog => pages::backlog_page::update(msg, model, orders),
     }
     if cfg!(features = "print-model") {
         log::debug!("{:?}", model);
@@ -278,6 +296,7 @@ fn view(model: &model::Model) -> Node<Msg> {
         Page::Users => pages::users_page::view(model),
         Page::Profile => pages::profile_page::view(model),
         Page::Reports => pages::reports_page::view(model),
+        Page::Backlog => pages::backlog_page::view(model),
     }
 }
 
@@ -301,6 +320,7 @@ fn resolve_page(url: Url) -> Option<Page> {
         "invite" => Page::Invite,
         "users" => Page::Users,
         "reports" => Page::Reports,
+        "backlog" => Page::Backlog,
         "delete-epic" => match url.path().get(1).as_ref().map(|s| s.parse::<i32>()) {
             Some(Ok(id)) => Page::DeleteEpic(id),
             _ => return None,
@@ -0,0 +1,251 @@
+use chrono::NaiveDate;
+use jirs_data::{
+    CustomField, CustomFieldId, CustomFieldType, CustomValue, IssueFieldId, User, UserId,
+};
+use seed::prelude::*;
+
+use crate::components::styled_avatar::StyledAvatar;
+use crate::components::styled_field::StyledField;
+use crate::components::styled_input::StyledInput;
+use crate::components::styled_select::{
+    SelectVariant, StyledSelect, StyledSelectChanged, StyledSelectState,
+};
+use crate::components::styled_select_child::StyledSelectOption;
+use crate::model::Model;
+use crate::{EditIssueModalSection, FieldId, Msg};
+
+/// Custom field id of add or edit issue modal input
+pub fn custom_field_id(field_id: &FieldId) -> Option<CustomFieldId> {
+    match field_id {
+        FieldId::AddIssueModal(IssueFieldId::CustomField(id))
+        | FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::CustomField(id))) => {
+            Some(*id)
+        }
+        _ => None,
+    }
+}
+
+fn msg_custom_field_id(msg: &Msg) -> Option<CustomFieldId> {
+    match msg {
+        Msg::StrInputChanged(field_id, _) | Msg::StyledSelectChanged(field_id, _) => {
+            custom_field_id(field_id)
+        }
+        _ => None,
+    }
+}
+
+/// Dropdown states of custom fields, created when dropdown is used for the
+/// first time
+#[derive(Clone, Debug, Default, PartialOrd, PartialEq)]
+pub struct CustomFieldStates {
+    states: Vec<StyledSelectState>,
+}
+
+impl CustomFieldStates {
+    pub fn get(&self, field_id: &FieldId) -> Option<&StyledSelectState> {
+        self.states.iter().find(|state| &state.field_id == field_id)
+    }
+
+    pub fn update(&mut self, msg: &Msg, orders: &mut impl Orders<Msg>) {
+        let field_id = match msg {
+            Msg::StyledSelectChanged(field_id, _) if custom_field_id(field_id).is_some() => {
+                field_id
+            }
+            _ => return,
+        };
+        if self.get(field_id).is_none() {
+            self.states
+                .push(StyledSelectState::new(field_id.clone(), vec![]));
+        }
+        for state in self.states.iter_mut() {
+            state.update(msg, orders);
+        }
+    }
+}
+
+/// New value of custom field after user input. Returns `None` when message does
+/// not change field
+pub fn custom_value_changed(
+    field: &CustomField,
+    current: Option<CustomValue>,
+    msg: &Msg,
+) -> Option<Option<CustomValue>> {
+    if msg_custom_field_id(msg) != Some(field.id) {
+        return None;
+    }
+    let value = match (field.field_type, msg) {
+        (CustomFieldType::Text, Msg::StrInputChanged(_, text)) => Some(text.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .map(CustomValue::Text),
+        (CustomFieldType::Number, Msg::StrInputChanged(_, text)) => {
+            text.trim().parse::<f64>().ok().map(CustomValue::Number)
+        }
+        (CustomFieldType::Date, Msg::StrInputChanged(_, text)) => {
+            NaiveDate::parse_from_str(text.as_str(), "%Y-%m-%d")
+                .ok()
+                .map(|date| CustomValue::Date(date.and_hms(0, 0, 0)))
+        }
+        (
+            CustomFieldType::SingleSelect,
+            Msg::StyledSelectChanged(_, StyledSelectChanged::Changed(idx)),
+        ) => idx
+            .and_then(|idx| field.options.get(idx as usize))
+            .cloned()
+            .map(CustomValue::SingleSelect),
+        (
+            CustomFieldType::MultiSelect,
+            Msg::StyledSelectChanged(_, StyledSelectChanged::Changed(Some(idx))),
+        ) => {
+            let mut selected = match current {
+                Some(CustomValue::MultiSelect(selected)) => selected,
+                _ => vec![],
+            };
+            match field.options.get(*idx as usize) {
+                Some(option) if !selected.contains(option) => selected.push(option.clone()),
+                _ => return None,
+            };
+            Some(CustomValue::MultiSelect(selected))
+        }
+        (
+            CustomFieldType::MultiSelect,
+            Msg::StyledSelectChanged(_, StyledSelectChanged::RemoveMulti(idx)),
+        ) => {
+            let mut selected = match current {
+                Some(CustomValue::MultiSelect(selected)) => selected,
+                _ => return None,
+            };
+            if let Some(option) = field.options.get(*idx as usize) {
+                selected.retain(|s| s != option);
+            }
+            Some(selected)
+                .filter(|selected| !selected.is_empty())
+                .map(CustomValue::MultiSelect)
+        }
+        (CustomFieldType::User, Msg::StyledSelectChanged(_, StyledSelectChanged::Changed(id))) => {
+            id.map(|id| CustomValue::User(id as UserId))
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+pub fn custom_field_input(
+    model: &Model,
+    field: &CustomField,
+    field_id: FieldId,
+    value: Option<&CustomValue>,
+    states: &CustomFieldStates,
+) -> Node<Msg> {
+    let input = match field.field_type {
+        CustomFieldType::Text | CustomFieldType::Number | CustomFieldType::Date => {
+            let text = match value {
+                Some(CustomValue::Text(s)) => s.clone(),
+                Some(CustomValue::Number(n)) => n.to_string(),
+                Some(CustomValue::Date(d)) => d.format("%Y-%m-%d").to_string(),
+                _ => String::new(),
+            };
+            StyledInput {
+                id: Some(field_id),
+                value: text.as_str(),
+                input_type: Some(match field.field_type {
+                    CustomFieldType::Number => "number",
+                    CustomFieldType::Date => "date",
+                    _ => "text",
+                }),
+                valid: true,
+                ..Default::default()
+            }
+            .render()
+        }
+        CustomFieldType::SingleSelect | CustomFieldType::MultiSelect => {
+            let selected: Vec<&String> = match value {
+                Some(CustomValue::SingleSelect(s)) => vec![s],
+                Some(CustomValue::MultiSelect(v)) => v.iter().collect(),
+                _ => vec![],
+            };
+            let state = states.get(&field_id);
+            StyledSelect {
+                id: field_id.clone(),
+                name: field.name.as_str(),
+                variant: SelectVariant::Normal,
+                is_multi: field.field_type == CustomFieldType::MultiSelect,
+                clearable: true,
+                text_filter: state.map(|s| s.text_filter.as_str()).unwrap_or_default(),
+                opened: state.map(|s| s.opened).unwrap_or_default(),
+                options: Some(
+                    field
+                        .options
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, option)| option_select_option(idx, option)),
+                ),
+                selected: field
+                    .options
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, option)| selected.contains(option))
+                    .map(|(idx, option)| option_select_option(idx, option))
+                    .collect(),
+                valid: true,
+                ..Default::default()
+            }
+            .render()
+        }
+        CustomFieldType::User => {
+            let state = states.get(&field_id);
+            let user_id = match value {
+                Some(CustomValue::User(id)) => Some(*id),
+                _ => None,
+            };
+            StyledSelect {
+                id: field_id.clone(),
+                name: field.name.as_str(),
+                variant: SelectVariant::Normal,
+                clearable: true,
+                text_filter: state.map(|s| s.text_filter.as_str()).unwrap_or_default(),
+                opened: state.map(|s| s.opened).unwrap_or_default(),
+                options: Some(model.users.iter().map(user_select_option)),
+                selected: model
+                    .users
+                    .iter()
+                    .filter(|user| Some(user.id) == user_id)
+                    .map(user_select_option)
+                    .collect(),
+                valid: true,
+                ..Default::default()
+            }
+            .render()
+        }
+    };
+    StyledField {
+        label: field.name.as_str(),
+        input,
+        ..Default::default()
+    }
+    .render()
+}
+
+fn option_select_option(idx: usize, option: &str) -> StyledSelectOption {
+    StyledSelectOption {
+        value: idx as u32,
+        text: Some(option),
+        ..Default::default()
+    }
+}
+
+fn user_select_option(user: &User) -> StyledSelectOption {
+    StyledSelectOption {
+        value: user.id as u32,
+        icon: Some(
+            StyledAvatar {
+                size: 20,
+                name: &user.name,
+                avatar_url: user.avatar_url.as_deref(),
+                ..StyledAvatar::default()
+            }
+            .render(),
+        ),
+        text: Some(user.name.as_str()),
+        ..Default::default()
+    }
+}
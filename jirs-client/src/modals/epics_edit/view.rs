@@ -82,6 +82,7 @@ fn transform_into_available(modal: &super::Model) -> Node<Msg> {
         options: Some(
             IssueType::default()
                 .into_iter()
+                .filter(|it| *it != IssueType::Subtask)
                 .map(|it| issue_type_select_option(it, &modal.transform_into)),
         ),
         ..Default::default()
@@ -1,11 +1,12 @@
 use derive_enum_iter::EnumIter;
 use derive_enum_primitive::EnumPrimitive;
-use jirs_data::{IssueFieldId, IssuePriority};
+use jirs_data::{CustomFieldId, CustomValue, IssueFieldId, IssuePriority};
 use seed::prelude::*;
 
 use crate::components::styled_date_time_input::*;
 use crate::components::styled_input::*;
 use crate::components::styled_select::*;
+use crate::modals::CustomFieldStates;
 use crate::model::IssueModal;
 use crate::{FieldId, Msg};
 
@@ -62,6 +63,7 @@ pub struct Model {
     pub reporter_id: Option<jirs_data::UserId>,
     pub issue_status_id: jirs_data::IssueStatusId,
     pub epic_id: Option<jirs_data::UserId>,
+    pub custom_values: Vec<(CustomFieldId, CustomValue)>,
 
     // modal fields
     pub title_state: StyledInputState,
@@ -74,6 +76,9 @@ pub struct Model {
     pub epic_name_state: StyledSelectState,
     pub epic_starts_at_state: StyledDateTimeInputState,
     pub epic_ends_at_state: StyledDateTimeInputState,
+
+    // custom fields
+    pub custom_field_states: CustomFieldStates,
 }
 
 impl Default for Model {
@@ -90,6 +95,7 @@ impl Default for Model {
             reporter_id: Default::default(),
             issue_status_id: Default::default(),
             epic_id: Default::default(),
+            custom_values: Default::default(),
             title_state: StyledInputState::new(FieldId::AddIssueModal(IssueFieldId::Title), ""),
             type_state: StyledSelectState::new(FieldId::AddIssueModal(IssueFieldId::Type), vec![]),
             reporter_state: StyledSelectState::new(
@@ -117,6 +123,7 @@ impl Default for Model {
                 FieldId::AddIssueModal(IssueFieldId::EpicEndsAt),
                 None,
             ),
+            custom_field_states: Default::default(),
         }
     }
 }
@@ -139,5 +146,6 @@ impl IssueModal for Model {
         self.epic_name_state.update(msg, orders);
         self.epic_starts_at_state.update(msg, orders);
         self.epic_ends_at_state.update(msg, orders);
+        self.custom_field_states.update(msg, orders);
     }
 }
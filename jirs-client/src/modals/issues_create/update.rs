@@ -2,6 +2,7 @@ use jirs_data::{IssueFieldId, UserId, WsMsg};
 use seed::prelude::*;
 
 use crate::components::styled_select::StyledSelectChanged;
+use crate::modals::{custom_field_id, custom_value_changed};
 use crate::model::IssueModal;
 use crate::ws::send_ws_msg;
 use crate::{FieldId, Msg, OperationKind, ResourceKind};
@@ -9,6 +10,14 @@ use crate::{FieldId, Msg, OperationKind, ResourceKind};
 pub fn update(msg: &Msg, model: &mut crate::model::Model, orders: &mut impl Orders<Msg>) {
     let user_id = model.user_id().unwrap_or_default();
     let project_id = model.project_id().unwrap_or_default();
+    let custom_field = match msg {
+        Msg::StrInputChanged(field_id, _) | Msg::StyledSelectChanged(field_id, _) => {
+            custom_field_id(field_id)
+                .and_then(|id| model.custom_fields.iter().find(|field| field.id == id))
+                .cloned()
+        }
+        _ => None,
+    };
 
     let modal = match &mut model.modals_mut().add_issue {
         Some(modal) => modal,
@@ -44,6 +53,8 @@ pub fn update(msg: &Msg, model: &mut crate::model::Model, orders: &mut impl Orde
                         user_ids: modal.user_ids.clone(),
                         reporter_id: modal.reporter_id.unwrap_or(user_id),
                         epic_id: modal.epic_id,
+                        parent_id: None,
+                        custom_values: modal.custom_values.clone(),
                     };
 
                     send_ws_msg(
@@ -66,6 +77,24 @@ pub fn update(msg: &Msg, model: &mut crate::model::Model, orders: &mut impl Orde
             modal.description = Some(value.clone());
         }
 
+        Msg::StrInputChanged(..) | Msg::StyledSelectChanged(..) if custom_field.is_some() => {
+            let field = match custom_field {
+                Some(field) => field,
+                _ => return,
+            };
+            let current = modal
+                .custom_values
+                .iter()
+                .find(|(id, _)| *id == field.id)
+                .map(|(_, value)| value.clone());
+            if let Some(value) = custom_value_changed(&field, current, msg) {
+                modal.custom_values.retain(|(id, _)| *id != field.id);
+                if let Some(value) = value {
+                    modal.custom_values.push((field.id, value));
+                }
+            }
+        }
+
         // ReporterAddIssueModal
         Msg::StyledSelectChanged(
             FieldId::AddIssueModal(IssueFieldId::Reporter),
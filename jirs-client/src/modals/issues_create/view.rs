@@ -13,8 +13,8 @@ use crate::components::styled_modal::StyledModal;
 use crate::components::styled_select::{SelectVariant, StyledSelect};
 use crate::components::styled_select_child::StyledSelectOption;
 use crate::components::styled_textarea::StyledTextarea;
-use crate::modals::epic_field;
 use crate::modals::issues_create::{Model as AddIssueModal, Type};
+use crate::modals::{custom_field_input, epic_field};
 use crate::model::Model;
 use crate::{FieldId, Msg};
 
@@ -83,6 +83,20 @@ pub fn view(model: &Model, modal: &AddIssueModal) -> Node<Msg> {
             if let Some(field) = epic_field {
                 form.fields.push(field);
             }
+            for field in model.custom_fields() {
+                let value = modal
+                    .custom_values
+                    .iter()
+                    .find(|(id, _)| *id == field.id)
+                    .map(|(_, value)| value);
+                form.fields.push(custom_field_input(
+                    model,
+                    field,
+                    FieldId::AddIssueModal(IssueFieldId::CustomField(field.id)),
+                    value,
+                    &modal.custom_field_states,
+                ));
+            }
         }
     };
 
@@ -1,4 +1,7 @@
-use jirs_data::{Issue, IssueFieldId, IssueId, TextEditorMode, TimeTracking, UpdateIssuePayload};
+use jirs_data::{
+    Issue, IssueFieldId, IssueHistory, IssueId, IssueLinkType, LabelId, TextEditorMode,
+    TimeTracking, UpdateIssuePayload, UserId,
+};
 use seed::prelude::*;
 
 use crate::components::styled_date_time_input::StyledDateTimeInputState;
@@ -6,9 +9,20 @@ use crate::components::styled_editor::StyledEditorState;
 use crate::components::styled_input::StyledInputState;
 use crate::components::styled_select::StyledSelectState;
 use crate::modals::time_tracking::value_for_time_tracking;
+use crate::modals::CustomFieldStates;
 use crate::model::{CommentForm, IssueModal};
 use crate::{EditIssueModalSection, FieldId, Msg};
 
+/// Link types available in edit issue modal. Value of select option is index
+/// in this list, inverted entries swap source and target issue.
+pub static LINK_CHOICES: [(IssueLinkType, bool, &str); 5] = [
+    (IssueLinkType::Blocks, false, "blocks"),
+    (IssueLinkType::Blocks, true, "is blocked by"),
+    (IssueLinkType::Duplicates, false, "duplicates"),
+    (IssueLinkType::Duplicates, true, "is duplicated by"),
+    (IssueLinkType::RelatesTo, false, "relates to"),
+];
+
 #[derive(Debug)]
 pub struct Model {
     pub id: IssueId,
@@ -18,6 +32,8 @@ pub struct Model {
     pub status_state: StyledSelectState,
     pub reporter_state: StyledSelectState,
     pub assignees_state: StyledSelectState,
+    pub label_ids: Vec<LabelId>,
+    pub labels_state: StyledSelectState,
     pub priority_state: StyledSelectState,
     pub epic_name_state: StyledSelectState,
     pub epic_starts_at_state: StyledDateTimeInputState,
@@ -25,8 +41,6 @@ pub struct Model {
 
     pub estimate: StyledInputState,
     pub estimate_select: StyledSelectState,
-    pub time_spent: StyledInputState,
-    pub time_spent_select: StyledSelectState,
     pub time_remaining: StyledInputState,
     pub time_remaining_select: StyledSelectState,
 
@@ -35,6 +49,23 @@ pub struct Model {
 
     // comments
     pub comment_form: CommentForm,
+
+    // activity
+    pub show_activity: bool,
+    pub history: Vec<IssueHistory>,
+
+    // watchers
+    pub watcher_ids: Vec<UserId>,
+
+    // links
+    pub link_type_state: StyledSelectState,
+    pub link_target_state: StyledSelectState,
+
+    // sub-tasks
+    pub subtask_title: StyledInputState,
+
+    // custom fields
+    pub custom_field_states: CustomFieldStates,
 }
 
 impl Model {
@@ -73,6 +104,11 @@ impl Model {
                 FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Assignees)),
                 issue.user_ids.iter().map(|n| *n as u32).collect(),
             ),
+            label_ids: issue.label_ids.clone(),
+            labels_state: StyledSelectState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Labels)),
+                issue.label_ids.iter().map(|n| *n as u32).collect(),
+            ),
             priority_state: StyledSelectState::new(
                 FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Priority)),
                 vec![issue.priority.into()],
@@ -85,14 +121,6 @@ impl Model {
                 FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Estimate)),
                 issue.estimate.map(|n| vec![n as u32]).unwrap_or_default(),
             ),
-            time_spent: StyledInputState::new(
-                FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeSpent)),
-                value_for_time_tracking(&issue.time_spent, &time_tracking_type),
-            ),
-            time_spent_select: StyledSelectState::new(
-                FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeSpent)),
-                issue.time_spent.map(|n| vec![n as u32]).unwrap_or_default(),
-            ),
             time_remaining: StyledInputState::new(
                 FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeRemaining)),
                 value_for_time_tracking(&issue.time_remaining, &time_tracking_type),
@@ -120,6 +148,23 @@ impl Model {
                 body: String::new(),
                 creating: false,
             },
+            show_activity: false,
+            history: vec![],
+            watcher_ids: vec![],
+            link_type_state: StyledSelectState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::LinkType),
+                vec![0],
+            ),
+            link_target_state: StyledSelectState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::LinkTarget),
+                vec![],
+            ),
+            subtask_title: StyledInputState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::SubtaskTitle),
+                "",
+            )
+            .with_min(Some(3)),
+            custom_field_states: CustomFieldStates::default(),
             // epic
             epic_name_state: StyledSelectState::new(
                 FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::EpicName)),
@@ -155,14 +200,17 @@ impl IssueModal for Model {
         self.status_state.update(msg, orders);
         self.reporter_state.update(msg, orders);
         self.assignees_state.update(msg, orders);
+        self.labels_state.update(msg, orders);
         self.priority_state.update(msg, orders);
         self.estimate.update(msg);
         self.estimate_select.update(msg, orders);
-        self.time_spent.update(msg);
-        self.time_spent_select.update(msg, orders);
         self.time_remaining.update(msg);
         self.time_remaining_select.update(msg, orders);
         self.epic_name_state.update(msg, orders);
+        self.link_type_state.update(msg, orders);
+        self.link_target_state.update(msg, orders);
+        self.subtask_title.update(msg);
+        self.custom_field_states.update(msg, orders);
 
         self.title_state.update(msg);
         self.description_state.update(msg, orders);
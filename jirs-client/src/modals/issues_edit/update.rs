@@ -2,6 +2,8 @@ use jirs_data::*;
 use seed::prelude::*;
 
 use crate::components::styled_select::StyledSelectChanged;
+use crate::modals::issues_edit::LINK_CHOICES;
+use crate::modals::{custom_field_id, custom_value_changed};
 use crate::model::{IssueModal, Model};
 use crate::ws::send_ws_msg;
 use crate::{EditIssueModalSection, FieldChange, FieldId, Msg, OperationKind, ResourceKind};
@@ -21,8 +23,49 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     issue.description_text.as_deref().unwrap_or_default(),
                     issue.description.as_deref().unwrap_or_default(),
                 );
+                modal.label_ids = issue.label_ids.clone();
                 modal.payload = issue.into();
             }
+            if modal.show_activity {
+                send_ws_msg(WsMsg::IssueHistoryLoad(modal.id), model.ws.as_ref(), orders);
+            }
+            send_ws_msg(
+                WsMsg::IssueWatchersLoad(modal.id),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+
+        // custom fields
+        Msg::StrInputChanged(field_id, _) | Msg::StyledSelectChanged(field_id, _)
+            if custom_field_id(field_id).is_some() =>
+        {
+            let id = custom_field_id(field_id);
+            let field = match model
+                .custom_fields
+                .iter()
+                .find(|field| Some(field.id) == id)
+            {
+                Some(field) => field,
+                _ => return,
+            };
+            let issue_id = modal.id;
+            let current = model
+                .issue_custom_values
+                .iter()
+                .find(|value| value.issue_id == issue_id && value.custom_field_id == field.id)
+                .and_then(|value| value.value(field.field_type));
+            if let Some(value) = custom_value_changed(field, current, msg) {
+                send_ws_msg(
+                    WsMsg::IssueUpdate(
+                        issue_id,
+                        IssueFieldId::CustomField(field.id),
+                        PayloadVariant::CustomValue(value),
+                    ),
+                    model.ws.as_ref(),
+                    orders,
+                );
+            }
         }
 
         // type
@@ -114,6 +157,39 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             );
         }
 
+        // labels
+        Msg::StyledSelectChanged(
+            FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Labels)),
+            StyledSelectChanged::Changed(Some(value)),
+        ) => {
+            modal.label_ids.push(*value as i32);
+            send_ws_msg(
+                WsMsg::IssueUpdate(
+                    modal.id,
+                    IssueFieldId::Labels,
+                    PayloadVariant::VecI32(modal.label_ids.clone()),
+                ),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::StyledSelectChanged(
+            FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Labels)),
+            StyledSelectChanged::RemoveMulti(value),
+        ) => {
+            let dropped = *value as i32;
+            modal.label_ids.retain(|id| *id != dropped);
+            send_ws_msg(
+                WsMsg::IssueUpdate(
+                    modal.id,
+                    IssueFieldId::Labels,
+                    PayloadVariant::VecI32(modal.label_ids.clone()),
+                ),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+
         // priority
         Msg::StyledSelectChanged(
             FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Priority)),
@@ -173,37 +249,6 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             );
             orders.skip();
         }
-        // TimeSpent
-        Msg::StrInputChanged(
-            FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeSpent)),
-            ..,
-        ) => {
-            modal.payload.time_spent = modal.time_spent.represent_f64_as_i32();
-            send_ws_msg(
-                WsMsg::IssueUpdate(
-                    modal.id,
-                    IssueFieldId::TimeSpent,
-                    PayloadVariant::OptionI32(modal.payload.time_spent),
-                ),
-                model.ws.as_ref(),
-                orders,
-            );
-        }
-        Msg::StyledSelectChanged(
-            FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeSpent)),
-            StyledSelectChanged::Changed(..),
-        ) => {
-            modal.payload.time_spent = modal.time_spent_select.values.get(0).map(|n| *n as i32);
-            send_ws_msg(
-                WsMsg::IssueUpdate(
-                    modal.id,
-                    IssueFieldId::TimeSpent,
-                    PayloadVariant::OptionI32(modal.payload.time_spent),
-                ),
-                model.ws.as_ref(),
-                orders,
-            );
-        }
         // Time Remaining
         Msg::StrInputChanged(
             FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeRemaining)),
@@ -312,6 +357,12 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 }),
             };
             send_ws_msg(msg, model.ws.as_ref(), orders);
+            // commenting user becomes watcher
+            send_ws_msg(
+                WsMsg::IssueWatchersLoad(modal.id),
+                model.ws.as_ref(),
+                orders,
+            );
             orders
                 .skip()
                 .send_msg(Msg::ModalChanged(FieldChange::ToggleCommentForm(
@@ -334,6 +385,93 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             modal.comment_form.id = Some(id);
             modal.comment_form.creating = true;
         }
+
+        // activity
+        Msg::ModalChanged(FieldChange::ToggleActivity(
+            FieldId::EditIssueModal(EditIssueModalSection::Activity),
+            flag,
+        )) => {
+            modal.show_activity = *flag;
+            if *flag {
+                send_ws_msg(WsMsg::IssueHistoryLoad(modal.id), model.ws.as_ref(), orders);
+            }
+        }
+
+        // watchers
+        Msg::ModalChanged(FieldChange::ToggleWatch(
+            FieldId::EditIssueModal(EditIssueModalSection::Watch),
+            watch,
+        )) => {
+            send_ws_msg(
+                WsMsg::IssueWatch(modal.id, *watch),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+
+        // links
+        Msg::StyledSelectChanged(
+            FieldId::EditIssueModal(EditIssueModalSection::LinkTarget),
+            StyledSelectChanged::Changed(Some(value)),
+        ) => {
+            let choice = modal
+                .link_type_state
+                .values
+                .first()
+                .and_then(|idx| LINK_CHOICES.get(*idx as usize));
+            if let Some((link_type, inverted, _)) = choice {
+                let (source_id, target_id) = if *inverted {
+                    (*value as IssueId, modal.id)
+                } else {
+                    (modal.id, *value as IssueId)
+                };
+                send_ws_msg(
+                    WsMsg::IssueLinkCreate(*link_type, source_id, target_id),
+                    model.ws.as_ref(),
+                    orders,
+                );
+            }
+            modal.link_target_state.reset();
+        }
+        Msg::DeleteIssueLink(link_id) => {
+            send_ws_msg(WsMsg::IssueLinkDelete(*link_id), model.ws.as_ref(), orders);
+        }
+
+        // sub-tasks
+        Msg::AddSubtask => {
+            let user_id = match model.user.as_ref().map(|user| user.id) {
+                Some(id) => id,
+                _ => return,
+            };
+            let title = modal.subtask_title.value.trim().to_string();
+            if title.len() < 3 {
+                return;
+            }
+            let epic_id = model
+                .issues_by_id
+                .get(&modal.id)
+                .and_then(|issue| issue.epic_id);
+            let payload = CreateIssuePayload {
+                title,
+                issue_type: IssueType::Subtask,
+                priority: modal.payload.priority,
+                description: None,
+                description_text: None,
+                estimate: None,
+                time_spent: None,
+                time_remaining: None,
+                project_id: modal.payload.project_id,
+                user_ids: vec![],
+                reporter_id: user_id,
+                issue_status_id: 0,
+                epic_id,
+                parent_id: Some(modal.id),
+                custom_values: vec![],
+            };
+            send_ws_msg(WsMsg::IssueCreate(payload), model.ws.as_ref(), orders);
+            modal.subtask_title.reset();
+        }
+
         Msg::DeleteComment(comment_id) => {
             send_ws_msg(WsMsg::CommentDelete(*comment_id), model.ws.as_ref(), orders);
             orders.skip().send_msg(Msg::ModalDropped);
@@ -1,10 +1,13 @@
+use activity::*;
 use comments::*;
 use jirs_data::{
-    CommentFieldId, IssueFieldId, IssuePriority, IssueStatus, IssueType, TimeTracking,
+    CommentFieldId, IssueFieldId, IssuePriority, IssueStatus, IssueType, Label, TimeTracking,
     UpdateIssuePayload, User,
 };
+use links::*;
 use seed::prelude::*;
 use seed::*;
+use subtasks::*;
 
 use crate::components::styled_avatar::StyledAvatar;
 use crate::components::styled_button::{ButtonVariant, StyledButton};
@@ -16,14 +19,17 @@ use crate::components::styled_modal::*;
 use crate::components::styled_select::{SelectVariant, StyledSelect, StyledSelectState};
 use crate::components::styled_select_child::StyledSelectOption;
 use crate::components::styled_tip::styled_tip;
-use crate::modals::epic_field;
 use crate::modals::issues_edit::Model as EditIssueModal;
 use crate::modals::time_tracking::time_tracking_field;
+use crate::modals::{custom_field_input, epic_field};
 use crate::model::{ModalType, Model};
 use crate::shared::tracking_widget::tracking_link;
 use crate::{BuildMsg, EditIssueModalSection, FieldChange, FieldId, Msg};
 
+mod activity;
 mod comments;
+mod links;
+mod subtasks;
 
 #[inline(always)]
 pub fn view(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
@@ -57,16 +63,22 @@ pub fn details(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
 }
 
 #[inline(always)]
-fn modal_header(_model: &Model, modal: &EditIssueModal) -> Node<Msg> {
+fn modal_header(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
     let EditIssueModal {
         id,
         payload,
         top_type_state,
         link_copied,
+        watcher_ids,
         ..
     } = modal;
 
     let issue_id = *id;
+    let watching = model
+        .user
+        .as_ref()
+        .map(|user| watcher_ids.contains(&user.id))
+        .unwrap_or_default();
 
     let click_handler = mouse_ev(Ev::Click, move |_| {
         let proto = seed::window().location().protocol().unwrap_or_default();
@@ -116,6 +128,21 @@ fn modal_header(_model: &Model, modal: &EditIssueModal) -> Node<Msg> {
         ..Default::default()
     }
     .render();
+    let watch_handler = mouse_ev(Ev::Click, move |ev| {
+        ev.stop_propagation();
+        Msg::ModalChanged(FieldChange::ToggleWatch(
+            FieldId::EditIssueModal(EditIssueModalSection::Watch),
+            !watching,
+        ))
+    });
+    let watch_button = StyledButton {
+        variant: ButtonVariant::Empty,
+        icon: Some(StyledIcon::from(Icon::Message).render()),
+        on_click: Some(watch_handler),
+        children: vec![span![if watching { "Watching" } else { "Watch" }]],
+        ..Default::default()
+    }
+    .render();
     let delete_button = StyledButton {
         variant: ButtonVariant::Empty,
         icon: Some(
@@ -148,6 +175,12 @@ fn modal_header(_model: &Model, modal: &EditIssueModal) -> Node<Msg> {
     let issue_type_select = {
         let id = modal.id;
         let issue_type = &payload.issue_type;
+        // sub-task type is reserved for issues with parent
+        let is_subtask = model
+            .issues_by_id
+            .get(&id)
+            .map(|issue| issue.parent_id.is_some())
+            .unwrap_or_default();
         let text = format!("{} - {}", issue_type, id);
 
         StyledSelect {
@@ -160,6 +193,7 @@ fn modal_header(_model: &Model, modal: &EditIssueModal) -> Node<Msg> {
             options: Some(
                 IssueType::default()
                     .into_iter()
+                    .filter(|t| (*t == IssueType::Subtask) == is_subtask)
                     .map(|t| type_select_option(t, &text)),
             ),
             selected: vec![type_select_option(payload.issue_type, &text)],
@@ -173,6 +207,7 @@ fn modal_header(_model: &Model, modal: &EditIssueModal) -> Node<Msg> {
         issue_type_select,
         div![
             C!["topActionsRight"],
+            watch_button,
             copy_button,
             delete_button,
             close_button
@@ -205,6 +240,8 @@ fn left_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
     let EditIssueModal {
         description_state,
         comment_form,
+        show_activity,
+        history,
         ..
     } = modal;
 
@@ -220,7 +257,7 @@ fn left_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
     }
     .render();
 
-    let description = render_styled_editor(&description_state);
+    let description = render_styled_editor(description_state, &model.users);
     let description_field = StyledField {
         input: description,
         ..Default::default()
@@ -249,15 +286,23 @@ fn left_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
         vec![div![C!["fakeTextArea"], "Add a comment...", handler]]
     };
 
-    let comments = model.comments.iter().flat_map(|c| comment(model, modal, c));
+    let tabs = div![
+        C!["title"],
+        activity_tab("Comments", !*show_activity, false),
+        activity_tab("Activity", *show_activity, true),
+    ];
 
-    div![
-        C!["left"],
-        title,
-        description_field,
+    let section = if *show_activity {
+        div![
+            C!["activity"],
+            tabs,
+            history.iter().flat_map(|entry| history_entry(model, entry))
+        ]
+    } else {
+        let comments = model.comments.iter().flat_map(|c| comment(model, modal, c));
         div![
             C!["comments"],
-            div![C!["title"], "Comments"],
+            tabs,
             div![
                 C!["create"],
                 user_avatar,
@@ -268,10 +313,34 @@ fn left_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
                 ]
             ],
             comments
-        ],
+        ]
+    };
+
+    let subtasks = subtasks(model, modal);
+    let links = issue_links(model, modal);
+
+    div![
+        C!["left"],
+        title,
+        description_field,
+        subtasks,
+        links,
+        section
     ]
 }
 
+#[inline(always)]
+fn activity_tab(label: &'static str, active: bool, show_activity: bool) -> Node<Msg> {
+    let handler = mouse_ev(Ev::Click, move |ev| {
+        ev.stop_propagation();
+        Msg::ModalChanged(FieldChange::ToggleActivity(
+            FieldId::EditIssueModal(EditIssueModalSection::Activity),
+            show_activity,
+        ))
+    });
+    span![C!["tab", IF![active => "active"]], label, handler]
+}
+
 #[derive(Debug)]
 pub struct EnableCommentBuilder;
 
@@ -299,6 +368,8 @@ fn right_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
 
     let assignees_field = assignees_select(model, payload, assignees_state);
 
+    let labels_field = labels_select(model, modal);
+
     let reporter_field = reporters_select(model, payload, reporter_state);
 
     let priority_field = priorities_select(payload, priority_state);
@@ -338,15 +409,34 @@ fn right_modal_column(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
     )
     .unwrap_or(Node::Empty);
 
+    let custom_fields: Vec<Node<Msg>> = model
+        .custom_fields()
+        .iter()
+        .map(|field| {
+            let value = model.issue_custom_value(modal.id, field);
+            custom_field_input(
+                model,
+                field,
+                FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::CustomField(
+                    field.id,
+                ))),
+                value.as_ref(),
+                &modal.custom_field_states,
+            )
+        })
+        .collect();
+
     div![
         C!["right"],
         status_field,
         assignees_field,
+        labels_field,
         reporter_field,
         priority_field,
         estimate_field,
         tracking_field,
         epic_field,
+        custom_fields,
     ]
 }
 
@@ -520,6 +610,47 @@ fn assignees_select(
     .render()
 }
 
+#[inline(always)]
+fn labels_select(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
+    let labels = StyledSelect {
+        id: FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::Labels)),
+        name: "labels",
+        variant: SelectVariant::Empty,
+        is_multi: true,
+        opened: modal.labels_state.opened,
+        text_filter: modal.labels_state.text_filter.as_str(),
+        options: Some(model.labels().iter().map(label_select_option)),
+        selected: model
+            .labels()
+            .iter()
+            .filter(|label| modal.label_ids.contains(&label.id))
+            .map(label_select_option)
+            .collect(),
+        ..Default::default()
+    }
+    .render();
+    StyledField {
+        input: labels,
+        label: "Labels",
+        ..Default::default()
+    }
+    .render()
+}
+
+#[inline(always)]
+fn label_select_option<'l>(label: &'l Label) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        value: label.id as u32,
+        icon: Some(span![
+            C!["labelColor"],
+            style![St::BackgroundColor => label.color.as_str()]
+        ]),
+        text: Some(label.name.as_str()),
+        name: Some("labels"),
+        ..Default::default()
+    }
+}
+
 #[inline(always)]
 fn assignee_select_option<'l>(user: &'l User) -> StyledSelectOption<'l> {
     StyledSelectOption {
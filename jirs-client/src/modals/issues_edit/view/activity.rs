@@ -0,0 +1,112 @@
+use jirs_data::{IssueHistory, IssueHistoryField};
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_avatar::StyledAvatar;
+use crate::model::Model;
+use crate::Msg;
+
+#[inline(always)]
+pub fn history_entry(model: &Model, entry: &IssueHistory) -> Option<Node<Msg>> {
+    let user = model.users_by_id.get(&entry.user_id)?;
+
+    let avatar = StyledAvatar {
+        avatar_url: user.avatar_url.as_deref(),
+        size: 32,
+        class_list: "userAvatar",
+        ..StyledAvatar::default()
+    }
+    .render();
+
+    let change = match entry.field {
+        IssueHistoryField::Description => {
+            vec![span![C!["field"], "Description"], plain![" was changed"]]
+        }
+        field => vec![
+            span![C!["field"], field_label(field)],
+            plain![" from "],
+            span![
+                C!["oldValue"],
+                display_value(model, field, entry.old_value.as_deref())
+            ],
+            plain![" to "],
+            span![
+                C!["newValue"],
+                display_value(model, field, entry.new_value.as_deref())
+            ],
+        ],
+    };
+
+    Some(div![
+        C!["historyEntry"],
+        avatar,
+        div![
+            C!["content"],
+            div![C!["userName"], user.name.as_str()],
+            div![
+                C!["createdAt"],
+                entry.created_at.format("%d/%m/%Y %H:%M").to_string()
+            ],
+            p![C!["change"], change],
+        ]
+    ])
+}
+
+fn field_label(field: IssueHistoryField) -> &'static str {
+    match field {
+        IssueHistoryField::Title => "Title",
+        IssueHistoryField::Type => "Type",
+        IssueHistoryField::Status => "Status",
+        IssueHistoryField::Priority => "Priority",
+        IssueHistoryField::Description => "Description",
+        IssueHistoryField::Estimate => "Original estimate",
+        IssueHistoryField::TimeSpent => "Time spent",
+        IssueHistoryField::TimeRemaining => "Time remaining",
+        IssueHistoryField::Assignees => "Assignees",
+        IssueHistoryField::Reporter => "Reporter",
+        IssueHistoryField::Epic => "Epic",
+        IssueHistoryField::Sprint => "Sprint",
+    }
+}
+
+/// References are stored as ids, resolve them to names when possible
+fn display_value(model: &Model, field: IssueHistoryField, value: Option<&str>) -> String {
+    let value = match value {
+        Some(v) if !v.is_empty() => v,
+        _ => return "None".to_string(),
+    };
+    let user_name = |id: &str| {
+        id.parse()
+            .ok()
+            .and_then(|id| model.users_by_id.get(&id))
+            .map(|u| u.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    match field {
+        IssueHistoryField::Status => value
+            .parse()
+            .ok()
+            .and_then(|id: i32| model.issue_statuses().iter().find(|s| s.id == id))
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| value.to_string()),
+        IssueHistoryField::Epic => value
+            .parse()
+            .ok()
+            .and_then(|id| model.epics_by_id.get(&id))
+            .map(|e| e.name.clone())
+            .unwrap_or_else(|| value.to_string()),
+        IssueHistoryField::Sprint => value
+            .parse()
+            .ok()
+            .and_then(|id: i32| model.sprints().iter().find(|s| s.id == id))
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| value.to_string()),
+        IssueHistoryField::Reporter => user_name(value),
+        IssueHistoryField::Assignees => value
+            .split(',')
+            .map(user_name)
+            .collect::<Vec<String>>()
+            .join(", "),
+        _ => value.to_string(),
+    }
+}
@@ -0,0 +1,164 @@
+use jirs_data::{Issue, IssueLink, IssueLinkType};
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_button::{ButtonVariant, StyledButton};
+use crate::components::styled_field::StyledField;
+use crate::components::styled_icon::{Icon, StyledIcon};
+use crate::components::styled_select::{SelectVariant, StyledSelect};
+use crate::components::styled_select_child::StyledSelectOption;
+use crate::modals::issues_edit::{Model as EditIssueModal, LINK_CHOICES};
+use crate::model::Model;
+use crate::{EditIssueModalSection, FieldId, Msg};
+
+#[inline(always)]
+pub fn issue_links(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
+    let links: Vec<Node<Msg>> = model
+        .issue_links()
+        .iter()
+        .filter(|link| link.source_id == modal.id || link.target_id == modal.id)
+        .filter_map(|link| link_row(model, modal, link))
+        .collect();
+
+    let link_type = StyledSelect {
+        id: FieldId::EditIssueModal(EditIssueModalSection::LinkType),
+        name: "linkType",
+        opened: modal.link_type_state.opened,
+        variant: SelectVariant::Normal,
+        options: Some(
+            LINK_CHOICES
+                .iter()
+                .enumerate()
+                .map(|(idx, (_, _, label))| link_type_option(idx, label)),
+        ),
+        selected: modal
+            .link_type_state
+            .values
+            .iter()
+            .filter_map(|idx| {
+                LINK_CHOICES
+                    .get(*idx as usize)
+                    .map(|(_, _, label)| link_type_option(*idx as usize, label))
+            })
+            .collect(),
+        ..Default::default()
+    }
+    .render();
+
+    let link_target = StyledSelect {
+        id: FieldId::EditIssueModal(EditIssueModalSection::LinkTarget),
+        name: "linkTarget",
+        opened: modal.link_target_state.opened,
+        variant: SelectVariant::Normal,
+        text_filter: modal.link_target_state.text_filter.as_str(),
+        options: Some(
+            model
+                .issues()
+                .iter()
+                .filter(|issue| issue.id != modal.id)
+                .map(link_target_option),
+        ),
+        selected: vec![],
+        ..Default::default()
+    }
+    .render();
+
+    let input = div![
+        C!["issueLinks"],
+        links,
+        div![C!["addLink"], link_type, link_target]
+    ];
+
+    StyledField {
+        label: "Linked issues",
+        input,
+        ..Default::default()
+    }
+    .render()
+}
+
+#[inline(always)]
+fn link_row(model: &Model, modal: &EditIssueModal, link: &IssueLink) -> Option<Node<Msg>> {
+    let inverted = link.target_id == modal.id;
+    let other_id = if inverted {
+        link.source_id
+    } else {
+        link.target_id
+    };
+    let other = model.issues_by_id.get(&other_id)?;
+    let status = model
+        .issue_statuses()
+        .iter()
+        .find(|status| status.id == other.issue_status_id)
+        .map(|status| status.name.as_str())
+        .unwrap_or_default();
+
+    let link_id = link.id;
+    let delete_button = StyledButton {
+        variant: ButtonVariant::Empty,
+        icon: Some(
+            StyledIcon {
+                icon: Icon::Close,
+                size: Some(16),
+                ..Default::default()
+            }
+            .render(),
+        ),
+        on_click: Some(mouse_ev(Ev::Click, move |ev| {
+            ev.stop_propagation();
+            Msg::DeleteIssueLink(link_id)
+        })),
+        ..Default::default()
+    }
+    .render();
+
+    Some(div![
+        C!["issueLink"],
+        span![C!["linkType"], link_label(link.link_type, inverted)],
+        a![
+            C!["linkedIssue"],
+            attrs![At::Href => format!("/issues/{id}", id = other_id)],
+            other.title.as_str()
+        ],
+        span![C!["status"], status],
+        delete_button,
+    ])
+}
+
+fn link_label(link_type: IssueLinkType, inverted: bool) -> &'static str {
+    LINK_CHOICES
+        .iter()
+        .find(|(t, i, _)| *t == link_type && *i == inverted)
+        .or_else(|| LINK_CHOICES.iter().find(|(t, _, _)| *t == link_type))
+        .map(|(_, _, label)| *label)
+        .unwrap_or_default()
+}
+
+#[inline(always)]
+fn link_type_option<'l>(idx: usize, label: &'l str) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        value: idx as u32,
+        text: Some(label),
+        name: Some("linkType"),
+        ..Default::default()
+    }
+}
+
+#[inline(always)]
+fn link_target_option<'l>(issue: &'l Issue) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        value: issue.id as u32,
+        icon: Some(
+            StyledIcon {
+                icon: issue.issue_type.into(),
+                class_list: issue.issue_type.to_str(),
+                color: Some(issue.issue_type.to_str()),
+                ..Default::default()
+            }
+            .render(),
+        ),
+        text: Some(issue.title.as_str()),
+        name: Some("linkTarget"),
+        ..Default::default()
+    }
+}
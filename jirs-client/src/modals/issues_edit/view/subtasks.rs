@@ -0,0 +1,110 @@
+use jirs_data::Issue;
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_button::{ButtonVariant, StyledButton};
+use crate::components::styled_field::StyledField;
+use crate::components::styled_icon::StyledIcon;
+use crate::components::styled_input::StyledInput;
+use crate::modals::issues_edit::Model as EditIssueModal;
+use crate::model::Model;
+use crate::{EditIssueModalSection, FieldId, Msg};
+
+#[inline(always)]
+pub fn subtasks(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
+    let is_subtask = model
+        .issues_by_id
+        .get(&modal.id)
+        .map(|issue| issue.parent_id.is_some())
+        .unwrap_or_default();
+    if is_subtask {
+        return Node::Empty;
+    }
+
+    let children: Vec<&Issue> = model
+        .issues()
+        .iter()
+        .filter(|issue| issue.parent_id == Some(modal.id))
+        .collect();
+    let done_status_id = model
+        .issue_statuses()
+        .iter()
+        .max_by_key(|status| status.position)
+        .map(|status| status.id);
+    let done = children
+        .iter()
+        .filter(|issue| Some(issue.issue_status_id) == done_status_id)
+        .count();
+
+    let progress = if children.is_empty() {
+        Node::Empty
+    } else {
+        div![
+            C!["progress"],
+            format!("{} of {} done", done, children.len())
+        ]
+    };
+
+    let rows: Vec<Node<Msg>> = children
+        .iter()
+        .map(|issue| subtask_row(model, issue))
+        .collect();
+
+    let input = StyledInput {
+        id: Some(FieldId::EditIssueModal(EditIssueModalSection::SubtaskTitle)),
+        value: modal.subtask_title.value.as_str(),
+        valid: true,
+        ..Default::default()
+    }
+    .render();
+    let submit = StyledButton {
+        variant: ButtonVariant::Secondary,
+        text: Some("Add"),
+        ..Default::default()
+    }
+    .render();
+    let form = form![
+        C!["addSubtask"],
+        ev(Ev::Submit, |ev| {
+            ev.prevent_default();
+            Msg::AddSubtask
+        }),
+        input,
+        submit,
+    ];
+
+    StyledField {
+        label: "Sub-tasks",
+        input: div![C!["subtasks"], progress, rows, form],
+        ..Default::default()
+    }
+    .render()
+}
+
+#[inline(always)]
+fn subtask_row(model: &Model, issue: &Issue) -> Node<Msg> {
+    let icon = StyledIcon {
+        icon: issue.issue_type.into(),
+        class_list: issue.issue_type.to_str(),
+        color: Some(issue.issue_type.to_str()),
+        ..Default::default()
+    }
+    .render();
+    let status = model
+        .issue_statuses()
+        .iter()
+        .find(|status| status.id == issue.issue_status_id)
+        .map(|status| status.name.as_str())
+        .unwrap_or_default();
+
+    div![
+        C!["subtask"],
+        icon,
+        a![
+            C!["title"],
+            attrs![At::Href => format!("/issues/{id}", id = issue.id)],
+            issue.title.as_str()
+        ],
+        span![C!["status"], status],
+    ]
+}
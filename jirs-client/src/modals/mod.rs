@@ -1,3 +1,4 @@
+pub use custom_fields::*;
 pub use epic_field::*;
 pub use update::*;
 pub use view::*;
@@ -13,6 +14,7 @@ pub mod issues_delete;
 pub mod issues_edit;
 pub mod time_tracking;
 
+mod custom_fields;
 mod epic_field;
 mod update;
 mod view;
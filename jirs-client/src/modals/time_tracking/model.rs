@@ -1,12 +1,66 @@
-use jirs_data::IssueId;
+use jirs_data::{IssueId, TimeTracking, Worklog, WorklogId};
 
-#[derive(Debug, Default)]
+use crate::components::styled_input::StyledInputState;
+use crate::components::styled_select::StyledSelectState;
+use crate::modals::time_tracking::value_for_time_tracking;
+use crate::{EditIssueModalSection, FieldId};
+
+#[derive(Debug)]
 pub struct Model {
     pub issue_id: IssueId,
+    /// Worklog loaded into form, new one is created when not set
+    pub worklog_id: Option<WorklogId>,
+    pub duration: StyledInputState,
+    pub duration_select: StyledSelectState,
+    pub started_at: StyledInputState,
+    pub comment: StyledInputState,
 }
 
 impl Model {
     pub fn new(issue_id: IssueId) -> Self {
-        Self { issue_id }
+        Self {
+            issue_id,
+            worklog_id: None,
+            duration: StyledInputState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::WorklogDuration),
+                "",
+            ),
+            duration_select: StyledSelectState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::WorklogDuration),
+                vec![],
+            ),
+            started_at: StyledInputState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::WorklogStartedAt),
+                today(),
+            ),
+            comment: StyledInputState::new(
+                FieldId::EditIssueModal(EditIssueModalSection::WorklogComment),
+                "",
+            ),
+        }
+    }
+
+    pub fn reset_form(&mut self) {
+        self.worklog_id = None;
+        self.duration.reset();
+        self.duration_select.reset();
+        self.started_at.value = today();
+        self.comment.reset();
     }
+
+    pub fn load_worklog(&mut self, worklog: &Worklog, time_tracking_type: TimeTracking) {
+        self.worklog_id = Some(worklog.id);
+        self.duration.value = value_for_time_tracking(&Some(worklog.duration), &time_tracking_type);
+        self.duration_select.values = vec![worklog.duration as u32];
+        self.started_at.value = worklog.started_at.format("%Y-%m-%d").to_string();
+        self.comment.value = worklog.comment.clone().unwrap_or_default();
+    }
+}
+
+fn today() -> String {
+    chrono::Utc::now()
+        .naive_utc()
+        .date()
+        .format("%Y-%m-%d")
+        .to_string()
 }
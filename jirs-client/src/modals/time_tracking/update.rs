@@ -1 +1,74 @@
+use chrono::NaiveDate;
+use jirs_data::{TimeTracking, WsMsg};
+use seed::prelude::*;
 
+use crate::model::Model;
+use crate::ws::send_ws_msg;
+use crate::{Msg, OperationKind, ResourceKind};
+
+pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    let time_tracking_type = model
+        .project
+        .as_ref()
+        .map(|p| p.time_tracking)
+        .unwrap_or(TimeTracking::Untracked);
+    let user_id = model.user.as_ref().map(|u| u.id);
+
+    let modal = match &mut model.modals.time_tracking {
+        Some(modal) => modal,
+        _ => return,
+    };
+    modal.duration.update(msg);
+    modal.duration_select.update(msg, orders);
+    modal.started_at.update(msg);
+    modal.comment.update(msg);
+
+    match msg {
+        Msg::SaveWorklog => {
+            let duration = match time_tracking_type {
+                TimeTracking::Fibonacci => modal.duration_select.values.get(0).map(|n| *n as i32),
+                TimeTracking::Hourly => modal.duration.represent_f64_as_i32(),
+                TimeTracking::Untracked => None,
+            };
+            let started_at = NaiveDate::parse_from_str(modal.started_at.value.as_str(), "%Y-%m-%d");
+            let (duration, started_at) = match (duration, started_at) {
+                (Some(duration), Ok(date)) if duration > 0 => (duration, date.and_hms(0, 0, 0)),
+                _ => return,
+            };
+            let comment = Some(modal.comment.value.clone());
+            let ws_msg = match modal.worklog_id {
+                Some(worklog_id) => WsMsg::WorklogUpdate(worklog_id, duration, started_at, comment),
+                None => WsMsg::WorklogCreate(modal.issue_id, duration, started_at, comment),
+            };
+            send_ws_msg(ws_msg, model.ws.as_ref(), orders);
+        }
+        Msg::EditWorklog(Some(worklog_id)) => {
+            if let Some(worklog) = model.worklogs.iter().find(|w| w.id == *worklog_id) {
+                modal.load_worklog(worklog, time_tracking_type);
+            }
+        }
+        Msg::EditWorklog(None) => {
+            modal.reset_form();
+        }
+        Msg::DeleteWorklog(worklog_id) => {
+            if modal.worklog_id == Some(*worklog_id) {
+                modal.reset_form();
+            }
+            send_ws_msg(WsMsg::WorklogDelete(*worklog_id), model.ws.as_ref(), orders);
+        }
+        Msg::ResourceChanged(
+            ResourceKind::Worklog,
+            OperationKind::SingleCreated | OperationKind::SingleModified,
+            Some(worklog_id),
+        ) => {
+            let own = model
+                .worklogs
+                .iter()
+                .any(|w| w.id == *worklog_id && Some(w.user_id) == user_id);
+            if own {
+                modal.reset_form();
+            }
+        }
+        _ => (),
+    }
+}
@@ -1,9 +1,11 @@
-use jirs_data::{IssueFieldId, IssueId, TimeTracking};
+use jirs_data::{IssueFieldId, IssueId, TimeTracking, Worklog};
 use seed::prelude::*;
 use seed::*;
 
-use crate::components::styled_button::StyledButton;
+use crate::components::styled_avatar::StyledAvatar;
+use crate::components::styled_button::{ButtonVariant, StyledButton};
 use crate::components::styled_field::StyledField;
+use crate::components::styled_icon::{Icon, StyledIcon};
 use crate::components::styled_input::{StyledInput, StyledInputState};
 use crate::components::styled_modal::StyledModal;
 use crate::components::styled_select::{StyledSelect, StyledSelectState};
@@ -42,13 +44,9 @@ pub fn view(model: &Model, modal: &super::Model) -> Node<Msg> {
 
     let tracking = tracking_widget(model, edit_issue_modal);
 
-    let time_spent_field = time_tracking_field(
-        time_tracking_type,
-        FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeSpent)),
-        "Time spent",
-        &edit_issue_modal.time_spent,
-        &edit_issue_modal.time_spent_select,
-    );
+    let worklogs = worklogs_list(model, modal, time_tracking_type);
+    let worklog_form = worklog_form(modal, time_tracking_type);
+
     let time_remaining_field = time_tracking_field(
         time_tracking_type,
         FieldId::EditIssueModal(EditIssueModalSection::Issue(IssueFieldId::TimeRemaining)),
@@ -59,7 +57,6 @@ pub fn view(model: &Model, modal: &super::Model) -> Node<Msg> {
 
     let inputs = div![
         C!["inputs"],
-        div![C!["inputContainer"], time_spent_field],
         div![C!["inputContainer"], time_remaining_field]
     ];
 
@@ -72,13 +69,193 @@ pub fn view(model: &Model, modal: &super::Model) -> Node<Msg> {
 
     StyledModal {
         class_list: "timeTrackingModal",
-        children: vec![modal_title, tracking, inputs, div![C!["actions"], close]],
+        children: vec![
+            modal_title,
+            tracking,
+            worklogs,
+            worklog_form,
+            inputs,
+            div![C!["actions"], close],
+        ],
         width: Some(400),
         ..Default::default()
     }
     .render()
 }
 
+/// Time logged on issue, author can edit or remove own entries
+fn worklogs_list(
+    model: &Model,
+    modal: &super::Model,
+    time_tracking_type: TimeTracking,
+) -> Node<Msg> {
+    let user_id = model.user.as_ref().map(|u| u.id);
+    let rows: Vec<Node<Msg>> = model
+        .worklogs()
+        .iter()
+        .filter(|worklog| worklog.issue_id == modal.issue_id)
+        .map(|worklog| worklog_row(model, worklog, user_id, time_tracking_type))
+        .collect();
+    if rows.is_empty() {
+        return Node::Empty;
+    }
+    div![C!["worklogs"], rows]
+}
+
+fn worklog_row(
+    model: &Model,
+    worklog: &Worklog,
+    user_id: Option<jirs_data::UserId>,
+    time_tracking_type: TimeTracking,
+) -> Node<Msg> {
+    let author = model.users_by_id.get(&worklog.user_id);
+    let avatar = author
+        .map(|user| {
+            StyledAvatar {
+                size: 24,
+                name: &user.name,
+                avatar_url: user.avatar_url.as_deref(),
+                ..StyledAvatar::default()
+            }
+            .render()
+        })
+        .unwrap_or(Node::Empty);
+    let actions = if Some(worklog.user_id) == user_id {
+        let worklog_id = worklog.id;
+        let edit = StyledIcon {
+            icon: Icon::EditAlt,
+            size: Some(14),
+            on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                ev.stop_propagation();
+                Msg::EditWorklog(Some(worklog_id))
+            })),
+            ..Default::default()
+        }
+        .render();
+        let delete = StyledIcon {
+            icon: Icon::Trash,
+            size: Some(14),
+            on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                ev.stop_propagation();
+                Msg::DeleteWorklog(worklog_id)
+            })),
+            ..Default::default()
+        }
+        .render();
+        div![C!["actions"], edit, delete]
+    } else {
+        Node::Empty
+    };
+    div![
+        C!["worklog"],
+        avatar,
+        div![
+            C!["details"],
+            div![
+                C!["header"],
+                span![
+                    C!["author"],
+                    author.map(|user| user.name.as_str()).unwrap_or_default()
+                ],
+                span![
+                    C!["duration"],
+                    value_for_time_tracking(&Some(worklog.duration), &time_tracking_type)
+                ],
+                span![
+                    C!["startedAt"],
+                    worklog.started_at.format("%Y-%m-%d").to_string()
+                ],
+            ],
+            worklog
+                .comment
+                .as_ref()
+                .map(|comment| div![C!["comment"], comment.as_str()])
+                .unwrap_or(Node::Empty),
+        ],
+        actions
+    ]
+}
+
+/// Form for logging time or changing loaded worklog
+fn worklog_form(modal: &super::Model, time_tracking_type: TimeTracking) -> Node<Msg> {
+    if time_tracking_type == TimeTracking::Untracked {
+        return Node::Empty;
+    }
+    let duration = time_tracking_field(
+        time_tracking_type,
+        FieldId::EditIssueModal(EditIssueModalSection::WorklogDuration),
+        "Time spent",
+        &modal.duration,
+        &modal.duration_select,
+    );
+    let started_at = StyledField {
+        label: "Date",
+        input: StyledInput {
+            id: Some(FieldId::EditIssueModal(
+                EditIssueModalSection::WorklogStartedAt,
+            )),
+            value: modal.started_at.value.as_str(),
+            input_type: Some("date"),
+            valid: true,
+            ..Default::default()
+        }
+        .render(),
+        ..Default::default()
+    }
+    .render();
+    let comment = StyledField {
+        label: "Comment",
+        input: StyledInput {
+            id: Some(FieldId::EditIssueModal(
+                EditIssueModalSection::WorklogComment,
+            )),
+            value: modal.comment.value.as_str(),
+            valid: true,
+            ..Default::default()
+        }
+        .render(),
+        ..Default::default()
+    }
+    .render();
+    let submit = StyledButton {
+        variant: ButtonVariant::Primary,
+        text: Some(match modal.worklog_id {
+            Some(_) => "Save",
+            None => "Log time",
+        }),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            Msg::SaveWorklog
+        })),
+        ..Default::default()
+    }
+    .render();
+    let cancel = match modal.worklog_id {
+        Some(_) => StyledButton {
+            variant: ButtonVariant::Empty,
+            text: Some("Cancel"),
+            on_click: Some(mouse_ev(Ev::Click, |ev| {
+                ev.prevent_default();
+                Msg::EditWorklog(None)
+            })),
+            ..Default::default()
+        }
+        .render(),
+        None => Node::Empty,
+    };
+
+    div![
+        C!["worklogForm"],
+        div![
+            C!["inputs"],
+            div![C!["inputContainer"], duration],
+            div![C!["inputContainer"], started_at]
+        ],
+        comment,
+        div![C!["formActions"], submit, cancel]
+    ]
+}
+
 #[inline(always)]
 pub fn time_tracking_field(
     time_tracking_type: TimeTracking,
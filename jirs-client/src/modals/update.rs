@@ -63,6 +63,7 @@ pub fn update(msg: &Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         issue_statuses_delete::update(msg, model, orders);
         epics_edit::update(msg, model, orders);
         epics_delete::update(msg, model, orders);
+        time_tracking::update(msg, model, orders);
     }
 }
 
@@ -197,6 +198,11 @@ fn push_edit_issue_modal(issue_id: EpicId, model: &mut Model, orders: &mut impl
         model.ws.as_ref(),
         orders,
     );
+    send_ws_msg(
+        WsMsg::IssueWatchersLoad(issue_id),
+        model.ws.as_ref(),
+        orders,
+    );
     model
         .modal_stack_mut()
         .push(ModalType::EditIssue(Some(issue_id)));
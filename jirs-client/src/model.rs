@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::components::styled_select::StyledSelectState;
+use crate::pages::backlog_page::model::BacklogPage;
 use crate::pages::invite_page::InvitePage;
 use crate::pages::profile_page::model::ProfilePage;
 use crate::pages::project_page::model::ProjectPage;
@@ -84,6 +85,7 @@ pub enum Page {
     Users,
     Profile,
     Reports,
+    Backlog,
 }
 
 impl Page {
@@ -102,6 +104,7 @@ impl Page {
             Page::Users => "/users".to_string(),
             Page::Profile => "/profile".to_string(),
             Page::Reports => "/reports".to_string(),
+            Page::Backlog => "/backlog".to_string(),
         }
     }
 
@@ -118,6 +121,7 @@ impl Page {
             Page::Invite => PageContent::Invite(Box::new(InvitePage::default())),
             Page::Users => PageContent::Users(Box::new(UsersPage::default())),
             Page::Reports => PageContent::Reports(Box::new(ReportsPage::default())),
+            Page::Backlog => PageContent::Backlog(Box::new(BacklogPage::default())),
             // for those which requires additional data
             _ => PageContent::Project(Box::new(ProjectPage::default())),
         }
@@ -190,6 +194,7 @@ pub enum PageContent {
     Users(Box<UsersPage>),
     Profile(Box<ProfilePage>),
     Reports(Box<ReportsPage>),
+    Backlog(Box<BacklogPage>),
 }
 
 #[derive(Debug)]
@@ -256,6 +261,25 @@ pub struct Model {
     pub epics: Vec<Epic>,
     pub epics_by_id: HashMap<EpicId, Epic>,
 
+    // sprints
+    pub sprints: Vec<Sprint>,
+
+    // issue links
+    pub issue_links: Vec<IssueLink>,
+
+    // labels
+    pub labels: Vec<Label>,
+
+    // custom fields
+    pub custom_fields: Vec<CustomField>,
+    pub issue_custom_values: Vec<IssueCustomValue>,
+
+    // worklogs
+    pub worklogs: Vec<Worklog>,
+
+    // workflow
+    pub workflow_transitions: Vec<WorkflowTransition>,
+
     pub key_triggers: std::rc::Rc<std::cell::RefCell<HashMap<char, Box<dyn BuildMsg>>>>,
     pub distinct_key_up: crate::shared::on_event::Distinct,
 
@@ -297,6 +321,13 @@ impl Model {
             issues_by_id: Default::default(),
             show_extras: false,
             epics_by_id: Default::default(),
+            sprints: vec![],
+            issue_links: vec![],
+            labels: vec![],
+            custom_fields: vec![],
+            issue_custom_values: vec![],
+            worklogs: vec![],
+            workflow_transitions: vec![],
             modals_stack: vec![],
             modals: Default::default(),
             key_triggers: std::rc::Rc::new(std::cell::RefCell::new(HashMap::with_capacity(20))),
@@ -324,6 +355,69 @@ impl Model {
         &self.epics
     }
 
+    #[inline(always)]
+    pub fn sprints(&self) -> &[Sprint] {
+        &self.sprints
+    }
+
+    #[inline(always)]
+    pub fn issue_links(&self) -> &[IssueLink] {
+        &self.issue_links
+    }
+
+    #[inline(always)]
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    #[inline(always)]
+    pub fn worklogs(&self) -> &[Worklog] {
+        &self.worklogs
+    }
+
+    #[inline(always)]
+    pub fn custom_fields(&self) -> &[CustomField] {
+        &self.custom_fields
+    }
+
+    /// Value of custom field set on issue, `None` if not set
+    pub fn issue_custom_value(
+        &self,
+        issue_id: IssueId,
+        field: &CustomField,
+    ) -> Option<CustomValue> {
+        self.issue_custom_values
+            .iter()
+            .find(|value| value.issue_id == issue_id && value.custom_field_id == field.id)
+            .and_then(|value| value.value(field.field_type))
+    }
+
+    /// Issue is blocked while any issue blocking it is not in the last column
+    pub fn is_blocked(&self, issue_id: IssueId) -> bool {
+        let done_status_id = self
+            .issue_statuses
+            .iter()
+            .max_by_key(|status| status.position)
+            .map(|status| status.id);
+        self.issue_links.iter().any(|link| {
+            link.link_type == IssueLinkType::Blocks
+                && link.target_id == issue_id
+                && self
+                    .issues_by_id
+                    .get(&link.source_id)
+                    .map(|issue| Some(issue.issue_status_id) != done_status_id)
+                    .unwrap_or_default()
+        })
+    }
+
+    #[inline(always)]
+    pub fn board_grouping(&self) -> BoardGrouping {
+        self.user_settings
+            .as_ref()
+            .map(|setting| setting.board_grouping)
+            .unwrap_or_default()
+    }
+
     #[inline(always)]
     pub fn user(&self) -> &Option<User> {
         &self.user
@@ -0,0 +1,7 @@
+pub use model::*;
+pub use update::*;
+pub use view::*;
+
+pub mod model;
+pub mod update;
+pub mod view;
@@ -0,0 +1,5 @@
+#[derive(Debug, Default)]
+pub struct BacklogPage {
+    pub sprint_name: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,98 @@
+use jirs_data::{IssueFieldId, PayloadVariant, SprintId, SprintState, WsMsg};
+use seed::prelude::*;
+
+use crate::changes::{BacklogPageChange, PageChanged};
+use crate::model::{Model, Page, PageContent};
+use crate::pages::backlog_page::model::BacklogPage;
+use crate::ws::{board_load, send_ws_msg};
+use crate::{FieldId, Msg, OperationKind, ResourceKind};
+
+/// Length of sprint started from backlog
+pub const SPRINT_DAYS: i64 = 14;
+
+pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Orders<Msg>) {
+    match msg {
+        Msg::ChangePage(Page::Backlog) => build_page_content(model),
+        Msg::ResourceChanged(ResourceKind::Auth, OperationKind::SingleLoaded, _)
+            if model.page == Page::Backlog =>
+        {
+            build_page_content(model);
+        }
+        _ => {}
+    };
+
+    let page = match &mut model.page_content {
+        PageContent::Backlog(page) => page,
+        _ => return,
+    };
+
+    if model.user.is_none() {
+        return;
+    }
+    match msg {
+        Msg::UserChanged(Some(..))
+        | Msg::ResourceChanged(ResourceKind::Auth, OperationKind::SingleLoaded, _)
+        | Msg::ChangePage(Page::Backlog) => {
+            board_load(model, orders);
+        }
+        Msg::ResourceChanged(ResourceKind::Sprint, ..) => {
+            page.error = None;
+        }
+        Msg::StrInputChanged(FieldId::SprintName, text) => {
+            page.sprint_name = text;
+        }
+        Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::CreateSprint)) => {
+            let name = page.sprint_name.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            page.sprint_name.clear();
+            send_ws_msg(WsMsg::SprintCreate(name, None), model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::StartSprint(sprint_id))) => {
+            let starts_at = chrono::Utc::now().naive_utc();
+            let ends_at = starts_at + chrono::Duration::days(SPRINT_DAYS);
+            send_ws_msg(
+                WsMsg::SprintStart(sprint_id, starts_at, ends_at),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::CompleteSprint(sprint_id))) => {
+            send_ws_msg(
+                WsMsg::SprintComplete(sprint_id, next_sprint_id(model, sprint_id)),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::MoveIssue(
+            issue_id,
+            sprint_id,
+        ))) => {
+            send_ws_msg(
+                WsMsg::IssueUpdate(
+                    issue_id,
+                    IssueFieldId::Sprint,
+                    PayloadVariant::OptionI32(sprint_id),
+                ),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Unfinished issues of completed sprint goes to first planned sprint or to
+/// backlog if there is none
+pub fn next_sprint_id(model: &Model, completed: SprintId) -> Option<SprintId> {
+    model
+        .sprints()
+        .iter()
+        .find(|s| s.id != completed && s.state == SprintState::Future)
+        .map(|s| s.id)
+}
+
+pub fn build_page_content(model: &mut Model) {
+    model.page_content = PageContent::Backlog(Box::new(BacklogPage::default()))
+}
@@ -0,0 +1,222 @@
+use jirs_data::{Issue, Sprint, SprintId, SprintState};
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_button::{ButtonVariant, StyledButton};
+use crate::components::styled_icon::StyledIcon;
+use crate::components::styled_input::{InputVariant, StyledInput};
+use crate::model::{Model, Page, PageContent};
+use crate::pages::backlog_page::model::BacklogPage;
+use crate::shared::inner_layout;
+use crate::{BacklogPageChange, FieldId, Msg, PageChanged};
+
+pub fn view(model: &Model) -> Node<Msg> {
+    let page = match &model.page_content {
+        PageContent::Backlog(page) => page,
+        _ => return empty![],
+    };
+
+    let open_sprints: Vec<&Sprint> = model
+        .sprints()
+        .iter()
+        .filter(|s| s.state != SprintState::Completed)
+        .collect();
+    let has_active = open_sprints.iter().any(|s| s.state == SprintState::Active);
+
+    let sprints: Vec<Node<Msg>> = open_sprints
+        .iter()
+        .map(|sprint| sprint_section(model, sprint, &open_sprints, has_active))
+        .collect();
+
+    let backlog_issues: Vec<Node<Msg>> = model
+        .issues()
+        .iter()
+        .filter(|issue| {
+            issue
+                .sprint_id
+                .map(|id| !open_sprints.iter().any(|s| s.id == id))
+                .unwrap_or(true)
+        })
+        .map(|issue| issue_row(issue, &open_sprints))
+        .collect();
+
+    let error = match page.error.as_deref() {
+        Some(e) => div![C!["error"], e],
+        _ => empty![],
+    };
+
+    let body = section![
+        C!["top"],
+        h1![C!["header"], "Backlog"],
+        error,
+        create_sprint_form(page),
+        sprints,
+        div![
+            C!["sprint", "backlog"],
+            div![
+                C!["sprintHeader"],
+                span![C!["sprintName"], "Backlog"],
+                span![C!["issueCount"], format!("{} issues", backlog_issues.len())],
+            ],
+            ul![C!["issues"], backlog_issues],
+        ],
+    ];
+
+    inner_layout(model, "backlog", &[body])
+}
+
+fn create_sprint_form(page: &BacklogPage) -> Node<Msg> {
+    let input = StyledInput {
+        id: Some(FieldId::SprintName),
+        valid: true,
+        value: page.sprint_name.as_str(),
+        variant: InputVariant::Normal,
+        ..Default::default()
+    }
+    .render();
+    let submit = StyledButton {
+        text: Some("Create sprint"),
+        variant: ButtonVariant::Primary,
+        ..Default::default()
+    }
+    .render();
+
+    form![
+        C!["createSprint"],
+        ev(Ev::Submit, |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::CreateSprint))
+        }),
+        input,
+        submit,
+    ]
+}
+
+fn sprint_section(
+    model: &Model,
+    sprint: &Sprint,
+    open_sprints: &[&Sprint],
+    has_active: bool,
+) -> Node<Msg> {
+    let sprint_id = sprint.id;
+    let issues: Vec<&Issue> = model
+        .issues()
+        .iter()
+        .filter(|issue| issue.sprint_id == Some(sprint_id))
+        .collect();
+
+    let action = match sprint.state {
+        SprintState::Future => StyledButton {
+            text: Some("Start sprint"),
+            variant: ButtonVariant::Secondary,
+            disabled: has_active,
+            on_click: Some(mouse_ev(Ev::Click, move |_| {
+                Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::StartSprint(
+                    sprint_id,
+                )))
+            })),
+            ..Default::default()
+        }
+        .render(),
+        SprintState::Active => StyledButton {
+            text: Some("Complete sprint"),
+            variant: ButtonVariant::Primary,
+            on_click: Some(mouse_ev(Ev::Click, move |_| {
+                Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::CompleteSprint(
+                    sprint_id,
+                )))
+            })),
+            ..Default::default()
+        }
+        .render(),
+        SprintState::Completed => empty![],
+    };
+
+    let dates = match (sprint.starts_at, sprint.ends_at) {
+        (Some(starts_at), Some(ends_at)) => span![
+            C!["dates"],
+            format!(
+                "{} - {}",
+                starts_at.format("%d/%m/%Y"),
+                ends_at.format("%d/%m/%Y")
+            )
+        ],
+        _ => empty![],
+    };
+
+    // active sprint issues are managed on board
+    let list = if sprint.state == SprintState::Active {
+        empty![]
+    } else {
+        ul![
+            C!["issues"],
+            issues
+                .iter()
+                .map(|issue| issue_row(issue, open_sprints))
+                .collect::<Vec<Node<Msg>>>()
+        ]
+    };
+
+    div![
+        C![
+            "sprint",
+            IF![sprint.state == SprintState::Active => "active"]
+        ],
+        div![
+            C!["sprintHeader"],
+            span![C!["sprintName"], sprint.name.as_str()],
+            dates,
+            span![C!["issueCount"], format!("{} issues", issues.len())],
+            action,
+        ],
+        list,
+    ]
+}
+
+fn issue_row(issue: &Issue, open_sprints: &[&Sprint]) -> Node<Msg> {
+    let issue_id = issue.id;
+    let targets: Vec<(Option<SprintId>, &str)> = vec![(None, "Backlog")]
+        .into_iter()
+        .chain(open_sprints.iter().map(|s| (Some(s.id), s.name.as_str())))
+        .collect();
+    let options: Vec<Node<Msg>> = targets
+        .iter()
+        .map(|(id, name)| {
+            let value = id.map(|n| n.to_string()).unwrap_or_default();
+            option![
+                attrs![At::Value => value],
+                IF![*id == issue.sprint_id => attrs![At::Selected => true.as_at_value()]],
+                *name
+            ]
+        })
+        .collect();
+
+    let move_to = select![
+        C!["moveTo"],
+        options,
+        input_ev(Ev::Change, move |value| {
+            Msg::PageChanged(PageChanged::Backlog(BacklogPageChange::MoveIssue(
+                issue_id,
+                value.parse().ok(),
+            )))
+        }),
+    ];
+
+    let icon = StyledIcon {
+        icon: issue.issue_type.into(),
+        class_list: issue.issue_type.to_label(),
+        ..Default::default()
+    }
+    .render();
+
+    li![
+        C!["issue"],
+        icon,
+        a![
+            C!["title"],
+            attrs![At::Href => Page::EditIssue(issue_id).to_path()],
+            issue.title.as_str()
+        ],
+        move_to,
+    ]
+}
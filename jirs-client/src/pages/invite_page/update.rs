@@ -6,7 +6,7 @@ use seed::prelude::*;
 
 use crate::model::{Model, Page, PageContent};
 use crate::pages::invite_page::InvitePage;
-use crate::shared::write_auth_token;
+use crate::shared::{write_auth_token, write_refresh_token};
 use crate::ws::send_ws_msg;
 use crate::{
     authorize_or_redirect, FieldId, InvitationPageChange, Msg, PageChanged, WebSocketChanged,
@@ -29,7 +29,8 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             WsMsg::InvitationAcceptFailure(_) => {
                 page.error = Some("Invalid token".to_string());
             }
-            WsMsg::InvitationAcceptSuccess(token) => {
+            WsMsg::InvitationAcceptSuccess(token, refresh_token) => {
+                let _ = write_refresh_token(Some(refresh_token));
                 if let Ok(Msg::AuthTokenStored) = write_auth_token(Some(token)) {
                     authorize_or_redirect(model, orders);
                 }
@@ -1,3 +1,4 @@
+pub mod backlog_page;
 pub mod invite_page;
 pub mod profile_page;
 pub mod project_page;
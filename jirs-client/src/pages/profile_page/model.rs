@@ -1,4 +1,7 @@
-use jirs_data::{ProjectId, TextEditorMode, User, UsersFieldId};
+use jirs_data::{
+    AuthMethods, CredentialsState, NotificationMode, ProjectId, Session, TextEditorMode, User,
+    UsersFieldId,
+};
 
 use crate::components::styled_checkbox::StyledCheckboxState;
 use crate::components::styled_image_input::StyledImageInputState;
@@ -13,10 +16,25 @@ pub struct ProfilePage {
     pub avatar: StyledImageInputState,
     pub current_project: StyledSelectState,
     pub text_editor_mode: StyledCheckboxState,
+    pub notification_mode: StyledCheckboxState,
+    pub sessions: Vec<Session>,
+    pub password: StyledInputState,
+    pub current_password: StyledInputState,
+    pub totp_code: StyledInputState,
+    pub methods: AuthMethods,
+    pub credentials: CredentialsState,
+    /// Secret and link of not yet confirmed authenticator application
+    pub totp_setup: Option<(String, String)>,
+    pub security_error: String,
 }
 
 impl ProfilePage {
-    pub fn new(user: &User, mode: TextEditorMode, project_ids: Vec<ProjectId>) -> Self {
+    pub fn new(
+        user: &User,
+        mode: TextEditorMode,
+        notification_mode: NotificationMode,
+        project_ids: Vec<ProjectId>,
+    ) -> Self {
         Self {
             name: StyledInputState::new(
                 FieldId::Profile(UsersFieldId::Username),
@@ -38,6 +56,21 @@ impl ProfilePage {
                 FieldId::Profile(UsersFieldId::TextEditorMode),
                 mode.into(),
             ),
+            notification_mode: StyledCheckboxState::new(
+                FieldId::Profile(UsersFieldId::NotificationMode),
+                notification_mode.into(),
+            ),
+            sessions: vec![],
+            password: StyledInputState::new(FieldId::Profile(UsersFieldId::Password), ""),
+            current_password: StyledInputState::new(
+                FieldId::Profile(UsersFieldId::CurrentPassword),
+                "",
+            ),
+            totp_code: StyledInputState::new(FieldId::Profile(UsersFieldId::TotpCode), ""),
+            methods: AuthMethods::default(),
+            credentials: CredentialsState::default(),
+            totp_setup: None,
+            security_error: String::new(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use jirs_data::msg::WsError;
 use jirs_data::{ProjectId, User, UsersFieldId, WsMsg};
 use seed::prelude::{Method, Orders, Request};
 use web_sys::FormData;
@@ -17,6 +18,9 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
         | Msg::ChangePage(Page::Profile) => {
             board_load(model, orders);
             build_page_content(model);
+            send_ws_msg(WsMsg::SessionsLoad, model.ws.as_ref(), orders);
+            send_ws_msg(WsMsg::AuthMethodsLoad, model.ws.as_ref(), orders);
+            send_ws_msg(WsMsg::CredentialsLoad, model.ws.as_ref(), orders);
         }
         _ => (),
     }
@@ -27,7 +31,11 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
     profile_page.email.update(&msg);
     profile_page.avatar.update(&msg);
     profile_page.text_editor_mode.update(&msg);
+    profile_page.notification_mode.update(&msg);
     profile_page.current_project.update(&msg, orders);
+    profile_page.password.update(&msg);
+    profile_page.current_password.update(&msg);
+    profile_page.totp_code.update(&msg);
 
     match msg {
         Msg::ResourceChanged(ResourceKind::UserSetting, OperationKind::SingleModified, _) => {
@@ -37,6 +45,12 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 .map(|us| us.text_editor_mode)
                 .unwrap_or_default()
                 .into();
+            profile_page.notification_mode.value = model
+                .user_settings
+                .as_ref()
+                .map(|us| us.notification_mode)
+                .unwrap_or_default()
+                .into();
         }
         Msg::FileInputChanged(FieldId::Profile(UsersFieldId::Avatar), ..) => {
             let file = match profile_page.avatar.file.as_ref() {
@@ -64,6 +78,61 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 }
             }
         }
+        Msg::WebSocketChange(WebSocketChanged::WsMsg(WsMsg::SessionsLoaded(sessions))) => {
+            profile_page.sessions = sessions;
+        }
+        Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SignOutEverywhere)) => {
+            send_ws_msg(WsMsg::SessionsRevokeAll, model.ws.as_ref(), orders);
+        }
+        Msg::WebSocketChange(WebSocketChanged::WsMsg(WsMsg::AuthMethodsLoaded(methods))) => {
+            profile_page.methods = methods;
+        }
+        Msg::WebSocketChange(WebSocketChanged::WsMsg(WsMsg::CredentialsLoaded(credentials))) => {
+            profile_page.credentials = credentials;
+            profile_page.totp_setup = None;
+            profile_page.password.value.clear();
+            profile_page.current_password.value.clear();
+            profile_page.totp_code.value.clear();
+            profile_page.security_error.clear();
+        }
+        Msg::WebSocketChange(WebSocketChanged::WsMsg(WsMsg::TotpSetup(secret, url))) => {
+            profile_page.totp_setup = Some((secret, url));
+        }
+        Msg::WebSocketChange(WebSocketChanged::WsMsg(WsMsg::Error(e)))
+            if matches!(
+                e,
+                WsError::PasswordTooShort
+                    | WsError::InvalidCredentials
+                    | WsError::InvalidTotpCode
+                    | WsError::AuthMethodDisabled
+                    | WsError::TooManyRequests
+            ) =>
+        {
+            profile_page.security_error = e.to_str().to_string();
+        }
+        Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SubmitPassword)) => {
+            let current_password = Some(profile_page.current_password.value.clone())
+                .filter(|s| profile_page.credentials.password && !s.is_empty());
+            let code = Some(profile_page.totp_code.value.trim().to_string())
+                .filter(|s| profile_page.credentials.totp && !s.is_empty());
+            send_ws_msg(
+                WsMsg::PasswordUpdate(profile_page.password.value.clone(), current_password, code),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::PageChanged(PageChanged::Profile(ProfilePageChange::StartTotpSetup)) => {
+            send_ws_msg(WsMsg::TotpSetupRequest, model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SubmitTotpCode)) => {
+            let code = profile_page.totp_code.value.trim().to_string();
+            let msg = if profile_page.credentials.totp {
+                WsMsg::TotpDisable(code)
+            } else {
+                WsMsg::TotpEnable(code)
+            };
+            send_ws_msg(msg, model.ws.as_ref(), orders);
+        }
         Msg::ProjectChanged(Some(project)) => {
             profile_page.current_project.values = vec![project.id as u32];
         }
@@ -74,6 +143,13 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 orders,
             );
         }
+        Msg::U32InputChanged(FieldId::Profile(UsersFieldId::NotificationMode), v) => {
+            send_ws_msg(
+                WsMsg::UserSettingSetNotificationMode(v.into()),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
         Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SubmitForm)) => {
             send_ws_msg(
                 WsMsg::ProfileUpdate(
@@ -114,6 +190,11 @@ fn build_page_content(model: &mut Model) {
         .as_ref()
         .map(|us| us.text_editor_mode)
         .unwrap_or_default();
+    let notification_mode = model
+        .user_settings
+        .as_ref()
+        .map(|us| us.notification_mode)
+        .unwrap_or_default();
     let project_ids = model
         .project
         .as_ref()
@@ -122,6 +203,7 @@ fn build_page_content(model: &mut Model) {
     model.page_content = PageContent::Profile(Box::new(ProfilePage::new(
         user,
         text_editor_mode,
+        notification_mode,
         project_ids,
     )));
 }
@@ -86,8 +86,11 @@ pub fn view(model: &Model) -> Node<Msg> {
             username_field,
             email_field,
             editor_mode_select(page),
+            notification_mode_select(page),
             current_project,
             submit_field,
+            security_section(page),
+            sessions_section(page),
         ],
     }
     .render();
@@ -146,6 +149,192 @@ fn build_current_project(model: &Model, page: &ProfilePage) -> Node<Msg> {
     .render()
 }
 
+/// Password and two factor authentication, visible only if server allows
+/// password sign in
+#[inline(always)]
+fn security_section(page: &ProfilePage) -> Node<Msg> {
+    if !page.methods.password {
+        return empty![];
+    }
+    let password = StyledInput {
+        id: Some(FieldId::Profile(UsersFieldId::Password)),
+        valid: true,
+        value: page.password.value.as_str(),
+        input_type: Some("password"),
+        variant: InputVariant::Primary,
+        ..Default::default()
+    }
+    .render();
+    // code from two factor section can be used instead of current password
+    let current_password_field = if page.credentials.password {
+        let input = StyledInput {
+            id: Some(FieldId::Profile(UsersFieldId::CurrentPassword)),
+            valid: true,
+            value: page.current_password.value.as_str(),
+            input_type: Some("password"),
+            variant: InputVariant::Primary,
+            ..Default::default()
+        }
+        .render();
+        StyledField {
+            label: "Current password",
+            tip: Some("Required to change password, authentication code can be used instead"),
+            input,
+            ..Default::default()
+        }
+        .render()
+    } else {
+        empty![]
+    };
+    let submit_password = StyledButton {
+        variant: ButtonVariant::Primary,
+        text: Some(if page.credentials.password {
+            "Change password"
+        } else {
+            "Set password"
+        }),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SubmitPassword))
+        })),
+        ..Default::default()
+    }
+    .render();
+    let password_field = StyledField {
+        label: "Password",
+        input: div![C!["passwordSection"], password, submit_password],
+        ..Default::default()
+    }
+    .render();
+
+    let two_factor_field = if page.methods.totp && page.credentials.password {
+        two_factor_section(page)
+    } else {
+        empty![]
+    };
+    let error = if page.security_error.is_empty() {
+        empty![]
+    } else {
+        div![C!["error"], page.security_error.as_str()]
+    };
+
+    section![
+        C!["securitySection"],
+        current_password_field,
+        password_field,
+        two_factor_field,
+        error
+    ]
+}
+
+#[inline(always)]
+fn two_factor_section(page: &ProfilePage) -> Node<Msg> {
+    let code = StyledInput {
+        id: Some(FieldId::Profile(UsersFieldId::TotpCode)),
+        valid: true,
+        value: page.totp_code.value.as_str(),
+        variant: InputVariant::Primary,
+        ..Default::default()
+    }
+    .render();
+    let submit_code = |text, variant| {
+        StyledButton {
+            variant,
+            text: Some(text),
+            on_click: Some(mouse_ev(Ev::Click, |ev| {
+                ev.prevent_default();
+                Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SubmitTotpCode))
+            })),
+            ..Default::default()
+        }
+        .render()
+    };
+
+    let input = match (page.credentials.totp, page.totp_setup.as_ref()) {
+        (true, _) => div![
+            C!["totpSection"],
+            span![C!["status"], "Enabled"],
+            code,
+            submit_code("Disable", ButtonVariant::Danger)
+        ],
+        (false, Some((secret, url))) => div![
+            C!["totpSection"],
+            a![
+                C!["setup"],
+                attrs![At::Href => url],
+                format!("Add {} to authenticator application", secret)
+            ],
+            code,
+            submit_code("Confirm", ButtonVariant::Primary)
+        ],
+        (false, None) => StyledButton {
+            variant: ButtonVariant::Secondary,
+            text: Some("Enable two factor authentication"),
+            on_click: Some(mouse_ev(Ev::Click, |ev| {
+                ev.prevent_default();
+                Msg::PageChanged(PageChanged::Profile(ProfilePageChange::StartTotpSetup))
+            })),
+            ..Default::default()
+        }
+        .render(),
+    };
+    StyledField {
+        label: "Two factor authentication",
+        tip: Some("Code from authenticator application is required after password"),
+        input,
+        ..Default::default()
+    }
+    .render()
+}
+
+/// List of signed in devices with possibility to sign out all of them
+#[inline(always)]
+fn sessions_section(page: &ProfilePage) -> Node<Msg> {
+    let sessions: Vec<Node<Msg>> = page
+        .sessions
+        .iter()
+        .map(|session| {
+            div![
+                C!["session", IF![session.current => "current"]],
+                span![
+                    C!["signedIn"],
+                    format!("Signed in {}", session.created_at.format("%Y-%m-%d %H:%M"))
+                ],
+                span![
+                    C!["lastUsed"],
+                    format!("Last used {}", session.updated_at.format("%Y-%m-%d %H:%M"))
+                ],
+                span![
+                    C!["expires"],
+                    format!("Expires {}", session.expires_at.format("%Y-%m-%d %H:%M"))
+                ],
+                IF![session.current => span![C!["currentMarker"], "This device"]]
+            ]
+        })
+        .collect();
+    let sign_out = StyledButton {
+        variant: ButtonVariant::Danger,
+        text: Some("Sign out everywhere"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::Profile(ProfilePageChange::SignOutEverywhere))
+        })),
+        ..Default::default()
+    }
+    .render();
+    StyledField {
+        label: "Active sessions",
+        tip: Some("Devices signed in to your account"),
+        input: section![
+            C!["sessionsSection"],
+            div![C!["sessions"], sessions],
+            sign_out
+        ],
+        class_list: "sessionsField",
+    }
+    .render()
+}
+
 #[inline(always)]
 fn project_select_option<'l>(project: &'l Project) -> StyledSelectOption<'l> {
     StyledSelectOption {
@@ -198,3 +387,43 @@ fn editor_mode_checkbox_option<'l>(
         ..Default::default()
     }
 }
+
+#[inline(always)]
+fn notification_mode_select(page: &ProfilePage) -> Node<Msg> {
+    let modes = StyledCheckbox {
+        options: Some(
+            NotificationMode::default()
+                .into_iter()
+                .map(|mode| notification_mode_checkbox_option(mode, &page.notification_mode)),
+        ),
+        class_list: "notificationMode",
+    }
+    .render();
+    StyledField {
+        input: modes,
+        label: "E-mail notifications",
+        tip: Some("Assignments, mentions and comments on issues you are watching"),
+        ..Default::default()
+    }
+    .render()
+}
+
+#[inline(always)]
+fn notification_mode_checkbox_option<'l>(
+    mode: NotificationMode,
+    state: &StyledCheckboxState,
+) -> ChildBuilder<'l> {
+    let value: u32 = mode.into();
+    ChildBuilder {
+        field_id: state.field_id.clone(),
+        selected: state.value == value,
+        label: match mode {
+            NotificationMode::Immediate => "Send each notification immediately",
+            NotificationMode::DailyDigest => "Send all notifications once a day",
+            NotificationMode::Off => "Don't send notifications",
+        },
+        class_list: mode.to_str(),
+        value,
+        ..Default::default()
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use chrono::NaiveDateTime;
 use jirs_data::*;
 
+use crate::model::Model;
 use crate::shared::drag::DragState;
 
 #[derive(Default, Debug)]
@@ -12,40 +13,119 @@ pub struct StatusIssueIds {
     pub issue_ids: Vec<IssueId>,
 }
 
-#[derive(Default, Debug)]
-pub struct EpicIssuePerStatus {
-    pub epic_ref: Option<(EpicId, EpicName, Option<StartsAt>, Option<EndsAt>)>,
+/// Swimlane of project board, each issue belongs to exactly one lane
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoardLane {
+    Epic(Option<EpicId>),
+    Assignee(Option<UserId>),
+    Priority(IssuePriority),
+    IssueType(IssueType),
+}
+
+impl BoardLane {
+    /// Lane of issue, issue with many assignees is in lane of first one
+    pub fn of(grouping: BoardGrouping, issue: &Issue) -> Self {
+        match grouping {
+            BoardGrouping::Epic => BoardLane::Epic(issue.epic_id),
+            BoardGrouping::Assignee => BoardLane::Assignee(issue.user_ids.first().copied()),
+            BoardGrouping::Priority => BoardLane::Priority(issue.priority),
+            BoardGrouping::IssueType => BoardLane::IssueType(issue.issue_type),
+        }
+    }
+
+    /// Change issue field so it belongs to lane. Moving to assignee lane
+    /// replaces all assignees.
+    pub fn apply(self, issue: &mut Issue) {
+        match self {
+            BoardLane::Epic(epic_id) => issue.epic_id = epic_id,
+            BoardLane::Assignee(user_id) => issue.user_ids = user_id.into_iter().collect(),
+            BoardLane::Priority(priority) => issue.priority = priority,
+            BoardLane::IssueType(issue_type) => issue.issue_type = issue_type,
+        }
+    }
+
+    /// Update message for lane field. Epic is send with list positions.
+    pub fn into_update(self, issue_id: IssueId) -> Option<WsMsg> {
+        let (field_id, payload) = match self {
+            BoardLane::Epic(_) => return None,
+            BoardLane::Assignee(user_id) => (
+                IssueFieldId::Assignees,
+                PayloadVariant::VecI32(user_id.into_iter().collect()),
+            ),
+            BoardLane::Priority(priority) => (
+                IssueFieldId::Priority,
+                PayloadVariant::IssuePriority(priority),
+            ),
+            BoardLane::IssueType(issue_type) => {
+                (IssueFieldId::Type, PayloadVariant::IssueType(issue_type))
+            }
+        };
+        Some(WsMsg::IssueUpdate(issue_id, field_id, payload))
+    }
+}
+
+#[derive(Debug)]
+pub struct LaneIssuePerStatus {
+    pub lane: BoardLane,
     pub per_status_issues: Vec<StatusIssueIds>,
 }
 
 #[derive(Debug, Default)]
 pub struct ProjectPage {
     pub text_filter: String,
+    pub search_issue_ids: Option<Vec<IssueId>>,
+    pub search_error: Option<String>,
+    pub transition_error: Option<String>,
     pub active_avatar_filters: Vec<UserId>,
+    pub active_label_filters: Vec<LabelId>,
     pub only_my_filter: bool,
     pub recently_updated_filter: bool,
     pub issue_drag: DragState,
-    pub visible_issues: Vec<EpicIssuePerStatus>,
+    pub visible_issues: Vec<LaneIssuePerStatus>,
+    /// Issues moved to other lane during current drag
+    pub lane_changes: HashMap<IssueId, BoardLane>,
 }
 
 impl ProjectPage {
-    pub fn visible_issues(
-        page: &ProjectPage,
-        epics: &[Epic],
-        statuses: &[IssueStatus],
-        issues: &[Issue],
-        user: &Option<User>,
-    ) -> Vec<EpicIssuePerStatus> {
-        let epics = vec![None].into_iter().chain(
-            epics
-                .iter()
-                .map(|epic| Some((epic.id, epic.name.as_str(), epic.starts_at, epic.ends_at))),
-        );
-
-        let statuses = statuses.iter().map(|s| (s.id, s.name.as_str()));
-        let issues = issues.iter().filter(|issue| {
-            issue_filter_with_avatars(issue, &page.active_avatar_filters)
-                && issue_filter_with_text(issue, page.text_filter.as_str())
+    pub fn visible_issues(page: &ProjectPage, model: &Model) -> Vec<LaneIssuePerStatus> {
+        let grouping = model.board_grouping();
+        let user = model.user();
+        let lanes: Vec<BoardLane> = match grouping {
+            BoardGrouping::Epic => std::iter::once(BoardLane::Epic(None))
+                .chain(
+                    model
+                        .epics()
+                        .iter()
+                        .map(|epic| BoardLane::Epic(Some(epic.id))),
+                )
+                .collect(),
+            BoardGrouping::Assignee => std::iter::once(BoardLane::Assignee(None))
+                .chain(model.users.iter().map(|u| BoardLane::Assignee(Some(u.id))))
+                .collect(),
+            BoardGrouping::Priority => IssuePriority::default()
+                .into_iter()
+                .map(BoardLane::Priority)
+                .collect(),
+            BoardGrouping::IssueType => IssueType::default()
+                .into_iter()
+                .map(BoardLane::IssueType)
+                .collect(),
+        };
+
+        let statuses = model
+            .issue_statuses()
+            .iter()
+            .map(|s| (s.id, s.name.as_str()));
+        let active_sprint_id = model
+            .sprints()
+            .iter()
+            .find(|s| s.state == SprintState::Active)
+            .map(|s| s.id);
+        let issues = model.issues().iter().filter(|issue| {
+            issue_filter_with_sprint(issue, active_sprint_id)
+                && issue_filter_with_avatars(issue, &page.active_avatar_filters)
+                && issue_filter_with_labels(issue, &page.active_label_filters)
+                && issue_filter_with_search(issue, &page.search_issue_ids)
                 && issue_filter_with_only_my(issue, page.only_my_filter, user)
         });
         let issues = if page.recently_updated_filter {
@@ -68,37 +148,30 @@ impl ProjectPage {
             issues.collect()
         };
 
-        let issues_per_epic_id = issues.into_iter().fold(HashMap::new(), |mut m, issue| {
-            m.entry(issue.epic_id).or_insert_with(Vec::new).push(issue);
-            m
-        });
-
-        epics
-            .map(|epic| {
-                let mut per_epic_map = EpicIssuePerStatus {
-                    epic_ref: epic.map(|(id, name, starts_at, ends_at)| {
-                        (id, name.to_string(), starts_at, ends_at)
-                    }),
-                    ..Default::default()
-                };
-
-                for (current_status_id, issue_status_name) in statuses.to_owned() {
-                    let per_status_map = StatusIssueIds {
+        lanes
+            .into_iter()
+            .map(|lane| {
+                let lane_issues: Vec<&Issue> = issues
+                    .iter()
+                    .filter(|issue| BoardLane::of(grouping, issue) == lane)
+                    .copied()
+                    .collect();
+                let per_status_issues = statuses
+                    .clone()
+                    .map(|(current_status_id, issue_status_name)| StatusIssueIds {
                         status_id: current_status_id,
                         status_name: issue_status_name.to_string(),
-                        issue_ids: issues_per_epic_id
-                            .get(&epic.map(|(id, ..)| id))
-                            .map(|v| {
-                                v.iter()
-                                    .filter(|issue| issue_filter_status(issue, current_status_id))
-                                    .map(|issue| issue.id)
-                                    .collect()
-                            })
-                            .unwrap_or_default(),
-                    };
-                    per_epic_map.per_status_issues.push(per_status_map);
+                        issue_ids: lane_issues
+                            .iter()
+                            .filter(|issue| issue_filter_status(issue, current_status_id))
+                            .map(|issue| issue.id)
+                            .collect(),
+                    })
+                    .collect();
+                LaneIssuePerStatus {
+                    lane,
+                    per_status_issues,
                 }
-                per_epic_map
             })
             .collect()
     }
@@ -112,14 +185,30 @@ fn issue_filter_with_avatars(issue: &Issue, user_ids: &[UserId]) -> bool {
     user_ids.contains(&issue.reporter_id) || issue.user_ids.iter().any(|id| user_ids.contains(id))
 }
 
+#[inline]
+fn issue_filter_with_labels(issue: &Issue, label_ids: &[LabelId]) -> bool {
+    label_ids.is_empty() || issue.label_ids.iter().any(|id| label_ids.contains(id))
+}
+
 #[inline]
 fn issue_filter_status(issue: &Issue, current_status_id: IssueStatusId) -> bool {
     issue.issue_status_id == current_status_id
 }
 
 #[inline]
-fn issue_filter_with_text(issue: &Issue, text: &str) -> bool {
-    text.is_empty() || issue.title.contains(text)
+fn issue_filter_with_search(issue: &Issue, search_issue_ids: &Option<Vec<IssueId>>) -> bool {
+    search_issue_ids
+        .as_ref()
+        .map(|ids| ids.contains(&issue.id))
+        .unwrap_or(true)
+}
+
+/// When sprint is running board shows only issues planned for it
+#[inline]
+fn issue_filter_with_sprint(issue: &Issue, active_sprint_id: Option<SprintId>) -> bool {
+    active_sprint_id
+        .map(|id| issue.sprint_id == Some(id))
+        .unwrap_or(true)
 }
 
 #[inline]
@@ -43,7 +43,11 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 ResourceKind::Issue
                 | ResourceKind::Project
                 | ResourceKind::IssueStatus
-                | ResourceKind::Epic,
+                | ResourceKind::Epic
+                | ResourceKind::Sprint
+                | ResourceKind::Label
+                | ResourceKind::User
+                | ResourceKind::UserSetting,
                 ..,
             ) => {
                 rebuild_visible = true;
@@ -57,8 +61,14 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 }
             }
             Msg::StrInputChanged(FieldId::TextFilterBoard, text) => {
-                project_page.text_filter = text;
-                rebuild_visible = true;
+                project_page.text_filter = text.clone();
+                if text.trim().is_empty() {
+                    project_page.search_issue_ids = None;
+                    project_page.search_error = None;
+                    rebuild_visible = true;
+                } else {
+                    send_ws_msg(WsMsg::IssueSearch(text), model.ws.as_ref(), orders);
+                }
             }
             Msg::ProjectAvatarFilterChanged(user_id, active) => {
                 if active {
@@ -72,6 +82,16 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
                 }
                 rebuild_visible = true;
             }
+            Msg::ProjectLabelFilterChanged(label_id, active) => {
+                if active {
+                    project_page
+                        .active_label_filters
+                        .retain(|id| *id != label_id);
+                } else {
+                    project_page.active_label_filters.push(label_id);
+                }
+                rebuild_visible = true;
+            }
             Msg::ProjectToggleOnlyMy => {
                 project_page.only_my_filter = !project_page.only_my_filter;
                 rebuild_visible = true;
@@ -82,11 +102,13 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
             }
             Msg::ProjectClearFilters => {
                 project_page.active_avatar_filters = vec![];
+                project_page.active_label_filters = vec![];
                 project_page.recently_updated_filter = false;
                 project_page.only_my_filter = false;
                 rebuild_visible = true;
             }
             Msg::PageChanged(PageChanged::Board(BoardPageChange::IssueDragStarted(issue_id))) => {
+                project_page.transition_error = None;
                 crate::ws::issue::drag_started(issue_id, model)
             }
             Msg::PageChanged(PageChanged::Board(BoardPageChange::IssueDragStopped(_))) => {
@@ -95,12 +117,20 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
             Msg::PageChanged(PageChanged::Board(BoardPageChange::ChangePosition(
                 issue_bellow_id,
             ))) => crate::ws::issue::change_position(issue_bellow_id, model),
-            Msg::PageChanged(PageChanged::Board(BoardPageChange::IssueDragOverStatus(status))) => {
-                crate::ws::issue::change_status(status, model)
-            }
+            Msg::PageChanged(PageChanged::Board(BoardPageChange::IssueDragOverStatus(
+                status,
+                lane,
+            ))) => crate::ws::issue::change_status(status, lane, model),
             Msg::PageChanged(PageChanged::Board(BoardPageChange::IssueDropZone(_status))) => {
                 crate::ws::issue::sync(model, orders)
             }
+            Msg::PageChanged(PageChanged::Board(BoardPageChange::GroupingChanged(grouping))) => {
+                send_ws_msg(
+                    WsMsg::UserSettingSetBoardGrouping(grouping),
+                    model.ws.as_ref(),
+                    orders,
+                );
+            }
             Msg::PageChanged(PageChanged::Board(BoardPageChange::DragLeave(_id))) => {
                 project_page.issue_drag.clear_last();
             }
@@ -115,13 +145,7 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
         }
     }
     if rebuild_visible {
-        let visible_issues = ProjectPage::visible_issues(
-            crate::match_page!(model, Project),
-            model.epics(),
-            model.issue_statuses(),
-            model.issues(),
-            model.user(),
-        );
+        let visible_issues = ProjectPage::visible_issues(crate::match_page!(model, Project), model);
         crate::match_page_mut!(model, Project).visible_issues = visible_issues;
     }
 }
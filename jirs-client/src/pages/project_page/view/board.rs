@@ -5,7 +5,9 @@ use seed::*;
 use crate::components::styled_avatar::*;
 use crate::components::styled_button::{ButtonVariant, StyledButton};
 use crate::components::styled_icon::*;
+use crate::components::styled_label_chip::StyledLabelChip;
 use crate::model::PageContent;
+use crate::pages::project_page::model::BoardLane;
 use crate::{match_page, BoardPageChange, Model, Msg, Page, PageChanged};
 
 #[inline(always)]
@@ -13,8 +15,8 @@ pub fn project_board_lists(model: &Model) -> Node<Msg> {
     let project_page = match_page!(model, Project; Empty);
 
     let now = chrono::Utc::now().naive_utc();
-    let rows = project_page.visible_issues.iter().map(|per_epic| {
-        let columns: Vec<Node<Msg>> = per_epic
+    let rows = project_page.visible_issues.iter().map(|per_lane| {
+        let columns: Vec<Node<Msg>> = per_lane
             .per_status_issues
             .iter()
             .map(|per_status| {
@@ -26,14 +28,18 @@ pub fn project_board_lists(model: &Model) -> Node<Msg> {
                 project_issue_list(
                     model,
                     per_status.status_id,
+                    per_lane.lane,
                     &per_status.status_name,
                     issues.as_slice(),
                 )
             })
             .collect();
-        let epic_name = match per_epic.epic_ref.as_ref() {
-            Some((id, name, starts_at, ends_at)) => {
-                let id = *id;
+        let lane_header = match per_lane.lane {
+            BoardLane::Epic(Some(id)) => {
+                let (name, starts_at, ends_at) = match model.epics_by_id.get(&id) {
+                    Some(epic) => (epic.name.as_str(), epic.starts_at, epic.ends_at),
+                    _ => return empty![],
+                };
                 let edit_button = StyledButton {
                     variant: ButtonVariant::Empty,
                     icon: Some(StyledIcon::from(Icon::EditAlt).render()),
@@ -87,9 +93,29 @@ pub fn project_board_lists(model: &Model) -> Node<Msg> {
                     div![C!["epicActions"], edit_button, delete_button],
                 ]
             }
-            _ => Node::Empty,
+            BoardLane::Epic(None) => Node::Empty,
+            BoardLane::Assignee(user_id) => {
+                let name = user_id
+                    .and_then(|id| model.users_by_id.get(&id))
+                    .map(|user| user.name.as_str())
+                    .unwrap_or("Unassigned");
+                div![C!["laneHeader"], div![C!["laneName"], name]]
+            }
+            BoardLane::Priority(priority) => {
+                div![C!["laneHeader"], div![C!["laneName"], priority.to_label()]]
+            }
+            BoardLane::IssueType(issue_type) => {
+                div![
+                    C!["laneHeader"],
+                    div![C!["laneName"], issue_type.to_label()]
+                ]
+            }
         };
-        div![C!["row"], epic_name, div![C!["projectBoardLists"], columns]]
+        div![
+            C!["row"],
+            lane_header,
+            div![C!["projectBoardLists"], columns]
+        ]
     });
     div![C!["rows"], rows]
 }
@@ -98,6 +124,7 @@ pub fn project_board_lists(model: &Model) -> Node<Msg> {
 fn project_issue_list(
     model: &Model,
     status_id: IssueStatusId,
+    lane: BoardLane,
     status_name: &str,
     issues: &[&Issue],
 ) -> Node<Msg> {
@@ -105,6 +132,26 @@ fn project_issue_list(
         .iter()
         .map(|issue| ProjectIssue { model, issue }.render())
         .collect();
+    // limit applies to whole project, not only to visible issues
+    let wip = model
+        .issue_statuses
+        .iter()
+        .find(|is| is.id == status_id)
+        .and_then(|status| {
+            let count = model
+                .issues()
+                .iter()
+                .filter(|issue| issue.issue_status_id == status_id)
+                .count();
+            status
+                .wip_limit
+                .map(|limit| (count, limit, status.is_over_wip_limit(count)))
+        });
+    let issues_count = match wip {
+        Some((count, limit, _)) => div![C!["issuesCount"], format!("{} / {}", count, limit)],
+        None => div![C!["issuesCount"]],
+    };
+    let over_limit = matches!(wip, Some((_, _, true)));
     let drop_handler = {
         let send_status = status_id;
         drag_ev(Ev::Drop, move |ev| {
@@ -122,14 +169,14 @@ fn project_issue_list(
             ev.prevent_default();
             ev.stop_propagation();
             Some(Msg::PageChanged(PageChanged::Board(
-                BoardPageChange::IssueDragOverStatus(send_status),
+                BoardPageChange::IssueDragOverStatus(send_status, lane),
             )))
         })
     };
 
     div![
-        C!["list"],
-        div![C!["title"], status_name, div![C!["issuesCount"]]],
+        C!["list", IF![over_limit => "overWipLimit"]],
+        div![C!["title"], status_name, issues_count],
         div![
             C!["issues"],
             attrs![At::DropZone => "link"],
@@ -168,6 +215,14 @@ impl<'l> ProjectIssue<'l> {
             })
             .collect();
 
+        let labels: Vec<Node<Msg>> = self
+            .model
+            .labels()
+            .iter()
+            .filter(|label| self.issue.label_ids.contains(&label.id))
+            .map(|label| StyledLabelChip::new(label).render())
+            .collect();
+
         let issue_type_icon = StyledIcon {
             icon: self.issue.issue_type.into(),
             class_list: self.issue.issue_type.to_str(),
@@ -220,6 +275,8 @@ impl<'l> ProjectIssue<'l> {
             Msg::ChangePage(Page::EditIssue(issue_id))
         });
 
+        let blocked = self.model.is_blocked(issue_id);
+
         a![
             drag_started,
             on_click,
@@ -227,16 +284,18 @@ impl<'l> ProjectIssue<'l> {
             attrs![At::Href => format!("/issues/{id}", id = issue_id)],
             IF![is_dragging => div![C!["dragCover"], drag_over_handler]],
             div![
-                C!["issue"],
+                C!["issue", IF![blocked => "blocked"]],
                 attrs![At::Draggable => true],
                 drag_stopped,
                 drag_out,
                 p![C!["title"], self.issue.title.as_str()],
+                IF![!labels.is_empty() => div![C!["labels"], labels]],
                 div![
                     C!["bottom"],
                     div![
                         div![C!["issueTypeIcon"], issue_type_icon],
-                        div![C!["issuePriorityIcon"], priority_icon]
+                        div![C!["issuePriorityIcon"], priority_icon],
+                        IF![blocked => span![C!["blockedLabel"], "Blocked"]],
                     ],
                     div![C!["assignees"], avatars,],
                 ]
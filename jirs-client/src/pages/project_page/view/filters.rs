@@ -1,3 +1,4 @@
+use jirs_data::BoardGrouping;
 use seed::prelude::*;
 use seed::*;
 
@@ -5,8 +6,9 @@ use crate::components::styled_avatar::*;
 use crate::components::styled_button::*;
 use crate::components::styled_icon::*;
 use crate::components::styled_input::*;
+use crate::components::styled_label_chip::StyledLabelChip;
 use crate::model::PageContent;
-use crate::{FieldId, Model, Msg};
+use crate::{BoardPageChange, FieldId, Model, Msg, PageChanged};
 
 pub fn project_board_filters(model: &Model) -> Node<Msg> {
     let project_page = match &model.page_content {
@@ -42,9 +44,39 @@ pub fn project_board_filters(model: &Model) -> Node<Msg> {
     }
     .render();
 
+    let current = model.board_grouping();
+    let grouping_buttons: Vec<Node<Msg>> = BoardGrouping::default()
+        .into_iter()
+        .map(|grouping| {
+            StyledButton {
+                variant: ButtonVariant::Empty,
+                active: grouping == current,
+                text: Some(match grouping {
+                    BoardGrouping::Epic => "Epic",
+                    BoardGrouping::Assignee => "Assignee",
+                    BoardGrouping::Priority => "Priority",
+                    BoardGrouping::IssueType => "Type",
+                }),
+                on_click: Some(mouse_ev(Ev::Click, move |_| {
+                    Msg::PageChanged(PageChanged::Board(BoardPageChange::GroupingChanged(
+                        grouping,
+                    )))
+                })),
+                ..Default::default()
+            }
+            .render()
+        })
+        .collect();
+    let grouping = div![
+        C!["filterChild", "grouping"],
+        span![C!["label"], "Group by"],
+        grouping_buttons
+    ];
+
     let clear_all = if project_page.only_my_filter
         || project_page.recently_updated_filter
         || !project_page.active_avatar_filters.is_empty()
+        || !project_page.active_label_filters.is_empty()
     {
         seed::button![
             id!["clearAllFilters"],
@@ -56,12 +88,25 @@ pub fn project_board_filters(model: &Model) -> Node<Msg> {
         empty![]
     };
 
+    let search_error = match project_page.search_error.as_deref() {
+        Some(error) => div![id!["searchError"], C!["filterChild"], error],
+        None => empty![],
+    };
+    let transition_error = match project_page.transition_error.as_deref() {
+        Some(error) => div![id!["transitionError"], C!["filterChild"], error],
+        None => empty![],
+    };
+
     div![
         id!["projectBoardFilters"],
         search_input,
+        search_error,
+        transition_error,
         avatars_filters(model),
+        labels_filters(model),
         only_my,
         recently_updated,
+        grouping,
         clear_all
     ]
 }
@@ -99,3 +144,32 @@ pub fn avatars_filters(model: &Model) -> Node<Msg> {
 
     div![id!["avatars"], C!["filterChild"], avatars]
 }
+
+pub fn labels_filters(model: &Model) -> Node<Msg> {
+    let project_page = match &model.page_content {
+        PageContent::Project(project_page) => project_page,
+        _ => return empty![],
+    };
+    if model.labels().is_empty() {
+        return empty![];
+    }
+    let active_label_filters = &project_page.active_label_filters;
+    let labels: Vec<Node<Msg>> = model
+        .labels()
+        .iter()
+        .map(|label| {
+            let label_id = label.id;
+            let active = active_label_filters.contains(&label_id);
+            StyledLabelChip {
+                active,
+                on_click: Some(mouse_ev(Ev::Click, move |_| {
+                    Msg::ProjectLabelFilterChanged(label_id, active)
+                })),
+                ..StyledLabelChip::new(label)
+            }
+            .render()
+        })
+        .collect();
+
+    div![id!["labelFilters"], C!["filterChild"], labels]
+}
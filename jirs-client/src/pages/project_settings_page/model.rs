@@ -1,4 +1,9 @@
-use jirs_data::{IssueStatusId, Project, ProjectFieldId, TextEditorMode, UpdateProjectPayload};
+use std::collections::HashMap;
+
+use jirs_data::{
+    CustomFieldType, IssueStatus, IssueStatusId, Project, ProjectFieldId, TextEditorMode,
+    UpdateProjectPayload, UserRole,
+};
 
 use crate::components::styled_checkbox::StyledCheckboxState;
 use crate::components::styled_editor::StyledEditorState;
@@ -7,6 +12,8 @@ use crate::components::styled_select::StyledSelectState;
 use crate::shared::drag::DragState;
 use crate::FieldId;
 
+pub static DEFAULT_LABEL_COLOR: &str = "#4fade6";
+
 #[derive(Debug)]
 pub struct ProjectSettingsPage {
     pub payload: UpdateProjectPayload,
@@ -17,6 +24,19 @@ pub struct ProjectSettingsPage {
     pub creating_issue_status: bool,
     pub name: StyledInputState,
     pub description: StyledEditorState,
+    pub label_name: StyledInputState,
+    pub label_color: StyledInputState,
+    pub custom_field_name: StyledInputState,
+    pub custom_field_type: StyledSelectState,
+    pub custom_field_options: StyledInputState,
+    pub transition_from: StyledSelectState,
+    pub transition_to: StyledSelectState,
+    pub transition_role: StyledSelectState,
+    pub transition_require_assignee: bool,
+    pub transition_require_estimate: bool,
+    pub transition_error: Option<String>,
+    /// Edited but not saved work in progress limits and strict flags
+    pub wip_limits: HashMap<IssueStatusId, (String, bool)>,
 }
 
 impl ProjectSettingsPage {
@@ -60,6 +80,42 @@ impl ProjectSettingsPage {
                 "",
                 "",
             ),
+            label_name: StyledInputState::new(
+                FieldId::ProjectSettings(ProjectFieldId::LabelName),
+                "",
+            ),
+            label_color: StyledInputState::new(
+                FieldId::ProjectSettings(ProjectFieldId::LabelColor),
+                DEFAULT_LABEL_COLOR,
+            ),
+            custom_field_name: StyledInputState::new(
+                FieldId::ProjectSettings(ProjectFieldId::CustomFieldName),
+                "",
+            ),
+            custom_field_type: StyledSelectState::new(
+                FieldId::ProjectSettings(ProjectFieldId::CustomFieldType),
+                vec![CustomFieldType::default().into()],
+            ),
+            custom_field_options: StyledInputState::new(
+                FieldId::ProjectSettings(ProjectFieldId::CustomFieldOptions),
+                "",
+            ),
+            transition_from: StyledSelectState::new(
+                FieldId::ProjectSettings(ProjectFieldId::TransitionFrom),
+                vec![],
+            ),
+            transition_to: StyledSelectState::new(
+                FieldId::ProjectSettings(ProjectFieldId::TransitionTo),
+                vec![],
+            ),
+            transition_role: StyledSelectState::new(
+                FieldId::ProjectSettings(ProjectFieldId::TransitionRole),
+                vec![],
+            ),
+            transition_require_assignee: false,
+            transition_require_estimate: false,
+            transition_error: None,
+            wip_limits: HashMap::new(),
         }
     }
 
@@ -68,4 +124,50 @@ impl ProjectSettingsPage {
         self.name.reset();
         self.creating_issue_status = false;
     }
+
+    pub fn reset_label_form(&mut self) {
+        self.label_name.reset();
+        self.label_color.value = DEFAULT_LABEL_COLOR.to_string();
+    }
+
+    pub fn reset_custom_field_form(&mut self) {
+        self.custom_field_name.reset();
+        self.custom_field_type.reset();
+        self.custom_field_type.values = vec![CustomFieldType::default().into()];
+        self.custom_field_options.reset();
+    }
+
+    pub fn reset_transition_form(&mut self) {
+        self.transition_from.reset();
+        self.transition_to.reset();
+        self.transition_role.reset();
+        self.transition_require_assignee = false;
+        self.transition_require_estimate = false;
+        self.transition_error = None;
+    }
+
+    /// Role required by new transition, `None` allows any member
+    pub fn transition_role(&self) -> Option<UserRole> {
+        self.transition_role.values.get(0).cloned().map(Into::into)
+    }
+
+    /// Work in progress limit of status with unsaved changes
+    pub fn wip_limit(&self, status: &IssueStatus) -> (String, bool) {
+        self.wip_limits.get(&status.id).cloned().unwrap_or_else(|| {
+            (
+                status.wip_limit.map(|n| n.to_string()).unwrap_or_default(),
+                status.wip_strict,
+            )
+        })
+    }
+
+    /// Selected type of new custom field
+    pub fn custom_field_type(&self) -> CustomFieldType {
+        self.custom_field_type
+            .values
+            .get(0)
+            .cloned()
+            .map(Into::into)
+            .unwrap_or_default()
+    }
 }
@@ -8,7 +8,10 @@ use crate::components::styled_select::StyledSelectChanged;
 use crate::model::{Model, Page, PageContent};
 use crate::pages::project_settings_page::ProjectSettingsPage;
 use crate::ws::{board_load, send_ws_msg};
-use crate::{match_page_mut, FieldId, Msg, PageChanged, ProjectPageChange, WebSocketChanged};
+use crate::{
+    match_page_mut, FieldId, Msg, OperationKind, PageChanged, ProjectPageChange, ResourceKind,
+    WebSocketChanged,
+};
 
 pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     if model.page != Page::ProjectSettings {
@@ -32,8 +35,28 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     _ => (),
                 };
             }
+            WebSocketChanged::WsMsg(WsMsg::IssueStatusUpdated(status)) => {
+                if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                    page.wip_limits.remove(&status.id);
+                }
+            }
             _ => (),
         },
+        Msg::ResourceChanged(ResourceKind::Label, OperationKind::SingleCreated, _) => {
+            if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                page.reset_label_form();
+            }
+        }
+        Msg::ResourceChanged(ResourceKind::CustomField, OperationKind::SingleCreated, _) => {
+            if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                page.reset_custom_field_form();
+            }
+        }
+        Msg::ResourceChanged(ResourceKind::WorkflowTransition, OperationKind::SingleCreated, _) => {
+            if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                page.reset_transition_form();
+            }
+        }
         Msg::ChangePage(Page::ProjectSettings) => {
             build_page_content(model);
             if model.user.is_some() {
@@ -65,6 +88,14 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     page.time_tracking.update(&msg);
     page.name.update(&msg);
     page.description.update(&msg, orders);
+    page.label_name.update(&msg);
+    page.label_color.update(&msg);
+    page.custom_field_name.update(&msg);
+    page.custom_field_type.update(&msg, orders);
+    page.custom_field_options.update(&msg);
+    page.transition_from.update(&msg, orders);
+    page.transition_to.update(&msg, orders);
+    page.transition_role.update(&msg, orders);
 
     match msg {
         Msg::StrInputChanged(FieldId::ProjectSettings(ProjectFieldId::Name), text) => {
@@ -160,6 +191,130 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             let ws_msg = WsMsg::IssueStatusCreate(name, position as i32);
             send_ws_msg(ws_msg, model.ws.as_ref(), orders);
         }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::SubmitLabelForm)) => {
+            let name = page.label_name.value.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let ws_msg = WsMsg::LabelCreate(name, page.label_color.value.clone());
+            send_ws_msg(ws_msg, model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::DeleteLabel(
+            label_id,
+        ))) => {
+            send_ws_msg(WsMsg::LabelDelete(label_id), model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(
+            ProjectPageChange::SubmitCustomFieldForm,
+        )) => {
+            let name = page.custom_field_name.value.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let options = page
+                .custom_field_options
+                .value
+                .split(',')
+                .map(|option| option.trim().to_string())
+                .filter(|option| !option.is_empty())
+                .collect();
+            let ws_msg = WsMsg::CustomFieldCreate(name, page.custom_field_type(), options);
+            send_ws_msg(ws_msg, model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::DeleteCustomField(
+            custom_field_id,
+        ))) => {
+            send_ws_msg(
+                WsMsg::CustomFieldDelete(custom_field_id),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::StrInputChanged(
+            FieldId::ProjectSettings(ProjectFieldId::WipLimit(issue_status_id)),
+            text,
+        ) => {
+            if let Some(status) = model
+                .issue_statuses
+                .iter()
+                .find(|is| is.id == issue_status_id)
+            {
+                let (_, strict) = page.wip_limit(status);
+                page.wip_limits.insert(issue_status_id, (text, strict));
+            }
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::ToggleWipStrict(
+            issue_status_id,
+        ))) => {
+            if let Some(status) = model
+                .issue_statuses
+                .iter()
+                .find(|is| is.id == issue_status_id)
+            {
+                let (limit, strict) = page.wip_limit(status);
+                page.wip_limits.insert(issue_status_id, (limit, !strict));
+            }
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::SubmitWipLimit(
+            issue_status_id,
+        ))) => {
+            let (limit, strict) = match model
+                .issue_statuses
+                .iter()
+                .find(|is| is.id == issue_status_id)
+            {
+                Some(status) => page.wip_limit(status),
+                _ => return,
+            };
+            let limit = match limit.trim() {
+                "" => None,
+                s => match s.parse::<i32>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return,
+                },
+            };
+            send_ws_msg(
+                WsMsg::IssueStatusWipLimitUpdate(issue_status_id, limit, strict),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(
+            ProjectPageChange::ToggleTransitionRequireAssignee,
+        )) => {
+            page.transition_require_assignee = !page.transition_require_assignee;
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(
+            ProjectPageChange::ToggleTransitionRequireEstimate,
+        )) => {
+            page.transition_require_estimate = !page.transition_require_estimate;
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::SubmitTransitionForm)) => {
+            let (from, to) = match (
+                page.transition_from.values.get(0),
+                page.transition_to.values.get(0),
+            ) {
+                (Some(from), Some(to)) => (*from as IssueStatusId, *to as IssueStatusId),
+                _ => return,
+            };
+            let ws_msg = WsMsg::WorkflowTransitionCreate(
+                from,
+                to,
+                page.transition_role(),
+                page.transition_require_assignee,
+                page.transition_require_estimate,
+            );
+            send_ws_msg(ws_msg, model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::ProjectSettings(ProjectPageChange::DeleteTransition(
+            transition_id,
+        ))) => {
+            send_ws_msg(
+                WsMsg::WorkflowTransitionDelete(transition_id),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
         _ => (),
     }
 }
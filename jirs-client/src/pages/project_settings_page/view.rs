@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use jirs_data::{IssueStatus, ProjectCategory, TimeTracking};
+use jirs_data::{
+    CustomFieldType, IssueStatus, IssueStatusId, ProjectCategory, TimeTracking, UserRole,
+};
 use seed::prelude::*;
 use seed::*;
 
@@ -11,7 +13,8 @@ use crate::components::styled_field::StyledField;
 use crate::components::styled_form::StyledForm;
 use crate::components::styled_icon::{Icon, StyledIcon};
 use crate::components::styled_input::{InputVariant, StyledInput};
-use crate::components::styled_select::{SelectVariant, StyledSelect};
+use crate::components::styled_label_chip::StyledLabelChip;
+use crate::components::styled_select::{SelectVariant, StyledSelect, StyledSelectState};
 use crate::components::styled_select_child::StyledSelectOption;
 use crate::components::styled_textarea::StyledTextarea;
 use crate::model::{self, ModalType, Model, PageContent};
@@ -31,7 +34,7 @@ pub fn view(model: &model::Model) -> Node<Msg> {
 
     let url_field = url_field(page);
 
-    let description_field = render_styled_editor(&page.description);
+    let description_field = render_styled_editor(&page.description, &model.users);
 
     let category_field = category_field(page);
 
@@ -39,6 +42,14 @@ pub fn view(model: &model::Model) -> Node<Msg> {
 
     let columns_field = columns_section(model, page);
 
+    let wip_limits_field = wip_limits_section(model, page);
+
+    let labels_field = labels_section(model, page);
+
+    let custom_fields_field = custom_fields_section(model, page);
+
+    let workflow_field = workflow_section(model, page);
+
     let save_button = StyledButton {
         class_list: "actionButton",
         on_click: Some(mouse_ev(Ev::Click, |ev| {
@@ -62,6 +73,10 @@ pub fn view(model: &model::Model) -> Node<Msg> {
             time_tracking_field,
             save_button,
             columns_field,
+            wip_limits_field,
+            labels_field,
+            custom_fields_field,
+            workflow_field,
         ],
         on_submit: Some(ev(Ev::Submit, |ev| {
             ev.prevent_default();
@@ -243,6 +258,441 @@ fn columns_section(model: &Model, page: &ProjectSettingsPage) -> Node<Msg> {
     .render()
 }
 
+/// Build work in progress limit form for each column
+#[inline(always)]
+fn wip_limits_section(model: &Model, page: &ProjectSettingsPage) -> Node<Msg> {
+    let rows: Vec<Node<Msg>> = model
+        .issue_statuses
+        .iter()
+        .map(|status| {
+            let issue_status_id = status.id;
+            let (limit, strict) = page.wip_limit(status);
+            let valid =
+                limit.trim().is_empty() || matches!(limit.trim().parse::<i32>(), Ok(n) if n > 0);
+            let input = StyledInput {
+                id: Some(FieldId::ProjectSettings(ProjectFieldId::WipLimit(
+                    issue_status_id,
+                ))),
+                value: limit.as_str(),
+                input_type: Some("number"),
+                valid,
+                ..Default::default()
+            }
+            .render();
+            let strict = StyledButton {
+                variant: ButtonVariant::Empty,
+                active: strict,
+                text: Some("Strict"),
+                on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                    ev.prevent_default();
+                    Msg::PageChanged(PageChanged::ProjectSettings(
+                        ProjectPageChange::ToggleWipStrict(issue_status_id),
+                    ))
+                })),
+                ..Default::default()
+            }
+            .render();
+            let save = StyledButton {
+                variant: ButtonVariant::Secondary,
+                text: Some("Save"),
+                disabled: !valid,
+                on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    Msg::PageChanged(PageChanged::ProjectSettings(
+                        ProjectPageChange::SubmitWipLimit(issue_status_id),
+                    ))
+                })),
+                ..Default::default()
+            }
+            .render();
+            div![
+                C!["wipLimit"],
+                span![C!["name"], status.name.as_str()],
+                input,
+                strict,
+                save
+            ]
+        })
+        .collect();
+
+    StyledField {
+        label: "Work in progress limits",
+        tip: Some(
+            "Empty limit means no limit. Strict limit rejects moving issue into full column.",
+        ),
+        input: section![C!["wipLimitsSection"], rows],
+        class_list: "wipLimitsField",
+    }
+    .render()
+}
+
+/// Build project labels list with form for adding new label
+#[inline(always)]
+fn labels_section(model: &Model, page: &ProjectSettingsPage) -> Node<Msg> {
+    let labels: Vec<Node<Msg>> = model
+        .labels()
+        .iter()
+        .map(|label| {
+            let label_id = label.id;
+            let delete = StyledIcon {
+                icon: Icon::Close,
+                size: Some(12),
+                on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    Msg::PageChanged(PageChanged::ProjectSettings(
+                        ProjectPageChange::DeleteLabel(label_id),
+                    ))
+                })),
+                ..Default::default()
+            }
+            .render();
+            StyledLabelChip {
+                children: vec![delete],
+                ..StyledLabelChip::new(label)
+            }
+            .render()
+        })
+        .collect();
+
+    let name = StyledInput {
+        id: Some(FieldId::ProjectSettings(ProjectFieldId::LabelName)),
+        value: page.label_name.value.as_str(),
+        valid: true,
+        ..Default::default()
+    }
+    .render();
+    let color = StyledInput {
+        id: Some(FieldId::ProjectSettings(ProjectFieldId::LabelColor)),
+        value: page.label_color.value.as_str(),
+        input_type: Some("color"),
+        valid: true,
+        ..Default::default()
+    }
+    .render();
+    let submit = StyledButton {
+        variant: ButtonVariant::Secondary,
+        text: Some("Add label"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            ev.stop_propagation();
+            Msg::PageChanged(PageChanged::ProjectSettings(
+                ProjectPageChange::SubmitLabelForm,
+            ))
+        })),
+        ..Default::default()
+    }
+    .render();
+
+    StyledField {
+        label: "Labels",
+        tip: Some("Labels can be attached to issues and used to filter board."),
+        input: section![
+            C!["labelsSection"],
+            div![C!["labels"], labels],
+            div![C!["addLabel"], name, color, submit]
+        ],
+        class_list: "labelsField",
+    }
+    .render()
+}
+
+/// Build project custom fields list with form for adding new field
+#[inline(always)]
+fn custom_fields_section(model: &Model, page: &ProjectSettingsPage) -> Node<Msg> {
+    let fields: Vec<Node<Msg>> = model
+        .custom_fields()
+        .iter()
+        .map(|field| {
+            let custom_field_id = field.id;
+            let delete = StyledIcon {
+                icon: Icon::Close,
+                size: Some(12),
+                on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    Msg::PageChanged(PageChanged::ProjectSettings(
+                        ProjectPageChange::DeleteCustomField(custom_field_id),
+                    ))
+                })),
+                ..Default::default()
+            }
+            .render();
+            div![
+                C!["customField"],
+                span![C!["name"], field.name.as_str()],
+                span![C!["type"], custom_field_type_label(field.field_type)],
+                span![C!["options"], field.options.join(", ")],
+                delete
+            ]
+        })
+        .collect();
+
+    let field_type = page.custom_field_type();
+    let name = StyledInput {
+        id: Some(FieldId::ProjectSettings(ProjectFieldId::CustomFieldName)),
+        value: page.custom_field_name.value.as_str(),
+        valid: true,
+        ..Default::default()
+    }
+    .render();
+    let type_select = StyledSelect {
+        id: FieldId::ProjectSettings(ProjectFieldId::CustomFieldType),
+        opened: page.custom_field_type.opened,
+        text_filter: page.custom_field_type.text_filter.as_str(),
+        valid: true,
+        variant: SelectVariant::Normal,
+        options: Some(
+            CustomFieldType::default()
+                .into_iter()
+                .map(custom_field_type_select_option),
+        ),
+        selected: vec![custom_field_type_select_option(field_type)],
+        ..Default::default()
+    }
+    .render();
+    let options = if field_type.requires_options() {
+        StyledInput {
+            id: Some(FieldId::ProjectSettings(ProjectFieldId::CustomFieldOptions)),
+            value: page.custom_field_options.value.as_str(),
+            valid: true,
+            ..Default::default()
+        }
+        .render()
+    } else {
+        Node::Empty
+    };
+    let submit = StyledButton {
+        variant: ButtonVariant::Secondary,
+        text: Some("Add field"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            ev.stop_propagation();
+            Msg::PageChanged(PageChanged::ProjectSettings(
+                ProjectPageChange::SubmitCustomFieldForm,
+            ))
+        })),
+        ..Default::default()
+    }
+    .render();
+
+    StyledField {
+        label: "Custom fields",
+        tip: Some("Additional issue fields. Options of select fields are separated by comma."),
+        input: section![
+            C!["customFieldsSection"],
+            div![C!["customFields"], fields],
+            div![C!["addCustomField"], name, type_select, options, submit]
+        ],
+        class_list: "customFieldsField",
+    }
+    .render()
+}
+
+fn custom_field_type_label(field_type: CustomFieldType) -> &'static str {
+    match field_type {
+        CustomFieldType::Text => "Text",
+        CustomFieldType::Number => "Number",
+        CustomFieldType::Date => "Date",
+        CustomFieldType::SingleSelect => "Single select",
+        CustomFieldType::MultiSelect => "Multi select",
+        CustomFieldType::User => "User",
+    }
+}
+
+#[inline(always)]
+fn custom_field_type_select_option<'l>(field_type: CustomFieldType) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        text: Some(custom_field_type_label(field_type)),
+        value: field_type.into(),
+        ..Default::default()
+    }
+}
+
+/// Build allowed status transitions list with form for adding new one
+#[inline(always)]
+fn workflow_section(model: &Model, page: &ProjectSettingsPage) -> Node<Msg> {
+    let status_name = |id: IssueStatusId| {
+        model
+            .issue_statuses
+            .iter()
+            .find(|is| is.id == id)
+            .map(|is| is.name.as_str())
+            .unwrap_or_default()
+    };
+    let transitions: Vec<Node<Msg>> = model
+        .workflow_transitions
+        .iter()
+        .map(|transition| {
+            let transition_id = transition.id;
+            let delete = StyledIcon {
+                icon: Icon::Close,
+                size: Some(12),
+                on_click: Some(mouse_ev(Ev::Click, move |ev| {
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    Msg::PageChanged(PageChanged::ProjectSettings(
+                        ProjectPageChange::DeleteTransition(transition_id),
+                    ))
+                })),
+                ..Default::default()
+            }
+            .render();
+            let mut requirements = vec![];
+            if let Some(role) = transition.required_role {
+                requirements.push(role.to_str());
+            }
+            if transition.require_assignee {
+                requirements.push("assignee");
+            }
+            if transition.require_estimate {
+                requirements.push("estimate");
+            }
+            div![
+                C!["transition"],
+                span![C!["from"], status_name(transition.from_status_id)],
+                span![C!["arrow"], "→"],
+                span![C!["to"], status_name(transition.to_status_id)],
+                span![C!["requirements"], requirements.join(", ")],
+                delete
+            ]
+        })
+        .collect();
+
+    let from = transition_status_select(model, &page.transition_from);
+    let to = transition_status_select(model, &page.transition_to);
+    let role = StyledSelect {
+        id: page.transition_role.field_id.clone(),
+        name: "transitionRole",
+        opened: page.transition_role.opened,
+        text_filter: page.transition_role.text_filter.as_str(),
+        valid: true,
+        variant: SelectVariant::Normal,
+        clearable: true,
+        options: Some(
+            UserRole::default()
+                .into_iter()
+                .map(transition_role_select_option),
+        ),
+        selected: page
+            .transition_role()
+            .map(transition_role_select_option)
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }
+    .render();
+    let require_assignee = StyledButton {
+        variant: ButtonVariant::Empty,
+        active: page.transition_require_assignee,
+        text: Some("Assignee"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::ProjectSettings(
+                ProjectPageChange::ToggleTransitionRequireAssignee,
+            ))
+        })),
+        ..Default::default()
+    }
+    .render();
+    let require_estimate = StyledButton {
+        variant: ButtonVariant::Empty,
+        active: page.transition_require_estimate,
+        text: Some("Estimate"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::ProjectSettings(
+                ProjectPageChange::ToggleTransitionRequireEstimate,
+            ))
+        })),
+        ..Default::default()
+    }
+    .render();
+    let submit = StyledButton {
+        variant: ButtonVariant::Secondary,
+        text: Some("Add transition"),
+        on_click: Some(mouse_ev(Ev::Click, |ev| {
+            ev.prevent_default();
+            ev.stop_propagation();
+            Msg::PageChanged(PageChanged::ProjectSettings(
+                ProjectPageChange::SubmitTransitionForm,
+            ))
+        })),
+        ..Default::default()
+    }
+    .render();
+    let error = match page.transition_error.as_deref() {
+        Some(error) => div![C!["error"], error],
+        None => empty![],
+    };
+
+    StyledField {
+        label: "Workflow",
+        tip: Some("Allowed status changes. Project without transitions allows moving issue to any status."),
+        input: section![
+            C!["workflowSection"],
+            div![C!["transitions"], transitions],
+            div![
+                C!["addTransition"],
+                from,
+                to,
+                role,
+                require_assignee,
+                require_estimate,
+                submit
+            ],
+            error
+        ],
+        class_list: "workflowField",
+    }
+    .render()
+}
+
+#[inline(always)]
+fn transition_status_select(model: &Model, state: &StyledSelectState) -> Node<Msg> {
+    let selected = state
+        .values
+        .get(0)
+        .and_then(|id| model.issue_statuses.iter().find(|is| is.id == *id as i32))
+        .map(transition_status_select_option);
+    StyledSelect {
+        id: state.field_id.clone(),
+        name: "transitionStatus",
+        opened: state.opened,
+        text_filter: state.text_filter.as_str(),
+        valid: true,
+        variant: SelectVariant::Normal,
+        options: Some(
+            model
+                .issue_statuses
+                .iter()
+                .map(transition_status_select_option),
+        ),
+        selected: selected.into_iter().collect(),
+        ..Default::default()
+    }
+    .render()
+}
+
+#[inline(always)]
+fn transition_status_select_option<'l>(is: &'l IssueStatus) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        value: is.id as u32,
+        text: Some(is.name.as_str()),
+        ..Default::default()
+    }
+}
+
+#[inline(always)]
+fn transition_role_select_option<'l>(role: UserRole) -> StyledSelectOption<'l> {
+    StyledSelectOption {
+        value: role.into(),
+        text: Some(role.to_str()),
+        ..Default::default()
+    }
+}
+
 #[inline(always)]
 fn add_column(page: &ProjectSettingsPage, column_style: &str) -> Node<Msg> {
     let on_click = mouse_ev(Ev::Click, move |_| {
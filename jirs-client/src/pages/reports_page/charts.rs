@@ -0,0 +1,331 @@
+use chrono::{Duration, NaiveDate};
+use jirs_data::IssueStatus;
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_date_time_input::StyledDateTimeInput;
+use crate::components::styled_field::StyledField;
+use crate::components::styled_select::{SelectVariant, StyledSelect};
+use crate::modals::epic_select_option;
+use crate::model::Model;
+use crate::pages::reports_page::model::ReportsPage;
+use crate::pages::reports_page::view::{
+    SVG_BAR_MARGIN, SVG_DRAWABLE_HEIGHT, SVG_HEIGHT, SVG_MARGIN_X, SVG_WIDTH,
+};
+use crate::Msg;
+
+const LABEL_STYLE: &str =
+    "fill: var(--textLight); font-family: var(--font-regular); font-size: 10px;";
+
+/// Horizontal lines with values from 0 to `dominant`, returns legend width
+fn legend(dominant: i32, svg_parts: &mut Vec<Node<Msg>>) -> f64 {
+    let legend_margin_width = (dominant.max(1) as f64).log10() * SVG_MARGIN_X as f64;
+    let resolution = 10;
+    let mut legend_parts: Vec<Node<Msg>> = vec![];
+    for y in 0..(resolution + 1) {
+        let current = dominant as f64 * (y as f64 / resolution as f64);
+        let line_y = SVG_DRAWABLE_HEIGHT as f64 * (1f64 - y as f64 / resolution as f64);
+        legend_parts.push(seed::text![
+            attrs![
+                At::X => 0,
+                At::Y => line_y + 12f64,
+                At::Style => LABEL_STYLE,
+            ],
+            format!("{:.1}", current),
+        ]);
+        legend_parts.push(seed::rect![attrs![
+            At::X => legend_margin_width + SVG_MARGIN_X as f64,
+            At::Y => line_y,
+            At::Width => SVG_WIDTH as f64 - (legend_margin_width + SVG_MARGIN_X as f64),
+            At::Height => 1,
+            At::Style => "fill: var(--textLight);",
+        ]]);
+    }
+    svg_parts.push(seed::g![legend_parts]);
+    legend_margin_width + SVG_MARGIN_X as f64
+}
+
+pub fn burndown_graph(model: &Model, page: &ReportsPage) -> Node<Msg> {
+    let epic = page
+        .burndown_epic_id
+        .and_then(|id| model.epics.iter().find(|epic| epic.id == id));
+
+    let select = StyledSelect {
+        id: page.burndown_epic_state.field_id.clone(),
+        name: "burndownEpic",
+        selected: epic.map(epic_select_option).into_iter().collect(),
+        options: Some(model.epics.iter().map(epic_select_option)),
+        variant: SelectVariant::Normal,
+        clearable: true,
+        text_filter: page.burndown_epic_state.text_filter.as_str(),
+        opened: page.burndown_epic_state.opened,
+        valid: true,
+        ..Default::default()
+    }
+    .render();
+    let select = StyledField {
+        label: "Epic",
+        input: select,
+        ..Default::default()
+    }
+    .render();
+
+    let (epic, first) = match (epic, page.burndown.first()) {
+        (Some(epic), Some(first)) => (epic, first),
+        _ => {
+            return div![
+                C!["graph", "burndown"],
+                h5![C!["graphHeader"], "Burndown"],
+                select,
+            ];
+        }
+    };
+    // ideal line ends with epic end, even if it is in future
+    let last_day = epic
+        .ends_at
+        .map(|at| at.date())
+        .unwrap_or_else(|| page.burndown.last().map(|p| p.day).unwrap_or(first.day));
+    let days = (last_day - first.day).num_days().max(1);
+    let dominant = page
+        .burndown
+        .iter()
+        .map(|point| point.remaining)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut svg_parts: Vec<Node<Msg>> = vec![];
+    let left = legend(dominant, &mut svg_parts);
+    let day_width = (SVG_WIDTH as f64 - left - SVG_MARGIN_X as f64) / days as f64;
+    let x = |day: NaiveDate| left + (day - first.day).num_days() as f64 * day_width;
+    let y =
+        |remaining: i32| SVG_DRAWABLE_HEIGHT as f64 * (1f64 - remaining as f64 / dominant as f64);
+
+    svg_parts.push(seed::path![attrs![
+        At::D => format!(
+            "M{},{} L{},{}",
+            x(first.day),
+            y(first.remaining),
+            x(last_day),
+            y(0)
+        ),
+        At::Style => "fill: none; stroke: var(--textLight); stroke-dasharray: 4;",
+    ]]);
+    let line = page
+        .burndown
+        .iter()
+        .enumerate()
+        .map(|(idx, point)| {
+            format!(
+                "{}{},{}",
+                if idx == 0 { "M" } else { "L" },
+                x(point.day),
+                y(point.remaining)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    svg_parts.push(seed::path![attrs![
+        At::D => line,
+        At::Style => "fill: none; stroke: var(--primary); stroke-width: 2;",
+    ]]);
+
+    // label every week to keep them readable
+    let mut day = first.day;
+    while day <= last_day {
+        svg_parts.push(seed::text![
+            attrs![At::X => x(day), At::Y => SVG_HEIGHT, At::Style => LABEL_STYLE],
+            day.format("%d/%m").to_string(),
+        ]);
+        day += Duration::weeks(1);
+    }
+
+    div![
+        C!["graph", "burndown"],
+        h5![C!["graphHeader"], "Burndown"],
+        select,
+        svg![
+            attrs![At::Height => SVG_HEIGHT, At::Width => SVG_WIDTH],
+            svg_parts,
+        ],
+    ]
+}
+
+pub fn velocity_graph(page: &ReportsPage) -> Node<Msg> {
+    if page.velocity.is_empty() {
+        return empty![];
+    }
+    let dominant = page
+        .velocity
+        .iter()
+        .map(|point| point.completed)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut svg_parts: Vec<Node<Msg>> = vec![];
+    let left = legend(dominant, &mut svg_parts);
+    let weeks = page.velocity.len() as f64;
+    let bar_width =
+        (SVG_WIDTH as f64 - left - SVG_MARGIN_X as f64 - SVG_BAR_MARGIN as f64 * weeks) / weeks;
+
+    for (idx, point) in page.velocity.iter().enumerate() {
+        let x = left + (bar_width + SVG_BAR_MARGIN as f64) * idx as f64;
+        let height = SVG_DRAWABLE_HEIGHT as f64 * point.completed as f64 / dominant as f64;
+        svg_parts.push(seed::g![
+            seed::rect![attrs![
+                At::X => x,
+                At::Y => SVG_DRAWABLE_HEIGHT as f64 - height,
+                At::Width => bar_width,
+                At::Height => height,
+                At::Style => "fill: var(--primary);",
+                At::Title => format!("Completed estimate: {}", point.completed),
+            ]],
+            seed::text![
+                attrs![At::X => x, At::Y => SVG_HEIGHT, At::Style => LABEL_STYLE],
+                point.week.format("%d/%m").to_string(),
+            ]
+        ]);
+    }
+
+    div![
+        C!["graph", "velocity"],
+        h5![C!["graphHeader"], "Velocity"],
+        svg![
+            attrs![At::Height => SVG_HEIGHT, At::Width => SVG_WIDTH],
+            svg_parts,
+        ],
+    ]
+}
+
+/// Colors of cumulative flow areas, repeated when project has more statuses
+const AREA_COLORS: [&str; 6] = [
+    "#0052cc", "#00875a", "#ff991f", "#6554c0", "#00b8d9", "#de350b",
+];
+
+pub fn cumulative_flow_graph(model: &Model, page: &ReportsPage) -> Node<Msg> {
+    let from = StyledDateTimeInput {
+        field_id: page.cumulative_flow_from_state.field_id.clone(),
+        popup_visible: page.cumulative_flow_from_state.popup_visible,
+        timestamp: page
+            .cumulative_flow_from_state
+            .timestamp
+            .or_else(|| Some(page.cumulative_flow_from.and_hms(0, 0, 0))),
+    }
+    .render();
+    let from = StyledField {
+        input: from,
+        label: "From",
+        ..Default::default()
+    }
+    .render();
+    let to = StyledDateTimeInput {
+        field_id: page.cumulative_flow_to_state.field_id.clone(),
+        popup_visible: page.cumulative_flow_to_state.popup_visible,
+        timestamp: page
+            .cumulative_flow_to_state
+            .timestamp
+            .or_else(|| Some(page.cumulative_flow_to.and_hms(0, 0, 0))),
+    }
+    .render();
+    let to = StyledField {
+        input: to,
+        label: "To",
+        ..Default::default()
+    }
+    .render();
+    let range = div![C!["range"], from, to];
+
+    let (first, last) = match (page.cumulative_flow.first(), page.cumulative_flow.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            return div![
+                C!["graph", "cumulativeFlow"],
+                h5![C!["graphHeader"], "Cumulative flow"],
+                range,
+            ];
+        }
+    };
+
+    // last column is drawn at the bottom
+    let mut statuses: Vec<&IssueStatus> = model.issue_statuses.iter().collect();
+    statuses.sort_by(|a, b| b.position.cmp(&a.position));
+
+    // stacked values, each day has sum of counts from bottom up to status
+    let stacked: Vec<Vec<i32>> = page
+        .cumulative_flow
+        .iter()
+        .map(|point| {
+            let mut sum = 0;
+            statuses
+                .iter()
+                .map(|status| {
+                    sum += point
+                        .counts
+                        .iter()
+                        .find(|(id, _)| *id == status.id)
+                        .map(|(_, n)| *n)
+                        .unwrap_or_default();
+                    sum
+                })
+                .collect()
+        })
+        .collect();
+    let dominant = stacked
+        .iter()
+        .filter_map(|sums| sums.last().copied())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut svg_parts: Vec<Node<Msg>> = vec![];
+    let left = legend(dominant, &mut svg_parts);
+    let days = (last.day - first.day).num_days().max(1);
+    let day_width = (SVG_WIDTH as f64 - left - SVG_MARGIN_X as f64) / days as f64;
+    let x = |day: NaiveDate| left + (day - first.day).num_days() as f64 * day_width;
+    let y = |count: i32| SVG_DRAWABLE_HEIGHT as f64 * (1f64 - count as f64 / dominant as f64);
+
+    let mut legend_items: Vec<Node<Msg>> = vec![];
+    for (idx, status) in statuses.iter().enumerate() {
+        let color = AREA_COLORS[idx % AREA_COLORS.len()];
+        // upper edge from left to right, then lower edge back
+        let mut edges: Vec<String> = vec![];
+        for (point, sums) in page.cumulative_flow.iter().zip(stacked.iter()) {
+            edges.push(format!("{},{}", x(point.day), y(sums[idx])));
+        }
+        for (point, sums) in page.cumulative_flow.iter().zip(stacked.iter()).rev() {
+            let below = if idx == 0 { 0 } else { sums[idx - 1] };
+            edges.push(format!("{},{}", x(point.day), y(below)));
+        }
+        svg_parts.push(seed::path![attrs![
+            At::D => format!("M{} Z", edges.join(" L")),
+            At::Style => format!("fill: {}; fill-opacity: 0.8;", color),
+            At::Title => status.name.as_str(),
+        ]]);
+        legend_items.push(div![
+            C!["legendItem"],
+            span![C!["color"], style![St::BackgroundColor => color]],
+            span![C!["name"], status.name.as_str()],
+        ]);
+    }
+
+    let mut day = first.day;
+    while day <= last.day {
+        svg_parts.push(seed::text![
+            attrs![At::X => x(day), At::Y => SVG_HEIGHT, At::Style => LABEL_STYLE],
+            day.format("%d/%m").to_string(),
+        ]);
+        day += Duration::weeks(1);
+    }
+
+    div![
+        C!["graph", "cumulativeFlow"],
+        h5![C!["graphHeader"], "Cumulative flow"],
+        range,
+        svg![
+            attrs![At::Height => SVG_HEIGHT, At::Width => SVG_WIDTH],
+            svg_parts,
+        ],
+        div![C!["legend"], legend_items],
+    ]
+}
@@ -2,6 +2,8 @@ pub use model::*;
 pub use update::*;
 pub use view::*;
 
+pub mod charts;
 pub mod model;
+pub mod timesheet;
 pub mod update;
 pub mod view;
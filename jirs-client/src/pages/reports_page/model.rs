@@ -1,5 +1,17 @@
 use chrono::prelude::*;
 use chrono::NaiveDate;
+use jirs_data::{BurndownPoint, CumulativeFlowPoint, EpicId, VelocityPoint, WsMsg};
+
+use crate::components::styled_date_time_input::StyledDateTimeInputState;
+use crate::components::styled_select::StyledSelectState;
+use crate::FieldId;
+
+/// Length of single timesheet column
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimesheetGrouping {
+    Day,
+    Week,
+}
 
 #[derive(Debug)]
 pub struct ReportsPage {
@@ -7,6 +19,35 @@ pub struct ReportsPage {
     pub hovered_day: Option<chrono::NaiveDate>,
     pub first_day: NaiveDate,
     pub last_day: NaiveDate,
+
+    // timesheet
+    pub timesheet_from: NaiveDate,
+    pub timesheet_to: NaiveDate,
+    pub timesheet_from_state: StyledDateTimeInputState,
+    pub timesheet_to_state: StyledDateTimeInputState,
+    pub timesheet_grouping: TimesheetGrouping,
+
+    // burndown & velocity
+    pub burndown_epic_id: Option<EpicId>,
+    pub burndown_epic_state: StyledSelectState,
+    pub burndown: Vec<BurndownPoint>,
+    pub velocity: Vec<VelocityPoint>,
+
+    // cumulative flow
+    pub cumulative_flow_from: NaiveDate,
+    pub cumulative_flow_to: NaiveDate,
+    pub cumulative_flow_from_state: StyledDateTimeInputState,
+    pub cumulative_flow_to_state: StyledDateTimeInputState,
+    pub cumulative_flow: Vec<CumulativeFlowPoint>,
+}
+
+impl ReportsPage {
+    pub fn cumulative_flow_load(&self) -> WsMsg {
+        WsMsg::CumulativeFlowLoad(
+            self.cumulative_flow_from.and_hms(0, 0, 0),
+            self.cumulative_flow_to.and_hms(0, 0, 0),
+        )
+    }
 }
 
 impl Default for ReportsPage {
@@ -21,6 +62,32 @@ impl Default for ReportsPage {
             last_day,
             selected_day: None,
             hovered_day: None,
+            timesheet_from: first_day,
+            timesheet_to: last_day,
+            timesheet_from_state: StyledDateTimeInputState::new(
+                FieldId::TimesheetFrom,
+                Some(first_day.and_hms(0, 0, 0)),
+            ),
+            timesheet_to_state: StyledDateTimeInputState::new(
+                FieldId::TimesheetTo,
+                Some(last_day.and_hms(0, 0, 0)),
+            ),
+            timesheet_grouping: TimesheetGrouping::Day,
+            burndown_epic_id: None,
+            burndown_epic_state: StyledSelectState::new(FieldId::BurndownEpic, vec![]),
+            burndown: vec![],
+            velocity: vec![],
+            cumulative_flow_from: first_day,
+            cumulative_flow_to: last_day,
+            cumulative_flow_from_state: StyledDateTimeInputState::new(
+                FieldId::CumulativeFlowFrom,
+                Some(first_day.and_hms(0, 0, 0)),
+            ),
+            cumulative_flow_to_state: StyledDateTimeInputState::new(
+                FieldId::CumulativeFlowTo,
+                Some(last_day.and_hms(0, 0, 0)),
+            ),
+            cumulative_flow: vec![],
         }
     }
 }
@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, Duration, NaiveDate};
+use jirs_data::{TimeTracking, UserId};
+use seed::prelude::*;
+use seed::*;
+
+use crate::components::styled_button::{ButtonVariant, StyledButton};
+use crate::components::styled_date_time_input::StyledDateTimeInput;
+use crate::components::styled_field::StyledField;
+use crate::modals::time_tracking::value_for_time_tracking;
+use crate::model::Model;
+use crate::pages::reports_page::model::{ReportsPage, TimesheetGrouping};
+use crate::{Msg, PageChanged, ReportsPageChange};
+
+/// Time logged by each user within single period
+struct Timesheet {
+    periods: Vec<NaiveDate>,
+    rows: Vec<(String, HashMap<NaiveDate, i32>)>,
+}
+
+impl Timesheet {
+    fn new(model: &Model, page: &ReportsPage) -> Self {
+        let grouping = page.timesheet_grouping;
+        let mut periods = vec![];
+        let mut current = period_start(page.timesheet_from, grouping);
+        while current <= page.timesheet_to {
+            periods.push(current);
+            current = match grouping {
+                TimesheetGrouping::Day => current + Duration::days(1),
+                TimesheetGrouping::Week => current + Duration::weeks(1),
+            };
+        }
+
+        let mut logged: BTreeMap<UserId, HashMap<NaiveDate, i32>> = BTreeMap::new();
+        for worklog in model.worklogs() {
+            let date = worklog.started_at.date();
+            if date < page.timesheet_from || date > page.timesheet_to {
+                continue;
+            }
+            *logged
+                .entry(worklog.user_id)
+                .or_default()
+                .entry(period_start(date, grouping))
+                .or_default() += worklog.duration;
+        }
+
+        let rows = logged
+            .into_iter()
+            .map(|(user_id, cells)| {
+                let name = model
+                    .users_by_id
+                    .get(&user_id)
+                    .map(|user| user.name.clone())
+                    .unwrap_or_else(|| "Unknown".to_string());
+                (name, cells)
+            })
+            .collect();
+
+        Self { periods, rows }
+    }
+
+    fn to_csv(&self, time_tracking: &TimeTracking) -> String {
+        let mut csv = String::from("User");
+        for period in self.periods.iter() {
+            csv.push(',');
+            csv.push_str(period.format("%Y-%m-%d").to_string().as_str());
+        }
+        csv.push_str(",Total\n");
+        for (name, cells) in self.rows.iter() {
+            csv.push_str(format!("\"{}\"", name.replace('"', "\"\"")).as_str());
+            for period in self.periods.iter() {
+                csv.push(',');
+                csv.push_str(
+                    value_for_time_tracking(&cells.get(period).copied(), time_tracking).as_str(),
+                );
+            }
+            csv.push(',');
+            csv.push_str(
+                value_for_time_tracking(&Some(cells.values().sum()), time_tracking).as_str(),
+            );
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// First day of period given date belongs to. Weeks starts on monday
+fn period_start(date: NaiveDate, grouping: TimesheetGrouping) -> NaiveDate {
+    match grouping {
+        TimesheetGrouping::Day => date,
+        TimesheetGrouping::Week => {
+            date - Duration::days(date.weekday().num_days_from_monday() as i64)
+        }
+    }
+}
+
+pub fn timesheet(model: &Model, page: &ReportsPage) -> Node<Msg> {
+    let time_tracking = model
+        .project
+        .as_ref()
+        .map(|p| p.time_tracking)
+        .unwrap_or(TimeTracking::Untracked);
+
+    let from = StyledDateTimeInput {
+        field_id: page.timesheet_from_state.field_id.clone(),
+        popup_visible: page.timesheet_from_state.popup_visible,
+        timestamp: page
+            .timesheet_from_state
+            .timestamp
+            .or_else(|| Some(page.timesheet_from.and_hms(0, 0, 0))),
+    }
+    .render();
+    let from = StyledField {
+        input: from,
+        label: "From",
+        ..Default::default()
+    }
+    .render();
+
+    let to = StyledDateTimeInput {
+        field_id: page.timesheet_to_state.field_id.clone(),
+        popup_visible: page.timesheet_to_state.popup_visible,
+        timestamp: page
+            .timesheet_to_state
+            .timestamp
+            .or_else(|| Some(page.timesheet_to.and_hms(0, 0, 0))),
+    }
+    .render();
+    let to = StyledField {
+        input: to,
+        label: "To",
+        ..Default::default()
+    }
+    .render();
+
+    let day = grouping_button(page, TimesheetGrouping::Day, "Day");
+    let week = grouping_button(page, TimesheetGrouping::Week, "Week");
+
+    let header = div![
+        C!["timesheetHeader"],
+        h5![C!["graphHeader"], "Timesheet"],
+        div![C!["range"], from, to, div![C!["grouping"], day, week]]
+    ];
+
+    if time_tracking == TimeTracking::Untracked {
+        return div![
+            C!["timesheet"],
+            header,
+            div![
+                C!["untracked"],
+                "Time tracking is disabled for this project"
+            ]
+        ];
+    }
+
+    let sheet = Timesheet::new(model, page);
+    let periods: Vec<Node<Msg>> = sheet
+        .periods
+        .iter()
+        .map(|period| th![period.format("%d/%m").to_string()])
+        .collect();
+    let rows: Vec<Node<Msg>> = sheet
+        .rows
+        .iter()
+        .map(|(name, logged)| {
+            let cells: Vec<Node<Msg>> = sheet
+                .periods
+                .iter()
+                .map(|period| {
+                    td![value_for_time_tracking(
+                        &logged.get(period).copied(),
+                        &time_tracking
+                    )]
+                })
+                .collect();
+            let total = value_for_time_tracking(&Some(logged.values().sum()), &time_tracking);
+            tr![td![C!["user"], name], cells, td![C!["total"], total]]
+        })
+        .collect();
+
+    let href = format!(
+        "data:text/csv;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(
+            sheet.to_csv(&time_tracking).as_str()
+        ))
+    );
+    let download = a![
+        C!["download"],
+        attrs![At::Href => href, At::Download => "timesheet.csv"],
+        "Download CSV"
+    ];
+
+    div![
+        C!["timesheet"],
+        header,
+        div![
+            C!["sheet"],
+            table![
+                thead![tr![
+                    th![C!["user"], "User"],
+                    periods,
+                    th![C!["total"], "Total"]
+                ]],
+                tbody![rows]
+            ]
+        ],
+        download
+    ]
+}
+
+fn grouping_button(page: &ReportsPage, grouping: TimesheetGrouping, text: &str) -> Node<Msg> {
+    StyledButton {
+        variant: ButtonVariant::Empty,
+        active: page.timesheet_grouping == grouping,
+        text: Some(text),
+        on_click: Some(mouse_ev(Ev::Click, move |ev| {
+            ev.prevent_default();
+            Msg::PageChanged(PageChanged::Reports(
+                ReportsPageChange::TimesheetGroupingChanged(grouping),
+            ))
+        })),
+        ..Default::default()
+    }
+    .render()
+}
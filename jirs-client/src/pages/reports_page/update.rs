@@ -1,10 +1,13 @@
+use jirs_data::{EpicId, WsMsg};
 use seed::prelude::*;
 
 use crate::changes::{PageChanged, ReportsPageChange};
+use crate::components::styled_date_time_input::StyledDateTimeChanged;
+use crate::components::styled_select::StyledSelectChanged;
 use crate::model::{Model, Page, PageContent};
 use crate::pages::reports_page::model::ReportsPage;
-use crate::ws::board_load;
-use crate::{Msg, OperationKind, ResourceKind};
+use crate::ws::{board_load, send_ws_msg};
+use crate::{FieldId, Msg, OperationKind, ResourceKind};
 
 pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Orders<Msg>) {
     match msg {
@@ -25,11 +28,45 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
     if model.user.is_none() {
         return;
     }
+    page.timesheet_from_state.update(&msg, orders);
+    page.timesheet_to_state.update(&msg, orders);
+    page.burndown_epic_state.update(&msg, orders);
+    page.cumulative_flow_from_state.update(&msg, orders);
+    page.cumulative_flow_to_state.update(&msg, orders);
+
     match msg {
         Msg::UserChanged(Some(..))
         | Msg::ResourceChanged(ResourceKind::Auth, OperationKind::SingleLoaded, _)
         | Msg::ChangePage(Page::Reports) => {
+            let cumulative_flow_load = page.cumulative_flow_load();
             board_load(model, orders);
+            send_ws_msg(WsMsg::VelocityLoad, model.ws.as_ref(), orders);
+            send_ws_msg(cumulative_flow_load, model.ws.as_ref(), orders);
+        }
+        Msg::ResourceChanged(ResourceKind::Issue, OperationKind::SingleModified, _)
+        | Msg::ResourceChanged(ResourceKind::Issue, OperationKind::SingleRemoved, _) => {
+            let burndown_epic_id = page.burndown_epic_id;
+            send_ws_msg(page.cumulative_flow_load(), model.ws.as_ref(), orders);
+            send_ws_msg(WsMsg::VelocityLoad, model.ws.as_ref(), orders);
+            if let Some(epic_id) = burndown_epic_id {
+                send_ws_msg(WsMsg::EpicBurndownLoad(epic_id), model.ws.as_ref(), orders);
+            }
+        }
+        Msg::StyledSelectChanged(
+            FieldId::BurndownEpic,
+            StyledSelectChanged::Changed(Some(epic_id)),
+        ) => {
+            page.burndown_epic_id = Some(epic_id as EpicId);
+            page.burndown.clear();
+            send_ws_msg(
+                WsMsg::EpicBurndownLoad(epic_id as EpicId),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
+        Msg::StyledSelectChanged(FieldId::BurndownEpic, StyledSelectChanged::Changed(None)) => {
+            page.burndown_epic_id = None;
+            page.burndown.clear();
         }
         Msg::PageChanged(PageChanged::Reports(ReportsPageChange::DayHovered(v))) => {
             page.hovered_day = v;
@@ -37,6 +74,53 @@ pub fn update(msg: Msg, model: &mut crate::model::Model, orders: &mut impl Order
         Msg::PageChanged(PageChanged::Reports(ReportsPageChange::DaySelected(v))) => {
             page.selected_day = v;
         }
+        Msg::StyledDateTimeInputChanged(
+            FieldId::TimesheetFrom,
+            StyledDateTimeChanged::DayChanged(Some(date)),
+        ) => {
+            page.timesheet_from = date.date();
+            if page.timesheet_to < page.timesheet_from {
+                page.timesheet_to = page.timesheet_from;
+                page.timesheet_to_state.timestamp = Some(date);
+            }
+        }
+        Msg::StyledDateTimeInputChanged(
+            FieldId::TimesheetTo,
+            StyledDateTimeChanged::DayChanged(Some(date)),
+        ) => {
+            page.timesheet_to = date.date();
+            if page.timesheet_from > page.timesheet_to {
+                page.timesheet_from = page.timesheet_to;
+                page.timesheet_from_state.timestamp = Some(date);
+            }
+        }
+        Msg::StyledDateTimeInputChanged(
+            FieldId::CumulativeFlowFrom,
+            StyledDateTimeChanged::DayChanged(Some(date)),
+        ) => {
+            page.cumulative_flow_from = date.date();
+            if page.cumulative_flow_to < page.cumulative_flow_from {
+                page.cumulative_flow_to = page.cumulative_flow_from;
+                page.cumulative_flow_to_state.timestamp = Some(date);
+            }
+            send_ws_msg(page.cumulative_flow_load(), model.ws.as_ref(), orders);
+        }
+        Msg::StyledDateTimeInputChanged(
+            FieldId::CumulativeFlowTo,
+            StyledDateTimeChanged::DayChanged(Some(date)),
+        ) => {
+            page.cumulative_flow_to = date.date();
+            if page.cumulative_flow_from > page.cumulative_flow_to {
+                page.cumulative_flow_from = page.cumulative_flow_to;
+                page.cumulative_flow_from_state.timestamp = Some(date);
+            }
+            send_ws_msg(page.cumulative_flow_load(), model.ws.as_ref(), orders);
+        }
+        Msg::PageChanged(PageChanged::Reports(ReportsPageChange::TimesheetGroupingChanged(
+            grouping,
+        ))) => {
+            page.timesheet_grouping = grouping;
+        }
         _ => {}
     }
 }
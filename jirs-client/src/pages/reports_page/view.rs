@@ -8,15 +8,17 @@ use seed::*;
 use crate::components::styled_icon::{Icon, StyledIcon};
 use crate::components::styled_link::*;
 use crate::model::{Model, PageContent};
+use crate::pages::reports_page::charts::{burndown_graph, cumulative_flow_graph, velocity_graph};
 use crate::pages::reports_page::model::ReportsPage;
+use crate::pages::reports_page::timesheet::timesheet;
 use crate::shared::inner_layout;
 use crate::{Msg, PageChanged, ReportsPageChange};
 
-const SVG_MARGIN_X: u32 = 10;
-const SVG_DRAWABLE_HEIGHT: u32 = 300;
-const SVG_HEIGHT: u32 = SVG_DRAWABLE_HEIGHT + 30;
-const SVG_WIDTH: u32 = 1060;
-const SVG_BAR_MARGIN: u32 = 10;
+pub const SVG_MARGIN_X: u32 = 10;
+pub const SVG_DRAWABLE_HEIGHT: u32 = 300;
+pub const SVG_HEIGHT: u32 = SVG_DRAWABLE_HEIGHT + 30;
+pub const SVG_WIDTH: u32 = 1060;
+pub const SVG_BAR_MARGIN: u32 = 10;
 
 pub fn view(model: &Model) -> Node<Msg> {
     let page = match &model.page_content {
@@ -33,7 +35,21 @@ pub fn view(model: &Model) -> Node<Msg> {
     let graph = this_month_graph(page, &this_month_updated);
     let list = issue_list(page, project_name, this_month_updated.as_slice());
 
-    let body = section![C!["top"], h1![C!["header"], "Reports"], graph, list];
+    let burndown = burndown_graph(model, page);
+    let velocity = velocity_graph(page);
+    let cumulative_flow = cumulative_flow_graph(model, page);
+    let timesheet = timesheet(model, page);
+
+    let body = section![
+        C!["top"],
+        h1![C!["header"], "Reports"],
+        graph,
+        cumulative_flow,
+        list,
+        burndown,
+        velocity,
+        timesheet
+    ];
 
     inner_layout(model, "reports", &[body])
 }
@@ -1,3 +1,5 @@
+use jirs_data::AuthMethods;
+
 use crate::shared::validate::*;
 use crate::validations::*;
 use crate::validator;
@@ -14,8 +16,14 @@ pub struct SignInPage {
     pub username: String,
     pub email: String,
     pub token: String,
+    pub password: String,
+    pub totp: String,
     pub login_success: bool,
     pub bad_token: String,
+    pub error: String,
+    pub methods: AuthMethods,
+    /// User has two factor authentication enabled and must provide code
+    pub totp_required: bool,
     // validators
     pub username_v: UsernameValidator,
     pub email_v: EmailValidator,
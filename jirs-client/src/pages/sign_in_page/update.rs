@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use jirs_data::msg::WsError;
 use jirs_data::{SignInFieldId, WsMsg};
 use seed::prelude::*;
 use seed::*;
@@ -8,7 +9,7 @@ use uuid::Uuid;
 use crate::model::{self, Model, Page, PageContent};
 use crate::pages::sign_in_page::model::SignInPage;
 use crate::shared::validate::*;
-use crate::shared::write_auth_token;
+use crate::shared::{write_auth_token, write_refresh_token};
 use crate::ws::send_ws_msg;
 use crate::{match_page_mut, FieldId, Msg, WebSocketChanged};
 
@@ -16,9 +17,9 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
     if model.page != Page::SignIn {
         return;
     } else if !matches!(model.page_content, PageContent::SignIn(..)) {
-        build_page_content(model);
+        build_page_content(model, orders);
     } else if matches!(msg, Msg::ChangePage(Page::SignIn)) {
-        build_page_content(model);
+        build_page_content(model, orders);
         return;
     };
 
@@ -37,13 +38,34 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
             page.token_v.validate(&value);
             page.token = value;
         }
+        Msg::StrInputChanged(FieldId::SignIn(SignInFieldId::Password), value) => {
+            page.password = value;
+        }
+        Msg::StrInputChanged(FieldId::SignIn(SignInFieldId::Totp), value) => {
+            page.totp = value;
+        }
         Msg::SignInRequest => {
+            page.error.clear();
             send_ws_msg(
                 WsMsg::AuthenticateRequest(page.email.clone(), page.username.clone()),
                 model.ws.as_ref(),
                 orders,
             );
         }
+        Msg::PasswordSignInRequest => {
+            page.error.clear();
+            let totp = Some(page.totp.trim().to_string()).filter(|code| !code.is_empty());
+            send_ws_msg(
+                WsMsg::PasswordSignInRequest(
+                    page.email.clone(),
+                    page.username.clone(),
+                    page.password.clone(),
+                    totp,
+                ),
+                model.ws.as_ref(),
+                orders,
+            );
+        }
         Msg::BindClientRequest => {
             let bind_token: uuid::Uuid = match Uuid::from_str(page.token.as_str()) {
                 Ok(token) => token,
@@ -52,13 +74,17 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
                     return;
                 }
             };
+            page.error.clear();
             send_ws_msg(WsMsg::BindTokenCheck(bind_token), model.ws.as_ref(), orders);
         }
         Msg::WebSocketChange(change) => match change {
             WebSocketChanged::WsMsg(WsMsg::AuthenticateSuccess) => {
                 page.login_success = true;
             }
-            WebSocketChanged::WsMsg(WsMsg::BindTokenOk(access_token)) => {
+            WebSocketChanged::WsMsg(WsMsg::BindTokenOk(access_token, refresh_token)) => {
+                if let Err(e) = write_refresh_token(Some(refresh_token)) {
+                    error!(e);
+                }
                 match write_auth_token(Some(access_token)) {
                     Ok(msg) => {
                         orders.skip().send_msg(msg);
@@ -68,12 +94,30 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
                     }
                 }
             }
+            WebSocketChanged::WsMsg(WsMsg::AuthMethodsLoaded(methods)) => {
+                page.methods = methods;
+            }
+            WebSocketChanged::WsMsg(WsMsg::TotpRequired) => {
+                page.totp_required = true;
+            }
+            WebSocketChanged::WsMsg(WsMsg::Error(e))
+                if matches!(
+                    e,
+                    WsError::TooManyRequests
+                        | WsError::AuthMethodDisabled
+                        | WsError::InvalidCredentials
+                        | WsError::InvalidTotpCode
+                ) =>
+            {
+                page.error = e.to_str().to_string();
+            }
             _ => (),
         },
         _ => (),
     };
 }
 
-fn build_page_content(model: &mut Model) {
+fn build_page_content(model: &mut Model, orders: &mut impl Orders<Msg>) {
     model.page_content = PageContent::SignIn(Box::new(SignInPage::default()));
+    send_ws_msg(WsMsg::AuthMethodsLoad, model.ws.as_ref(), orders);
 }
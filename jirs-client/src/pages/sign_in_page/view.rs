@@ -44,21 +44,88 @@ pub fn view(model: &model::Model) -> Node<Msg> {
     }
     .render();
 
-    let submit = if page.login_success {
+    let password_field = if page.methods.password {
+        let password = StyledInput {
+            value: page.password.as_str(),
+            valid: true,
+            id: Some(FieldId::SignIn(SignInFieldId::Password)),
+            input_type: Some("password"),
+            ..Default::default()
+        }
+        .render();
+        StyledField {
+            label: "Password",
+            input: password,
+            ..Default::default()
+        }
+        .render()
+    } else {
+        empty![]
+    };
+
+    let totp_field = if page.totp_required {
+        let totp = StyledInput {
+            value: page.totp.as_str(),
+            valid: true,
+            id: Some(FieldId::SignIn(SignInFieldId::Totp)),
+            auto_focus: true,
+            ..Default::default()
+        }
+        .render();
+        StyledField {
+            label: "Authentication code",
+            tip: Some("Code from your authenticator application"),
+            input: totp,
+            ..Default::default()
+        }
+        .render()
+    } else {
+        empty![]
+    };
+
+    let password_submit = if page.methods.password {
+        StyledButton {
+            variant: ButtonVariant::Primary,
+            text: Some("Sign In"),
+            on_click: Some(mouse_ev(Ev::Click, |ev| {
+                ev.prevent_default();
+                Msg::PasswordSignInRequest
+            })),
+            ..Default::default()
+        }
+        .render()
+    } else {
+        empty![]
+    };
+    let submit = if !page.methods.email_token {
+        empty![]
+    } else if page.login_success {
         StyledButton {
             variant: ButtonVariant::Success,
             text: Some("✓ Please check your mail"),
             ..Default::default()
         }
+        .render()
     } else {
         StyledButton {
-            variant: ButtonVariant::Primary,
-            text: Some("Sign In"),
-            on_click: Some(mouse_ev(Ev::Click, |_| Msg::SignInRequest)),
+            variant: if page.methods.password {
+                ButtonVariant::Empty
+            } else {
+                ButtonVariant::Primary
+            },
+            text: Some(if page.methods.password {
+                "Send me single use token"
+            } else {
+                "Sign In"
+            }),
+            on_click: Some(mouse_ev(Ev::Click, |ev| {
+                ev.prevent_default();
+                Msg::SignInRequest
+            })),
             ..Default::default()
         }
-    }
-    .render();
+        .render()
+    };
     let register_link = StyledLink {
         children: vec![span!["Register"]],
         class_list: "signUpLink",
@@ -66,37 +133,63 @@ pub fn view(model: &model::Model) -> Node<Msg> {
     }
     .render();
     let submit_field = StyledField {
-        input: div![C!["twoRow"], submit, register_link],
+        input: div![C!["twoRow"], password_submit, submit, register_link],
         ..Default::default()
     }
     .render();
 
-    let help_icon = StyledIcon {
-        icon: Icon::Help,
-        class_list: "noPasswordHelp",
-        size: Some(22),
-        ..Default::default()
-    }
-    .render();
+    let no_pass_section = if page.methods.password {
+        empty![]
+    } else {
+        let help_icon = StyledIcon {
+            icon: Icon::Help,
+            class_list: "noPasswordHelp",
+            size: Some(22),
+            ..Default::default()
+        }
+        .render();
+        div![
+            C!["noPasswordSection"],
+            attrs![At::Title => "We don't believe password is helping anyone. Instead after user provide correct login and e-mail he'll receive mail with 1-use token."],
+            help_icon,
+            span!["Why I don't see password?"]
+        ]
+    };
 
-    let no_pass_section = div![
-        C!["noPasswordSection"],
-        attrs![At::Title => "We don't believe password is helping anyone. Instead after user provide correct login and e-mail he'll receive mail with 1-use token."],
-        help_icon,
-        span!["Why I don't see password?"]
-    ];
+    let error_row = if page.error.is_empty() {
+        empty![]
+    } else {
+        div![C!["error"], p![page.error.as_str()]]
+    };
 
+    let use_password = page.methods.password;
     let sign_in_form = StyledForm {
         heading: "Sign In to your account",
-        fields: vec![username_field, email_field, submit_field, no_pass_section],
-        on_submit: Some(ev(Ev::Submit, |ev| {
+        fields: vec![
+            username_field,
+            email_field,
+            password_field,
+            totp_field,
+            submit_field,
+            no_pass_section,
+            error_row,
+        ],
+        on_submit: Some(ev(Ev::Submit, move |ev| {
             ev.stop_propagation();
             ev.prevent_default();
-            Msg::SignInRequest
+            if use_password {
+                Msg::PasswordSignInRequest
+            } else {
+                Msg::SignInRequest
+            }
         })),
     }
     .render();
 
+    if !page.methods.email_token {
+        return outer_layout(model, "login", vec![sign_in_form]);
+    }
+
     let token = StyledInput {
         id: Some(FieldId::SignIn(SignInFieldId::Token)),
         valid: page.token_v.is_valid(),
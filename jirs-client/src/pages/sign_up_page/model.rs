@@ -1,9 +1,13 @@
+use jirs_data::AuthMethods;
+
 #[derive(Debug, Default)]
 pub struct SignUpPage {
     pub username: String,
     pub email: String,
+    pub password: String,
     pub sign_up_success: bool,
     pub error: String,
+    pub methods: AuthMethods,
     // touched
     pub username_touched: bool,
     pub email_touched: bool,
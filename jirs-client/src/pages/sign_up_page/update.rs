@@ -1,3 +1,4 @@
+use jirs_data::msg::WsError;
 use jirs_data::{SignUpFieldId, WsMsg};
 use seed::prelude::*;
 
@@ -12,7 +13,7 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
     }
 
     if let Msg::ChangePage(Page::SignUp) = msg {
-        build_page_content(model);
+        build_page_content(model, orders);
         return;
     };
 
@@ -30,9 +31,16 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
             page.email = value;
             page.email_touched = true;
         }
+        Msg::StrInputChanged(FieldId::SignUp(SignUpFieldId::Password), value) => {
+            page.password = value;
+        }
         Msg::SignUpRequest => {
             send_ws_msg(
-                WsMsg::SignUpRequest(page.email.clone(), page.username.clone()),
+                WsMsg::SignUpRequest(
+                    page.email.clone(),
+                    page.username.clone(),
+                    Some(page.password.clone()).filter(|password| !password.is_empty()),
+                ),
                 model.ws.as_ref(),
                 orders,
             );
@@ -44,12 +52,26 @@ pub fn update(msg: Msg, model: &mut model::Model, orders: &mut impl Orders<Msg>)
             WebSocketChanged::WsMsg(WsMsg::SignUpPairTaken) => {
                 page.error = "Pair you give is either taken or is not matching".to_string();
             }
+            WebSocketChanged::WsMsg(WsMsg::AuthMethodsLoaded(methods)) => {
+                page.methods = methods;
+            }
+            WebSocketChanged::WsMsg(WsMsg::Error(e))
+                if matches!(
+                    e,
+                    WsError::TooManyRequests
+                        | WsError::AuthMethodDisabled
+                        | WsError::PasswordTooShort
+                ) =>
+            {
+                page.error = e.to_str().to_string();
+            }
             _ => (),
         },
         _ => (),
     }
 }
 
-fn build_page_content(model: &mut Model) {
+fn build_page_content(model: &mut Model, orders: &mut impl Orders<Msg>) {
     model.page_content = PageContent::SignUp(Box::new(SignUpPage::default()));
+    send_ws_msg(WsMsg::AuthMethodsLoad, model.ws.as_ref(), orders);
 }
@@ -43,10 +43,38 @@ pub fn view(model: &model::Model) -> Node<Msg> {
     }
     .render();
 
+    let password_field = if page.methods.password {
+        let password = StyledInput {
+            value: page.password.as_str(),
+            valid: true,
+            id: Some(FieldId::SignUp(SignUpFieldId::Password)),
+            input_type: Some("password"),
+            ..Default::default()
+        }
+        .render();
+        StyledField {
+            label: "Password",
+            tip: if page.methods.email_token {
+                Some("Leave empty to sign in only with single use token")
+            } else {
+                None
+            },
+            input: password,
+            ..Default::default()
+        }
+        .render()
+    } else {
+        empty![]
+    };
+
     let submit = if page.sign_up_success {
         StyledButton {
             variant: ButtonVariant::Success,
-            text: Some("✓ Please check your mail"),
+            text: Some(if page.methods.email_token {
+                "✓ Please check your mail"
+            } else {
+                "✓ You can sign in now"
+            }),
             ..Default::default()
         }
     } else {
@@ -72,20 +100,23 @@ pub fn view(model: &model::Model) -> Node<Msg> {
     }
     .render();
 
-    let help_icon = StyledIcon {
-        icon: Icon::Help,
-        class_list: "noPasswordHelp",
-        size: Some(22),
-        ..Default::default()
-    }
-    .render();
-
-    let no_pass_section = div![
-        C!["noPasswordSection"],
-        attrs![At::Title => "We don't believe password is helping anyone. Instead after user provide correct login and e-mail he'll receive mail with 1-use token."],
-        help_icon,
-        span!["Why I don't see password?"]
-    ];
+    let no_pass_section = if page.methods.password {
+        empty![]
+    } else {
+        let help_icon = StyledIcon {
+            icon: Icon::Help,
+            class_list: "noPasswordHelp",
+            size: Some(22),
+            ..Default::default()
+        }
+        .render();
+        div![
+            C!["noPasswordSection"],
+            attrs![At::Title => "We don't believe password is helping anyone. Instead after user provide correct login and e-mail he'll receive mail with 1-use token."],
+            help_icon,
+            span!["Why I don't see password?"]
+        ]
+    };
 
     let error_row = if page.error.is_empty() {
         empty![]
@@ -103,6 +134,7 @@ pub fn view(model: &model::Model) -> Node<Msg> {
         fields: vec![
             username_field,
             email_field,
+            password_field,
             submit_field,
             no_pass_section,
             error_row,
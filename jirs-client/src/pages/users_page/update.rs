@@ -1,3 +1,4 @@
+use jirs_data::msg::WsError;
 use jirs_data::{InvitationState, UserRole, UsersFieldId, WsMsg};
 use seed::prelude::Orders;
 
@@ -58,7 +59,8 @@ pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 send_ws_msg(WsMsg::InvitationListLoad, model.ws.as_ref(), orders);
                 page.form_state = InvitationFormState::Succeed;
             }
-            WebSocketChanged::WsMsg(WsMsg::InvitationSendFailure) => {
+            WebSocketChanged::WsMsg(WsMsg::InvitationSendFailure)
+            | WebSocketChanged::WsMsg(WsMsg::Error(WsError::TooManyRequests)) => {
                 page.form_state = InvitationFormState::Failed;
             }
             _ => (),
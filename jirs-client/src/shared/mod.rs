@@ -111,3 +111,33 @@ pub fn read_auth_token() -> Result<uuid::Uuid, String> {
         .parse()
         .map_err(|_| "Bad token format".to_string())
 }
+
+pub fn write_refresh_token(token: Option<uuid::Uuid>) -> Result<(), String> {
+    let w = window();
+    let store = match w.local_storage() {
+        Ok(Some(store)) => store,
+        _ => return Err("Local storage is not available".to_string()),
+    };
+    match token {
+        Some(token) => store
+            .set_item("refreshToken", format!("{}", token).as_str())
+            .map_err(|e| format!("Failed to write refresh token. {:?}", e)),
+        _ => store
+            .remove_item("refreshToken")
+            .map_err(|e| format!("Failed to remove refresh token. {:?}", e)),
+    }
+}
+
+pub fn read_refresh_token() -> Result<uuid::Uuid, String> {
+    let w = window();
+    let store = match w.local_storage() {
+        Ok(Some(store)) => store,
+        _ => return Err("Local storage is not available".to_string()),
+    };
+    store
+        .get_item("refreshToken")
+        .map_err(|_e| "Failed to read refresh token".to_string())?
+        .ok_or_else(|| "Refresh token not found".to_string())?
+        .parse()
+        .map_err(|_| "Bad token format".to_string())
+}
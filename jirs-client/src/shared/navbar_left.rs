@@ -274,6 +274,12 @@ fn message_ui(model: &Model, message: &Message) -> Option<Node<Msg>> {
             div![C!["description"], message_description],
             hyperlink,
         ],
+        MessageType::WatchedIssueUpdated => div![
+            C!["message watchedIssueUpdated"],
+            top,
+            div![C!["description"], message_description],
+            hyperlink,
+        ],
     };
     Some(node)
 }
@@ -1,4 +1,4 @@
-use jirs_data::{TimeTracking, UpdateIssuePayload};
+use jirs_data::{Issue, TimeTracking, UpdateIssuePayload};
 use seed::prelude::*;
 use seed::*;
 
@@ -61,6 +61,21 @@ pub fn tracking_widget(model: &Model, modal: &EditIssueModal) -> Node<Msg> {
             },
         ..
     } = modal;
+    let subtasks: Vec<&Issue> = model
+        .issues()
+        .iter()
+        .filter(|issue| issue.parent_id == Some(modal.id))
+        .collect();
+    let (estimate, time_spent, time_remaining) = if subtasks.is_empty() {
+        (*estimate, *time_spent, *time_remaining)
+    } else {
+        (
+            roll_up(&subtasks, |issue| issue.estimate),
+            roll_up(&subtasks, |issue| issue.time_spent),
+            roll_up(&subtasks, |issue| issue.time_remaining),
+        )
+    };
+    let (estimate, time_spent, time_remaining) = (&estimate, &time_spent, &time_remaining);
 
     let icon = StyledIcon {
         icon: Icon::Stopwatch,
@@ -124,6 +139,15 @@ fn remaining_node(
     div![text]
 }
 
+/// Sum of sub-tasks values, `None` when none of sub-tasks has value
+#[inline]
+fn roll_up(subtasks: &[&Issue], value: fn(&Issue) -> Option<i32>) -> Option<i32> {
+    subtasks
+        .iter()
+        .filter_map(|issue| value(issue))
+        .fold(None, |sum, v| Some(sum.unwrap_or_default() + v))
+}
+
 #[inline]
 fn calc_bar_width(
     estimate: Option<i32>,
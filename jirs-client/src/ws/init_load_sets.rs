@@ -12,6 +12,13 @@ pub fn board_load(model: &mut Model, orders: &mut impl Orders<Msg>) {
             WsMsg::ProjectIssuesLoad,
             WsMsg::ProjectUsersLoad,
             WsMsg::EpicsLoad,
+            WsMsg::SprintsLoad,
+            WsMsg::IssueLinksLoad,
+            WsMsg::LabelsLoad,
+            WsMsg::CustomFieldsLoad,
+            WsMsg::IssueCustomValuesLoad,
+            WsMsg::WorklogsLoad,
+            WsMsg::WorkflowTransitionsLoad,
         ],
         model.ws.as_ref(),
         orders,
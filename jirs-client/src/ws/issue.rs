@@ -3,6 +3,7 @@ use seed::prelude::Orders;
 use seed::*;
 
 use crate::model::{Model, PageContent};
+use crate::pages::project_page::model::BoardLane;
 use crate::pages::project_page::ProjectPage;
 use crate::ws::send_ws_msg;
 use crate::Msg;
@@ -28,21 +29,30 @@ pub fn change_position(below_id: EpicId, model: &mut Model) {
         return;
     }
 
-    let (issue_status_id, epic_id) = model
-        .issues()
-        .iter()
-        .find_map(|issue| {
-            if issue.id == dragged_id {
-                Some((issue.issue_status_id, issue.epic_id))
-            } else {
-                None
-            }
-        })
-        .unwrap_or_default();
+    // issue from other column or lane, move dragged issue there first
+    let grouping = model.board_grouping();
+    let below = model
+        .issues_by_id
+        .get(&below_id)
+        .map(|issue| (issue.issue_status_id, BoardLane::of(grouping, issue)));
+    let dragged = model
+        .issues_by_id
+        .get(&dragged_id)
+        .map(|issue| (issue.issue_status_id, BoardLane::of(grouping, issue)));
+    let (issue_status_id, lane) = match (below, dragged) {
+        (Some(below), Some(dragged)) if below != dragged => {
+            change_status(below.0, below.1, model);
+            below
+        }
+        (_, Some(dragged)) => dragged,
+        _ => return,
+    };
 
     let mut issues: Vec<Issue> = model
         .issues_mut()
-        .drain_filter(|issue| issue.issue_status_id == issue_status_id && issue.epic_id == epic_id)
+        .drain_filter(|issue| {
+            issue.issue_status_id == issue_status_id && BoardLane::of(grouping, issue) == lane
+        })
         .collect();
     issues.sort_by(|a, b| a.list_position.cmp(&b.list_position));
 
@@ -68,13 +78,7 @@ pub fn change_position(below_id: EpicId, model: &mut Model) {
         model.issues_mut().push(issue);
     }
 
-    let visible = ProjectPage::visible_issues(
-        crate::match_page!(model, Project),
-        model.epics(),
-        model.issue_statuses(),
-        model.issues(),
-        model.user(),
-    );
+    let visible = ProjectPage::visible_issues(crate::match_page!(model, Project), model);
     if let PageContent::Project(project_page) = &mut model.page_content {
         project_page.visible_issues = visible;
         for (id, _) in changed.iter() {
@@ -84,12 +88,15 @@ pub fn change_position(below_id: EpicId, model: &mut Model) {
 }
 
 pub fn sync(model: &mut Model, orders: &mut impl Orders<Msg>) {
-    let dirty = match &mut model.page_content {
-        PageContent::Project(project_page) => std::mem::take(&mut project_page.issue_drag.dirty),
+    let (dirty, lane_changes) = match &mut model.page_content {
+        PageContent::Project(project_page) => (
+            std::mem::take(&mut project_page.issue_drag.dirty),
+            std::mem::take(&mut project_page.lane_changes),
+        ),
         _ => return,
     };
 
-    let changes: Vec<(EpicId, ListPosition, IssueStatusId, Option<EpicId>)> = dirty
+    let changes: Vec<(IssueId, ListPosition, IssueStatusId, Option<EpicId>)> = dirty
         .into_iter()
         .filter_map(|id| {
             model.issues_by_id.get(&id).map(|issue| {
@@ -108,10 +115,15 @@ pub fn sync(model: &mut Model, orders: &mut impl Orders<Msg>) {
         model.ws.as_ref(),
         orders,
     );
+    for (issue_id, lane) in lane_changes {
+        if let Some(ws_msg) = lane.into_update(issue_id) {
+            send_ws_msg(ws_msg, model.ws.as_ref(), orders);
+        }
+    }
     crate::match_page_mut!(model, Project).issue_drag.clear();
 }
 
-pub fn change_status(status_id: IssueStatusId, model: &mut Model) {
+pub fn change_status(status_id: IssueStatusId, lane: BoardLane, model: &mut Model) {
     let dragged_id = match crate::match_page!(model, Project)
         .issue_drag
         .dragged_id
@@ -121,12 +133,12 @@ pub fn change_status(status_id: IssueStatusId, model: &mut Model) {
         Some(issue_id) => issue_id,
         _ => return error!("Nothing is dragged"),
     };
-    let (issue_status_id, epic_id) = model
-        .issues_by_id
-        .get(&dragged_id)
-        .map(|issue| (issue.issue_status_id, issue.epic_id))
-        .unwrap_or_default();
-    if status_id == issue_status_id {
+    let grouping = model.board_grouping();
+    let (issue_status_id, current_lane) = match model.issues_by_id.get(&dragged_id) {
+        Some(issue) => (issue.issue_status_id, BoardLane::of(grouping, issue)),
+        _ => return,
+    };
+    if status_id == issue_status_id && lane == current_lane {
         return;
     }
 
@@ -135,19 +147,20 @@ pub fn change_status(status_id: IssueStatusId, model: &mut Model) {
         .drain_filter(|issue| {
             if issue.id == dragged_id {
                 issue.issue_status_id = status_id;
+                lane.apply(issue);
             }
-            issue.issue_status_id == status_id && issue.epic_id == epic_id
+            issue.issue_status_id == status_id && BoardLane::of(grouping, issue) == lane
         })
         .collect();
 
     issues.sort_by(|a, b| a.list_position.cmp(&b.list_position));
 
     let mut dirty = vec![];
-    for mut issue in issues {
+    for issue in issues {
         if issue.id == dragged_id {
-            issue.issue_status_id = status_id;
             if let Some(iss) = model.issues_by_id.get_mut(&issue.id) {
                 iss.issue_status_id = status_id;
+                lane.apply(iss);
             }
         }
 
@@ -159,15 +172,12 @@ pub fn change_status(status_id: IssueStatusId, model: &mut Model) {
         for id in dirty {
             project_page.issue_drag.mark_dirty(id);
         }
+        if lane != current_lane {
+            project_page.lane_changes.insert(dragged_id, lane);
+        }
     }
 
-    let visible = ProjectPage::visible_issues(
-        crate::match_page!(model, Project),
-        model.epics(),
-        model.issue_statuses(),
-        model.issues(),
-        model.user(),
-    );
+    let visible = ProjectPage::visible_issues(crate::match_page!(model, Project), model);
 
     crate::match_page_mut!(model, Project).visible_issues = visible;
 }
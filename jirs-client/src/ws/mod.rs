@@ -1,9 +1,10 @@
 pub use init_load_sets::*;
+use jirs_data::msg::WsError;
 use jirs_data::*;
 use seed::prelude::*;
 
 use crate::model::*;
-use crate::shared::{go_to_board, write_auth_token};
+use crate::shared::{go_to_board, read_refresh_token, write_auth_token, write_refresh_token};
 use crate::{Msg, OperationKind, ResourceKind, WebSocketChanged};
 
 mod init_load_sets;
@@ -120,6 +121,16 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         }
         WsMsg::AuthorizeExpired => {
             log::warn!("Received token expired");
+            // refresh is tried only once, next expired message signs out
+            if let Ok(refresh_token) = read_refresh_token() {
+                let _ = write_refresh_token(None);
+                send_ws_msg(
+                    WsMsg::RefreshToken(refresh_token),
+                    model.ws.as_ref(),
+                    orders,
+                );
+                return;
+            }
             if let Ok(msg) = write_auth_token(None) {
                 orders.skip().send_msg(msg).send_msg(Msg::ResourceChanged(
                     ResourceKind::Auth,
@@ -128,6 +139,16 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 ));
             }
         }
+        WsMsg::TokenRefreshed(access_token, refresh_token) => {
+            let _ = write_refresh_token(Some(refresh_token));
+            if write_auth_token(Some(access_token)).is_ok() {
+                send_ws_msg(
+                    WsMsg::AuthorizeLoad(access_token),
+                    model.ws.as_ref(),
+                    orders,
+                );
+            }
+        }
         // project
         WsMsg::ProjectsLoaded(v) => {
             model.projects = v;
@@ -259,6 +280,16 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 Some(id),
             ));
         }
+        WsMsg::IssueCreated(issue) => {
+            let id = issue.id;
+            model.issues_by_id.insert(id, issue.clone());
+            model.issues_mut().push(issue);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Issue,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
         WsMsg::IssueDeleted(id, _count) => {
             let mut old = vec![];
             std::mem::swap(model.issues_mut(), &mut old);
@@ -287,6 +318,39 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 None,
             ));
         }
+        // issue search
+        WsMsg::IssueSearchResult(issues) => {
+            if let PageContent::Project(page) = &mut model.page_content {
+                page.search_issue_ids = Some(issues.into_iter().map(|i| i.id).collect());
+                page.search_error = None;
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Issue,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::Error(WsError::InvalidSearchQuery(e)) => {
+            if let PageContent::Project(page) = &mut model.page_content {
+                page.search_error = Some(e.to_string());
+            }
+        }
+        // issue history
+        WsMsg::IssueHistoryLoaded(issue_id, history) => {
+            if let Some(modal) = model.modals_mut().edit_issue.as_mut() {
+                if modal.id == issue_id {
+                    modal.history = history;
+                }
+            }
+        }
+        // issue watchers
+        WsMsg::IssueWatchersLoaded(issue_id, watcher_ids) => {
+            if let Some(modal) = model.modals_mut().edit_issue.as_mut() {
+                if modal.id == issue_id {
+                    modal.watcher_ids = watcher_ids;
+                }
+            }
+        }
         // comments
         WsMsg::IssueCommentsLoaded(mut comments) => {
             let issue_id = match &model.modals().edit_issue {
@@ -307,6 +371,23 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 None,
             ));
         }
+        WsMsg::CommentCreated(comment) => {
+            let issue_id = match &model.modals().edit_issue {
+                Some(modal) => modal.id,
+                _ => return,
+            };
+            if comment.issue_id != issue_id || model.comments_by_id.contains_key(&comment.id) {
+                return;
+            }
+            let comment_id = comment.id;
+            model.comments.push(comment.clone());
+            model.comments_by_id.insert(comment.id, comment);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Comment,
+                OperationKind::SingleCreated,
+                Some(comment_id),
+            ));
+        }
         WsMsg::CommentUpdated(comment) => {
             let comment_id = comment.id;
             if let Some(idx) = model.comments.iter().position(|c| c.id == comment.id) {
@@ -428,6 +509,267 @@ pub fn update(msg: WsMsg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 Some(id),
             ));
         }
+        // sprints
+        WsMsg::SprintsLoaded(sprints) => {
+            model.sprints = sprints;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Sprint,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::SprintCreated(sprint) => {
+            let id = sprint.id;
+            model.sprints.push(sprint);
+            model.sprints.sort_by(|a, b| a.id.cmp(&b.id));
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Sprint,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::Error(e)
+            if matches!(
+                e,
+                WsError::InvalidSprint | WsError::SprintAlreadyActive | WsError::SprintNotActive
+            ) =>
+        {
+            if let PageContent::Backlog(page) = &mut model.page_content {
+                page.error = Some(e.to_str().to_string());
+            }
+        }
+        WsMsg::SprintUpdated(sprint) => {
+            let id = sprint.id;
+            if let Some(idx) = model.sprints.iter().position(|s| s.id == id) {
+                let _ = std::mem::replace(&mut model.sprints[idx], sprint);
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Sprint,
+                OperationKind::SingleModified,
+                Some(id),
+            ));
+        }
+        // issue links
+        WsMsg::IssueLinksLoaded(links) => {
+            model.issue_links = links;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::IssueLink,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::IssueLinkCreated(link) => {
+            let id = link.id;
+            model.issue_links.push(link);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::IssueLink,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::IssueLinkDeleted(id, _count) => {
+            model.issue_links.retain(|link| link.id != id);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::IssueLink,
+                OperationKind::SingleRemoved,
+                Some(id),
+            ));
+        }
+        // labels
+        WsMsg::LabelsLoaded(labels) => {
+            model.labels = labels;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Label,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::LabelCreated(label) => {
+            let id = label.id;
+            model.labels.push(label);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Label,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::LabelDeleted(id, _count) => {
+            model.labels.retain(|label| label.id != id);
+            for issue in model.issues_mut().iter_mut() {
+                issue.label_ids.retain(|label_id| *label_id != id);
+            }
+            for issue in model.issues_by_id.values_mut() {
+                issue.label_ids.retain(|label_id| *label_id != id);
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Label,
+                OperationKind::SingleRemoved,
+                Some(id),
+            ));
+        }
+        // custom fields
+        WsMsg::CustomFieldsLoaded(fields) => {
+            model.custom_fields = fields;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::CustomField,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::CustomFieldCreated(field) => {
+            let id = field.id;
+            model.custom_fields.push(field);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::CustomField,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::CustomFieldUpdated(field) => {
+            let id = field.id;
+            if let Some(current) = model.custom_fields.iter_mut().find(|f| f.id == id) {
+                *current = field;
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::CustomField,
+                OperationKind::SingleModified,
+                Some(id),
+            ));
+        }
+        WsMsg::CustomFieldDeleted(id, _count) => {
+            model.custom_fields.retain(|field| field.id != id);
+            model
+                .issue_custom_values
+                .retain(|value| value.custom_field_id != id);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::CustomField,
+                OperationKind::SingleRemoved,
+                Some(id),
+            ));
+        }
+        // workflow
+        WsMsg::WorkflowTransitionsLoaded(transitions) => {
+            model.workflow_transitions = transitions;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::WorkflowTransition,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::WorkflowTransitionCreated(transition) => {
+            let id = transition.id;
+            model.workflow_transitions.push(transition);
+            if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                page.transition_error = None;
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::WorkflowTransition,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::WorkflowTransitionDeleted(id, _count) => {
+            model.workflow_transitions.retain(|t| t.id != id);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::WorkflowTransition,
+                OperationKind::SingleRemoved,
+                Some(id),
+            ));
+        }
+        WsMsg::Error(WsError::InvalidWorkflowTransition) => {
+            if let PageContent::ProjectSettings(page) = &mut model.page_content {
+                page.transition_error =
+                    Some(WsError::InvalidWorkflowTransition.to_str().to_string());
+            }
+        }
+        WsMsg::Error(e)
+            if matches!(
+                e,
+                WsError::TransitionNotAllowed
+                    | WsError::TransitionRoleRequired
+                    | WsError::TransitionRequiresAssignee
+                    | WsError::TransitionRequiresEstimate
+                    | WsError::WipLimitExceeded
+            ) =>
+        {
+            if let PageContent::Project(page) = &mut model.page_content {
+                page.transition_error = Some(e.to_str().to_string());
+            }
+        }
+        WsMsg::IssueCustomValuesLoaded(values) => {
+            model.issue_custom_values = values;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::IssueCustomValue,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::IssueCustomValuesUpdated(issue_id, values) => {
+            model
+                .issue_custom_values
+                .retain(|value| value.issue_id != issue_id);
+            model.issue_custom_values.extend(values);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::IssueCustomValue,
+                OperationKind::SingleModified,
+                Some(issue_id),
+            ));
+        }
+        // worklogs
+        WsMsg::WorklogsLoaded(worklogs) => {
+            model.worklogs = worklogs;
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Worklog,
+                OperationKind::ListLoaded,
+                None,
+            ));
+        }
+        WsMsg::WorklogCreated(worklog) => {
+            let id = worklog.id;
+            model.worklogs.push(worklog);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Worklog,
+                OperationKind::SingleCreated,
+                Some(id),
+            ));
+        }
+        WsMsg::WorklogUpdated(worklog) => {
+            let id = worklog.id;
+            if let Some(current) = model.worklogs.iter_mut().find(|w| w.id == id) {
+                *current = worklog;
+            }
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Worklog,
+                OperationKind::SingleModified,
+                Some(id),
+            ));
+        }
+        WsMsg::WorklogDeleted(id, _count) => {
+            model.worklogs.retain(|worklog| worklog.id != id);
+            orders.send_msg(Msg::ResourceChanged(
+                ResourceKind::Worklog,
+                OperationKind::SingleRemoved,
+                Some(id),
+            ));
+        }
+        // reports
+        WsMsg::EpicBurndownLoaded(epic_id, points) => {
+            if let PageContent::Reports(page) = &mut model.page_content {
+                if page.burndown_epic_id == Some(epic_id) {
+                    page.burndown = points;
+                }
+            }
+        }
+        WsMsg::VelocityLoaded(points) => {
+            if let PageContent::Reports(page) = &mut model.page_content {
+                page.velocity = points;
+            }
+        }
+        WsMsg::CumulativeFlowLoaded(points) => {
+            if let PageContent::Reports(page) = &mut model.page_content {
+                page.cumulative_flow = points;
+            }
+        }
         _ => (),
     };
 }
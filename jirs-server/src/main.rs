@@ -44,6 +44,49 @@ async fn main() -> Result<(), String> {
     );
 
     let ws_server = websocket_actor::server::WsServer::start_default();
+    let rate_limiter = websocket_actor::rate_limit::RateLimiter::start_default();
+
+    let cleanup_interval = jirs_config::auth::Configuration::read().cleanup_interval;
+    let cleanup_addr = db_addr.clone();
+    if cleanup_interval > 0 {
+        actix_rt::spawn(async move {
+            let mut interval =
+                actix_rt::time::interval(std::time::Duration::from_secs(cleanup_interval));
+            loop {
+                interval.tick().await;
+                match cleanup_addr
+                    .send(database_actor::tokens::DeleteExpiredTokens)
+                    .await
+                {
+                    Ok(Ok(n)) => log::debug!("removed {} expired tokens", n),
+                    Ok(Err(e)) => log::error!("{:?}", e),
+                    Err(e) => log::error!("{}", e),
+                }
+            }
+        });
+    } else {
+        log::warn!("removing expired tokens is disabled");
+    }
+
+    let digest_interval =
+        std::time::Duration::from_secs(jirs_config::mail::Configuration::read().digest_interval);
+    let (digest_db_addr, digest_mail_addr) = (db_addr.clone(), mail_addr.clone());
+    actix_rt::spawn(async move {
+        // first digest is sent after full interval, not on every restart
+        let mut interval = actix_rt::time::interval_at(
+            actix_rt::time::Instant::now() + digest_interval,
+            digest_interval,
+        );
+        loop {
+            interval.tick().await;
+            match websocket_actor::notifications::send_digests(&digest_db_addr, &digest_mail_addr)
+                .await
+            {
+                Ok(n) => log::debug!("sent {} notification digests", n),
+                Err(e) => log::error!("{}", e),
+            }
+        }
+    });
 
     HttpServer::new(move || {
         let app = App::new().wrap(actix_web::middleware::Logger::default());
@@ -51,6 +94,7 @@ async fn main() -> Result<(), String> {
         // data step
         let app = app
             .data(ws_server.clone())
+            .data(rate_limiter.clone())
             .data(db_addr.clone())
             .data(mail_addr.clone())
             .data(hi_addr.clone())
@@ -61,7 +105,8 @@ async fn main() -> Result<(), String> {
         // services step
         let app = app
             .service(websocket_actor::index)
-            .service(actix_web::web::scope("/avatar").service(web_actor::avatar::upload));
+            .service(actix_web::web::scope("/avatar").service(web_actor::avatar::upload))
+            .service(web_actor::api::service());
 
         featured! { app, "local-storage", app.service(filesystem_actor::service()) };
         app
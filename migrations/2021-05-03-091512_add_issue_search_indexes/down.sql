@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS comments_search_idx;
+DROP INDEX IF EXISTS issues_search_idx;
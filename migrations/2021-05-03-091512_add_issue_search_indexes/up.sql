@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS issues_search_idx ON issues
+    USING GIN (to_tsvector('english', title || ' ' || COALESCE(description_text, '')));
+
+CREATE INDEX IF NOT EXISTS comments_search_idx ON comments
+    USING GIN (to_tsvector('english', body));
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS issue_history;
+DROP TYPE IF EXISTS "IssueHistoryFieldType";
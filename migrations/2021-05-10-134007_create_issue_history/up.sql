@@ -0,0 +1,25 @@
+DROP TYPE IF EXISTS "IssueHistoryFieldType" CASCADE;
+CREATE TYPE "IssueHistoryFieldType" AS ENUM (
+    'title',
+    'type',
+    'status',
+    'priority',
+    'description',
+    'estimate',
+    'time_spent',
+    'time_remaining',
+    'assignees',
+    'reporter',
+    'epic'
+    );
+
+CREATE TABLE issue_history (
+    id serial primary key not null,
+    issue_id integer not null references issues (id) ON DELETE CASCADE,
+    user_id integer not null references users (id),
+    field "IssueHistoryFieldType" not null,
+    old_value text,
+    new_value text,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now()
+);
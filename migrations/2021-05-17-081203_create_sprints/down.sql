@@ -0,0 +1,4 @@
+ALTER TABLE issues
+    DROP COLUMN sprint_id;
+DROP TABLE IF EXISTS sprints;
+DROP TYPE IF EXISTS "SprintStateType" CASCADE;
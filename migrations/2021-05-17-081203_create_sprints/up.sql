@@ -0,0 +1,22 @@
+DROP TYPE IF EXISTS "SprintStateType" CASCADE;
+CREATE TYPE "SprintStateType" AS ENUM (
+    'future',
+    'active',
+    'completed'
+    );
+
+CREATE TABLE sprints (
+    id serial primary key not null,
+    name text not null,
+    goal text,
+    project_id integer not null references projects (id),
+    state "SprintStateType" not null default 'future',
+    starts_at timestamp,
+    ends_at timestamp,
+    completed_at timestamp,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now()
+);
+
+ALTER TABLE issues
+    ADD COLUMN sprint_id integer references sprints (id) ON DELETE SET NULL;
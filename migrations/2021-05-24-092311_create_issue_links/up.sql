@@ -0,0 +1,20 @@
+DROP TYPE IF EXISTS "IssueLinkTypeType" CASCADE;
+CREATE TYPE "IssueLinkTypeType" AS ENUM (
+    'blocks',
+    'duplicates',
+    'relates_to'
+    );
+
+CREATE TABLE issue_links (
+    id serial primary key not null,
+    link_type "IssueLinkTypeType" not null,
+    source_id integer not null references issues (id) ON DELETE CASCADE,
+    target_id integer not null references issues (id) ON DELETE CASCADE,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT issue_links_not_self CHECK ( source_id <> target_id ),
+    CONSTRAINT issue_links_unique UNIQUE (source_id, target_id, link_type)
+);
+
+CREATE INDEX issue_links_source_id_idx ON issue_links (source_id);
+CREATE INDEX issue_links_target_id_idx ON issue_links (target_id);
@@ -0,0 +1,25 @@
+ALTER TABLE "issues"
+    ALTER COLUMN "issue_type"
+        SET DATA TYPE TEXT
+        USING "issue_type"::TEXT;
+
+DROP TYPE IF EXISTS "IssueTypeType" CASCADE;
+CREATE TYPE "IssueTypeType" AS ENUM (
+    'task',
+    'bug',
+    'story',
+    'subtask'
+    );
+
+ALTER TABLE "issues"
+    ALTER COLUMN "issue_type"
+        SET DATA TYPE "IssueTypeType"
+        USING "issue_type"::"IssueTypeType";
+ALTER TABLE "issues"
+    ALTER COLUMN "issue_type"
+        SET DEFAULT 'task';
+
+ALTER TABLE issues
+    ADD COLUMN parent_id integer references issues (id) ON DELETE SET NULL;
+
+CREATE INDEX issues_parent_id_idx ON issues (parent_id);
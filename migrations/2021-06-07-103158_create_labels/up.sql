@@ -0,0 +1,18 @@
+CREATE TABLE labels (
+    id serial primary key not null,
+    name text not null,
+    color text not null,
+    project_id integer not null references projects (id),
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT labels_unique_name UNIQUE (project_id, name)
+);
+
+CREATE TABLE issue_labels (
+    id serial primary key not null,
+    issue_id integer not null references issues (id) ON DELETE CASCADE,
+    label_id integer not null references labels (id) ON DELETE CASCADE,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT issue_labels_unique UNIQUE (issue_id, label_id)
+);
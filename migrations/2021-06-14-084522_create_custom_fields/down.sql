@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS issue_custom_values;
+DROP TABLE IF EXISTS custom_fields;
+DROP TYPE IF EXISTS "CustomFieldTypeType" CASCADE;
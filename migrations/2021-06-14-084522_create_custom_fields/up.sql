@@ -0,0 +1,37 @@
+DROP TYPE IF EXISTS "CustomFieldTypeType" CASCADE;
+CREATE TYPE "CustomFieldTypeType" AS ENUM (
+    'text',
+    'number',
+    'date',
+    'single_select',
+    'multi_select',
+    'user'
+    );
+
+CREATE TABLE custom_fields (
+    id serial primary key not null,
+    name text not null,
+    field_type "CustomFieldTypeType" not null default 'text',
+    options text[] not null default '{}',
+    position integer not null default 0,
+    project_id integer not null references projects (id),
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT custom_fields_unique_name UNIQUE (project_id, name)
+);
+
+CREATE TABLE issue_custom_values (
+    id serial primary key not null,
+    issue_id integer not null references issues (id) ON DELETE CASCADE,
+    custom_field_id integer not null references custom_fields (id) ON DELETE CASCADE,
+    text_value text,
+    number_value double precision,
+    date_value timestamp,
+    option_values text[] not null default '{}',
+    user_id integer references users (id) ON DELETE SET NULL,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT issue_custom_values_unique UNIQUE (issue_id, custom_field_id)
+);
+
+CREATE INDEX issue_custom_values_issue_id_idx ON issue_custom_values (issue_id);
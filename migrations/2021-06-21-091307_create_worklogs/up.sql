@@ -0,0 +1,20 @@
+CREATE TABLE worklogs (
+    id serial primary key not null,
+    issue_id integer not null references issues (id) ON DELETE CASCADE,
+    user_id integer not null references users (id) ON DELETE CASCADE,
+    duration integer not null,
+    started_at timestamp not null default now(),
+    comment text,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now()
+);
+
+CREATE INDEX worklogs_issue_id ON worklogs (issue_id);
+
+-- keep already tracked time as single entry of reporter
+INSERT INTO worklogs (issue_id, user_id, duration, started_at)
+SELECT issues.id, issues.reporter_id, issues.time_spent, issues.updated_at
+FROM issues
+WHERE issues.time_spent IS NOT NULL
+  AND issues.time_spent > 0
+  AND NOT EXISTS (SELECT 1 FROM issues AS subtasks WHERE subtasks.parent_id = issues.id);
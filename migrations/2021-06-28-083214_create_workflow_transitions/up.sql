@@ -0,0 +1,15 @@
+CREATE TABLE workflow_transitions (
+    id serial primary key not null,
+    project_id integer not null references projects (id) ON DELETE CASCADE,
+    from_status_id integer not null references issue_statuses (id) ON DELETE CASCADE,
+    to_status_id integer not null references issue_statuses (id) ON DELETE CASCADE,
+    required_role "UserRoleType",
+    require_assignee boolean not null default false,
+    require_estimate boolean not null default false,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT workflow_transitions_not_self CHECK ( from_status_id <> to_status_id ),
+    CONSTRAINT workflow_transitions_unique UNIQUE (from_status_id, to_status_id)
+);
+
+CREATE INDEX workflow_transitions_project_id_idx ON workflow_transitions (project_id);
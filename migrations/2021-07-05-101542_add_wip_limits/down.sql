@@ -0,0 +1,3 @@
+ALTER TABLE issue_statuses
+    DROP COLUMN wip_strict,
+    DROP COLUMN wip_limit;
@@ -0,0 +1,3 @@
+ALTER TABLE issue_statuses
+    ADD COLUMN wip_limit integer CHECK ( wip_limit > 0 ),
+    ADD COLUMN wip_strict boolean NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+ALTER TABLE user_settings
+    DROP COLUMN board_grouping;
+DROP TYPE "BoardGroupingType";
@@ -0,0 +1,9 @@
+CREATE TYPE "BoardGroupingType" AS ENUM (
+    'epic',
+    'assignee',
+    'priority',
+    'issue_type'
+    );
+
+ALTER TABLE user_settings
+    ADD COLUMN board_grouping "BoardGroupingType" DEFAULT 'epic' NOT NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE tokens
+    DROP COLUMN access_expires_at,
+    DROP COLUMN refresh_expires_at;
@@ -0,0 +1,3 @@
+ALTER TABLE tokens
+    ADD COLUMN access_expires_at timestamp NOT NULL DEFAULT now() + interval '1 day',
+    ADD COLUMN refresh_expires_at timestamp NOT NULL DEFAULT now() + interval '30 days';
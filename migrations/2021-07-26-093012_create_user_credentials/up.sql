@@ -0,0 +1,13 @@
+-- totp_secret is kept as plain base32 because server computes codes from it.
+-- Encrypting it with key from server configuration was not done: anyone able
+-- to read this table can also read active access tokens, so database and its
+-- backups must be protected anyway.
+CREATE TABLE user_credentials (
+    id serial primary key not null,
+    user_id integer not null unique references users (id) ON DELETE CASCADE,
+    password_hash text,
+    totp_secret text,
+    totp_enabled boolean not null default false,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now()
+);
@@ -0,0 +1,5 @@
+ALTER TABLE messages
+    DROP COLUMN notified_at;
+ALTER TABLE user_settings
+    DROP COLUMN notification_mode;
+DROP TYPE "NotificationModeType";
@@ -0,0 +1,11 @@
+CREATE TYPE "NotificationModeType" AS ENUM (
+    'immediate',
+    'daily_digest',
+    'off'
+    );
+
+ALTER TABLE user_settings
+    ADD COLUMN notification_mode "NotificationModeType" DEFAULT 'immediate' NOT NULL;
+
+ALTER TABLE messages
+    ADD COLUMN notified_at timestamp;
@@ -0,0 +1,17 @@
+DELETE FROM messages WHERE message_type = 'watched_issue_updated';
+ALTER TABLE messages
+ALTER COLUMN message_type
+SET DATA TYPE text;
+DROP TYPE "MessageTypeType";
+CREATE TYPE "MessageTypeType" AS ENUM (
+    'received_invitation',
+    'assigned_to_issue',
+    'mention'
+);
+
+ALTER TABLE messages
+ALTER COLUMN message_type
+SET DATA TYPE "MessageTypeType"
+USING message_type::text::"MessageTypeType";
+
+DROP TABLE IF EXISTS issue_watchers;
@@ -0,0 +1,32 @@
+CREATE TABLE issue_watchers (
+    id serial primary key not null,
+    issue_id integer not null references issues (id) ON DELETE CASCADE,
+    user_id integer not null references users (id) ON DELETE CASCADE,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    CONSTRAINT issue_watchers_unique UNIQUE (issue_id, user_id)
+);
+
+INSERT INTO issue_watchers (issue_id, user_id)
+SELECT id, reporter_id FROM issues
+UNION
+SELECT issue_id, user_id FROM issue_assignees
+UNION
+SELECT issue_id, user_id FROM comments
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE messages
+ALTER COLUMN message_type
+SET DATA TYPE text;
+DROP TYPE "MessageTypeType";
+CREATE TYPE "MessageTypeType" AS ENUM (
+    'received_invitation',
+    'assigned_to_issue',
+    'mention',
+    'watched_issue_updated'
+);
+
+ALTER TABLE messages
+ALTER COLUMN message_type
+SET DATA TYPE "MessageTypeType"
+USING message_type::text::"MessageTypeType";
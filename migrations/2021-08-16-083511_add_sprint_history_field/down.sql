@@ -0,0 +1,23 @@
+DELETE FROM issue_history WHERE field = 'sprint';
+ALTER TABLE issue_history
+ALTER COLUMN field
+SET DATA TYPE text;
+DROP TYPE "IssueHistoryFieldType";
+CREATE TYPE "IssueHistoryFieldType" AS ENUM (
+    'title',
+    'type',
+    'status',
+    'priority',
+    'description',
+    'estimate',
+    'time_spent',
+    'time_remaining',
+    'assignees',
+    'reporter',
+    'epic'
+);
+
+ALTER TABLE issue_history
+ALTER COLUMN field
+SET DATA TYPE "IssueHistoryFieldType"
+USING field::text::"IssueHistoryFieldType";
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Configuration {
+    /// Seconds after which access token is no longer accepted
+    #[serde(default = "Configuration::default_access_token_lifetime")]
+    pub access_token_lifetime: i64,
+    /// Seconds after which access token can't be refreshed
+    #[serde(default = "Configuration::default_refresh_token_lifetime")]
+    pub refresh_token_lifetime: i64,
+    /// Seconds between removing expired tokens, `0` disables removing
+    #[serde(default = "Configuration::default_cleanup_interval")]
+    pub cleanup_interval: u64,
+    /// Allow sign in with single use token sent by e-mail
+    #[serde(default = "Configuration::default_email_token")]
+    pub email_token: bool,
+    /// Allow sign in with argon2 hashed password
+    #[serde(default)]
+    pub password: bool,
+    /// Allow users to require time based one time code after password
+    #[serde(default)]
+    pub totp: bool,
+    /// Name displayed in authenticator application
+    #[serde(default = "Configuration::default_totp_issuer")]
+    pub totp_issuer: String,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            access_token_lifetime: Self::default_access_token_lifetime(),
+            refresh_token_lifetime: Self::default_refresh_token_lifetime(),
+            cleanup_interval: Self::default_cleanup_interval(),
+            email_token: Self::default_email_token(),
+            password: false,
+            totp: false,
+            totp_issuer: Self::default_totp_issuer(),
+        }
+    }
+}
+
+impl Configuration {
+    crate::rw!("auth.toml");
+
+    fn default_access_token_lifetime() -> i64 {
+        24 * 60 * 60
+    }
+
+    fn default_refresh_token_lifetime() -> i64 {
+        30 * 24 * 60 * 60
+    }
+
+    fn default_cleanup_interval() -> u64 {
+        60 * 60
+    }
+
+    fn default_email_token() -> bool {
+        true
+    }
+
+    fn default_totp_issuer() -> String {
+        "JIRS".to_string()
+    }
+}
+crate::read!(Configuration);
@@ -1,6 +1,9 @@
 #[cfg(feature = "aws-s3")]
 pub mod amazon;
 
+#[cfg(feature = "auth")]
+pub mod auth;
+
 #[cfg(feature = "database")]
 pub mod database;
 
@@ -13,6 +16,9 @@ pub mod hi;
 #[cfg(feature = "mail")]
 pub mod mail;
 
+#[cfg(feature = "rate-limit")]
+pub mod rate_limit;
+
 #[cfg(feature = "web")]
 pub mod web;
 
@@ -1,3 +1,21 @@
+/// How e-mails are delivered
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    /// Send using SMTP server
+    Smtp,
+    /// Write `.eml` files to `spool_dir`
+    File,
+    /// Keep e-mails in process memory
+    Memory,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Transport::Smtp
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Configuration {
     pub concurrency: usize,
@@ -5,6 +23,15 @@ pub struct Configuration {
     pub pass: String,
     pub host: String,
     pub from: String,
+    /// Seconds between sending collected notifications to users who chose
+    /// daily digest
+    #[serde(default = "Configuration::default_digest_interval")]
+    pub digest_interval: u64,
+    #[serde(default)]
+    pub transport: Transport,
+    /// Directory where `file` transport writes e-mails
+    #[serde(default = "Configuration::default_spool_dir")]
+    pub spool_dir: String,
 }
 
 impl Default for Configuration {
@@ -15,11 +42,22 @@ impl Default for Configuration {
             pass: "YOUR-TOKEN".to_string(),
             host: "smtp.sendgrid.net".to_string(),
             from: "contact@jirs.pl".to_string(),
+            digest_interval: Self::default_digest_interval(),
+            transport: Transport::default(),
+            spool_dir: Self::default_spool_dir(),
         }
     }
 }
 
 impl Configuration {
     crate::rw!("mail.toml");
+
+    fn default_digest_interval() -> u64 {
+        24 * 60 * 60
+    }
+
+    fn default_spool_dir() -> String {
+        "./tmp/mails".to_string()
+    }
 }
 crate::read!(Configuration);
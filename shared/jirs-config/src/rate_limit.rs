@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Configuration {
+    /// Disable to accept any number of attempts
+    #[serde(default = "Configuration::default_enabled")]
+    pub enabled: bool,
+    /// Seconds in which attempts are counted
+    #[serde(default = "Configuration::default_window")]
+    pub window: u64,
+    /// Attempts for single e-mail address in window
+    #[serde(default = "Configuration::default_email_attempts")]
+    pub email_attempts: usize,
+    /// Attempts from single remote address in window
+    #[serde(default = "Configuration::default_remote_attempts")]
+    pub remote_attempts: usize,
+    /// Header with client address appended by trusted reverse proxy, for
+    /// example `X-Forwarded-For`. Last value of header is used.
+    ///
+    /// Without it connection peer address is used, which behind proxy is
+    /// address of proxy and all clients share single remote limit.
+    /// Never set it when server is reachable without proxy because
+    /// clients can send any value.
+    #[serde(default)]
+    pub remote_header: Option<String>,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            window: Self::default_window(),
+            email_attempts: Self::default_email_attempts(),
+            remote_attempts: Self::default_remote_attempts(),
+            remote_header: None,
+        }
+    }
+}
+
+impl Configuration {
+    crate::rw!("rate_limit.toml");
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn default_window() -> u64 {
+        15 * 60
+    }
+
+    fn default_email_attempts() -> usize {
+        5
+    }
+
+    fn default_remote_attempts() -> usize {
+        50
+    }
+}
+crate::read!(Configuration);
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{CustomFieldId, IssueStatusId};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
 pub enum ProjectFieldId {
     Name,
@@ -9,6 +11,15 @@ pub enum ProjectFieldId {
     Category,
     TimeTracking,
     IssueStatusName,
+    LabelName,
+    LabelColor,
+    CustomFieldName,
+    CustomFieldType,
+    CustomFieldOptions,
+    TransitionFrom,
+    TransitionTo,
+    TransitionRole,
+    WipLimit(IssueStatusId),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
@@ -16,12 +27,15 @@ pub enum SignInFieldId {
     Username,
     Email,
     Token,
+    Password,
+    Totp,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
 pub enum SignUpFieldId {
     Username,
     Email,
+    Password,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
@@ -32,6 +46,10 @@ pub enum UsersFieldId {
     Avatar,
     CurrentProject,
     TextEditorMode,
+    NotificationMode,
+    Password,
+    CurrentPassword,
+    TotpCode,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
@@ -60,6 +78,9 @@ pub enum IssueFieldId {
     EpicName,
     EpicStartsAt,
     EpicEndsAt,
+    Sprint,
+    Labels,
+    CustomField(CustomFieldId),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Hash)]
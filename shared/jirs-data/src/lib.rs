@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 use std::str::FromStr;
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use derive_enum_iter::EnumIter;
 use derive_enum_primitive::EnumPrimitive;
 #[cfg(feature = "backend")]
@@ -17,6 +17,7 @@ use uuid::Uuid;
 pub mod fields;
 pub mod msg;
 mod payloads;
+pub mod search;
 
 pub type NumberOfDeleted = usize;
 pub type IssueId = i32;
@@ -35,6 +36,15 @@ pub type Position = i32;
 pub type MessageId = i32;
 pub type EpicId = i32;
 pub type EpicName = String;
+pub type IssueHistoryId = i32;
+pub type SprintId = i32;
+pub type IssueLinkId = i32;
+pub type LabelId = i32;
+pub type IssueLabelId = i32;
+pub type CustomFieldId = i32;
+pub type IssueCustomValueId = i32;
+pub type WorklogId = i32;
+pub type WorkflowTransitionId = i32;
 
 pub type EmailString = String;
 pub type UsernameString = String;
@@ -61,6 +71,7 @@ pub enum IssueType {
     Task,
     Bug,
     Story,
+    Subtask,
 }
 
 impl Default for IssueType {
@@ -249,8 +260,11 @@ pub struct Issue {
     pub updated_at: NaiveDateTime,
     pub issue_status_id: IssueStatusId,
     pub epic_id: Option<EpicId>,
+    pub sprint_id: Option<SprintId>,
+    pub parent_id: Option<IssueId>,
 
     pub user_ids: Vec<i32>,
+    pub label_ids: Vec<LabelId>,
 }
 
 #[cfg_attr(feature = "backend", derive(Queryable))]
@@ -262,6 +276,15 @@ pub struct IssueStatus {
     pub project_id: ProjectId,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub wip_limit: Option<i32>,
+    pub wip_strict: bool,
+}
+
+impl IssueStatus {
+    /// Given number of issues in status is above work in progress limit
+    pub fn is_over_wip_limit(&self, issues: usize) -> bool {
+        matches!(self.wip_limit, Some(limit) if issues > limit as usize)
+    }
 }
 
 #[cfg_attr(feature = "backend", derive(Queryable))]
@@ -330,6 +353,44 @@ pub struct Token {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub bind_token: Option<Uuid>,
+    pub access_expires_at: NaiveDateTime,
+    pub refresh_expires_at: NaiveDateTime,
+}
+
+/// Signed in device, secrets of token are never sent to client
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    pub id: TokenId,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    /// Session used by receiving connection
+    pub current: bool,
+}
+
+/// Sign in methods enabled on server
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuthMethods {
+    pub email_token: bool,
+    pub password: bool,
+    pub totp: bool,
+}
+
+impl Default for AuthMethods {
+    fn default() -> Self {
+        Self {
+            email_token: true,
+            password: false,
+            totp: false,
+        }
+    }
+}
+
+/// Which credentials user already configured, secrets are never sent to client
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CredentialsState {
+    pub password: bool,
+    pub totp: bool,
 }
 
 #[cfg_attr(feature = "backend", derive(Queryable))]
@@ -342,6 +403,75 @@ pub struct IssueAssignee {
     pub updated_at: NaiveDateTime,
 }
 
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "IssueHistoryFieldType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+pub enum IssueHistoryField {
+    Title,
+    Type,
+    Status,
+    Priority,
+    Description,
+    Estimate,
+    TimeSpent,
+    TimeRemaining,
+    Assignees,
+    Reporter,
+    Epic,
+    Sprint,
+}
+
+impl Default for IssueHistoryField {
+    fn default() -> Self {
+        IssueHistoryField::Title
+    }
+}
+
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IssueHistory {
+    pub id: IssueHistoryId,
+    pub issue_id: IssueId,
+    pub user_id: UserId,
+    pub field: IssueHistoryField,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "IssueLinkTypeType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+pub enum IssueLinkType {
+    Blocks,
+    Duplicates,
+    RelatesTo,
+}
+
+impl Default for IssueLinkType {
+    fn default() -> Self {
+        IssueLinkType::RelatesTo
+    }
+}
+
+/// Directed relation between two issues, `source_id` blocks or duplicates
+/// `target_id`. Inverse relation ("is blocked by") is read from target side.
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IssueLink {
+    pub id: IssueLinkId,
+    pub link_type: IssueLinkType,
+    pub source_id: IssueId,
+    pub target_id: IssueId,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
 #[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
 #[cfg_attr(feature = "backend", sql_type = "MessageTypeType")]
 #[derive(
@@ -351,6 +481,7 @@ pub enum MessageType {
     ReceivedInvitation,
     AssignedToIssue,
     Mention,
+    WatchedIssueUpdated,
 }
 
 impl Default for MessageType {
@@ -377,6 +508,8 @@ pub struct Message {
     pub hyper_link: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Set once message was sent to receiver e-mail
+    pub notified_at: Option<NaiveDateTime>,
 }
 
 #[cfg_attr(feature = "backend", derive(Queryable))]
@@ -394,6 +527,255 @@ pub struct Epic {
     pub description_html: Option<DescriptionString>,
 }
 
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "SprintStateType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+pub enum SprintState {
+    Future,
+    Active,
+    Completed,
+}
+
+impl Default for SprintState {
+    fn default() -> Self {
+        SprintState::Future
+    }
+}
+
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Sprint {
+    pub id: SprintId,
+    pub name: NameString,
+    pub goal: Option<DescriptionString>,
+    pub project_id: ProjectId,
+    pub state: SprintState,
+    pub starts_at: Option<StartsAt>,
+    pub ends_at: Option<EndsAt>,
+    pub completed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Label {
+    pub id: LabelId,
+    pub name: NameString,
+    /// Css color, for example `#2d9cdb`
+    pub color: String,
+    pub project_id: ProjectId,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IssueLabel {
+    pub id: IssueLabelId,
+    pub issue_id: IssueId,
+    pub label_id: LabelId,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "CustomFieldTypeType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Date,
+    SingleSelect,
+    MultiSelect,
+    User,
+}
+
+impl Default for CustomFieldType {
+    fn default() -> Self {
+        CustomFieldType::Text
+    }
+}
+
+impl CustomFieldType {
+    /// Select fields must define at least one option
+    pub fn requires_options(&self) -> bool {
+        matches!(
+            self,
+            CustomFieldType::SingleSelect | CustomFieldType::MultiSelect
+        )
+    }
+}
+
+/// Project level definition of additional issue field
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CustomField {
+    pub id: CustomFieldId,
+    pub name: NameString,
+    pub field_type: CustomFieldType,
+    /// Allowed values of single and multi select fields
+    pub options: Vec<String>,
+    pub position: Position,
+    pub project_id: ProjectId,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl CustomField {
+    /// Check if value type matches field type, select values are one of
+    /// options and user is one of project members
+    pub fn accepts(&self, value: &CustomValue, member_ids: &[UserId]) -> bool {
+        match (self.field_type, value) {
+            (CustomFieldType::Text, CustomValue::Text(_))
+            | (CustomFieldType::Number, CustomValue::Number(_))
+            | (CustomFieldType::Date, CustomValue::Date(_)) => true,
+            (CustomFieldType::User, CustomValue::User(user_id)) => member_ids.contains(user_id),
+            (CustomFieldType::SingleSelect, CustomValue::SingleSelect(option)) => {
+                self.options.contains(option)
+            }
+            (CustomFieldType::MultiSelect, CustomValue::MultiSelect(options)) => {
+                options.iter().all(|option| self.options.contains(option))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Typed value of custom field
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub enum CustomValue {
+    Text(String),
+    Number(f64),
+    Date(NaiveDateTime),
+    SingleSelect(String),
+    MultiSelect(Vec<String>),
+    User(UserId),
+}
+
+/// Value of custom field for single issue. Only column matching field type is
+/// set.
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IssueCustomValue {
+    pub id: IssueCustomValueId,
+    pub issue_id: IssueId,
+    pub custom_field_id: CustomFieldId,
+    pub text_value: Option<String>,
+    pub number_value: Option<f64>,
+    pub date_value: Option<NaiveDateTime>,
+    pub option_values: Vec<String>,
+    pub user_id: Option<UserId>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl IssueCustomValue {
+    pub fn value(&self, field_type: CustomFieldType) -> Option<CustomValue> {
+        match field_type {
+            CustomFieldType::Text => self.text_value.clone().map(CustomValue::Text),
+            CustomFieldType::Number => self.number_value.map(CustomValue::Number),
+            CustomFieldType::Date => self.date_value.map(CustomValue::Date),
+            CustomFieldType::SingleSelect => self
+                .option_values
+                .first()
+                .cloned()
+                .map(CustomValue::SingleSelect),
+            CustomFieldType::MultiSelect => {
+                Some(CustomValue::MultiSelect(self.option_values.clone()))
+            }
+            CustomFieldType::User => self.user_id.map(CustomValue::User),
+        }
+    }
+}
+
+/// Time logged by user on issue. Issue time spent is sum of its worklogs.
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Worklog {
+    pub id: WorklogId,
+    pub issue_id: IssueId,
+    pub user_id: UserId,
+    /// Logged time in the same unit as issue time spent
+    pub duration: i32,
+    pub started_at: NaiveDateTime,
+    pub comment: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Allowed move of issues between two statuses. Project without transitions
+/// allows any move.
+#[cfg_attr(feature = "backend", derive(Queryable))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowTransition {
+    pub id: WorkflowTransitionId,
+    pub project_id: ProjectId,
+    pub from_status_id: IssueStatusId,
+    pub to_status_id: IssueStatusId,
+    /// Lowest role which can move issue
+    pub required_role: Option<UserRole>,
+    pub require_assignee: bool,
+    pub require_estimate: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Check if issue with given assignees and estimate can be moved between
+/// statuses by user with given role.
+pub fn check_transition(
+    transitions: &[WorkflowTransition],
+    from: IssueStatusId,
+    to: IssueStatusId,
+    role: UserRole,
+    has_assignee: bool,
+    has_estimate: bool,
+) -> Result<(), msg::WsError> {
+    if from == to || transitions.is_empty() {
+        return Ok(());
+    }
+    let transition = transitions
+        .iter()
+        .find(|t| t.from_status_id == from && t.to_status_id == to)
+        .ok_or(msg::WsError::TransitionNotAllowed)?;
+    if matches!(transition.required_role, Some(required) if role < required) {
+        return Err(msg::WsError::TransitionRoleRequired);
+    }
+    if transition.require_assignee && !has_assignee {
+        return Err(msg::WsError::TransitionRequiresAssignee);
+    }
+    if transition.require_estimate && !has_estimate {
+        return Err(msg::WsError::TransitionRequiresEstimate);
+    }
+    Ok(())
+}
+
+/// Sum of estimates of issues which were not done at the end of the day
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BurndownPoint {
+    pub day: NaiveDate,
+    pub remaining: i32,
+}
+
+/// Sum of estimates of issues moved to last issue status within week
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct VelocityPoint {
+    /// First day (monday) of week
+    pub week: NaiveDate,
+    pub completed: i32,
+}
+
+/// Number of issues in each issue status at the end of the day
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CumulativeFlowPoint {
+    pub day: NaiveDate,
+    pub counts: Vec<(IssueStatusId, i32)>,
+}
+
 pub type FontStyle = u8;
 
 pub static BOLD: FontStyle = 1;
@@ -445,10 +827,191 @@ impl Default for TextEditorMode {
     }
 }
 
+/// Field by which project board is split into swimlanes
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "BoardGroupingType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+#[repr(C)]
+pub enum BoardGrouping {
+    Epic,
+    Assignee,
+    Priority,
+    IssueType,
+}
+
+impl Default for BoardGrouping {
+    fn default() -> Self {
+        BoardGrouping::Epic
+    }
+}
+
+/// How user is informed by e-mail about received messages
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression, EnumSql))]
+#[cfg_attr(feature = "backend", sql_type = "NotificationModeType")]
+#[derive(
+    Clone, Copy, Deserialize, Serialize, Debug, PartialOrd, PartialEq, Hash, EnumIter, EnumPrimitive,
+)]
+#[repr(C)]
+pub enum NotificationMode {
+    Immediate,
+    DailyDigest,
+    Off,
+}
+
+impl Default for NotificationMode {
+    fn default() -> Self {
+        NotificationMode::Immediate
+    }
+}
+
 #[cfg_attr(feature = "backend", derive(Queryable))]
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct UserSetting {
     pub id: UserSettingId,
     pub user_id: UserId,
     pub text_editor_mode: TextEditorMode,
+    pub board_grouping: BoardGrouping,
+    pub notification_mode: NotificationMode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_field(field_type: CustomFieldType) -> CustomField {
+        CustomField {
+            id: 1,
+            name: "field".to_string(),
+            field_type,
+            options: vec!["foo".to_string(), "bar".to_string()],
+            position: 0,
+            project_id: 1,
+            created_at: NaiveDateTime::from_timestamp(0, 0),
+            updated_at: NaiveDateTime::from_timestamp(0, 0),
+        }
+    }
+
+    #[test]
+    fn accept_value_of_field_type() {
+        let date = NaiveDateTime::from_timestamp(0, 0);
+        let text = custom_field(CustomFieldType::Text);
+        let number = custom_field(CustomFieldType::Number);
+        let date_field = custom_field(CustomFieldType::Date);
+
+        assert!(text.accepts(&CustomValue::Text("foo".to_string()), &[]));
+        assert!(!text.accepts(&CustomValue::Number(1.0), &[]));
+        assert!(number.accepts(&CustomValue::Number(1.5), &[]));
+        assert!(!number.accepts(&CustomValue::Text("1".to_string()), &[]));
+        assert!(date_field.accepts(&CustomValue::Date(date), &[]));
+        assert!(!date_field.accepts(&CustomValue::User(1), &[1]));
+    }
+
+    #[test]
+    fn accept_only_select_options() {
+        let single = custom_field(CustomFieldType::SingleSelect);
+        let multi = custom_field(CustomFieldType::MultiSelect);
+
+        assert!(single.accepts(&CustomValue::SingleSelect("foo".to_string()), &[]));
+        assert!(!single.accepts(&CustomValue::SingleSelect("baz".to_string()), &[]));
+        assert!(!single.accepts(&CustomValue::MultiSelect(vec!["foo".to_string()]), &[]));
+        assert!(multi.accepts(
+            &CustomValue::MultiSelect(vec!["foo".to_string(), "bar".to_string()]),
+            &[]
+        ));
+        assert!(multi.accepts(&CustomValue::MultiSelect(vec![]), &[]));
+        assert!(!multi.accepts(
+            &CustomValue::MultiSelect(vec!["foo".to_string(), "baz".to_string()]),
+            &[]
+        ));
+        assert!(!multi.accepts(&CustomValue::SingleSelect("foo".to_string()), &[]));
+    }
+
+    #[test]
+    fn accept_only_project_members() {
+        let user = custom_field(CustomFieldType::User);
+
+        assert!(user.accepts(&CustomValue::User(2), &[1, 2]));
+        assert!(!user.accepts(&CustomValue::User(3), &[1, 2]));
+        assert!(!user.accepts(&CustomValue::User(1), &[]));
+        assert!(!user.accepts(&CustomValue::Text("1".to_string()), &[1]));
+    }
+
+    fn transition(from: IssueStatusId, to: IssueStatusId) -> WorkflowTransition {
+        WorkflowTransition {
+            id: 1,
+            project_id: 1,
+            from_status_id: from,
+            to_status_id: to,
+            required_role: None,
+            require_assignee: false,
+            require_estimate: false,
+            created_at: NaiveDateTime::from_timestamp(0, 0),
+            updated_at: NaiveDateTime::from_timestamp(0, 0),
+        }
+    }
+
+    #[test]
+    fn allow_defined_transition() {
+        let transitions = vec![transition(1, 2), transition(2, 3)];
+
+        assert_eq!(
+            check_transition(&transitions, 1, 2, UserRole::User, false, false),
+            Ok(())
+        );
+        assert_eq!(
+            check_transition(&[], 1, 3, UserRole::User, false, false),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reject_undefined_transition() {
+        let transitions = vec![transition(1, 2), transition(2, 3)];
+
+        assert_eq!(
+            check_transition(&transitions, 1, 3, UserRole::Owner, true, true),
+            Err(msg::WsError::TransitionNotAllowed)
+        );
+        assert_eq!(
+            check_transition(&transitions, 2, 1, UserRole::Owner, true, true),
+            Err(msg::WsError::TransitionNotAllowed)
+        );
+    }
+
+    #[test]
+    fn allow_staying_in_same_status() {
+        let transitions = vec![transition(1, 2)];
+
+        assert_eq!(
+            check_transition(&transitions, 3, 3, UserRole::User, false, false),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_transition_requirements() {
+        let mut transitions = vec![transition(1, 2)];
+        transitions[0].required_role = Some(UserRole::Manager);
+        transitions[0].require_assignee = true;
+        transitions[0].require_estimate = true;
+
+        assert_eq!(
+            check_transition(&transitions, 1, 2, UserRole::User, true, true),
+            Err(msg::WsError::TransitionRoleRequired)
+        );
+        assert_eq!(
+            check_transition(&transitions, 1, 2, UserRole::Manager, false, true),
+            Err(msg::WsError::TransitionRequiresAssignee)
+        );
+        assert_eq!(
+            check_transition(&transitions, 1, 2, UserRole::Owner, true, false),
+            Err(msg::WsError::TransitionRequiresEstimate)
+        );
+        assert_eq!(
+            check_transition(&transitions, 1, 2, UserRole::Manager, true, true),
+            Ok(())
+        );
+    }
 }
@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::search::SearchQueryError;
 use crate::{
-    AvatarUrl, BindToken, Code, Comment, CommentId, CreateCommentPayload, CreateIssuePayload,
-    DescriptionString, EmailString, EndsAt, Epic, EpicId, HighlightedCode, Invitation,
-    InvitationId, InvitationToken, Issue, IssueFieldId, IssueId, IssueStatus, IssueStatusId,
-    IssueType, Lang, ListPosition, Message, MessageId, NameString, NumberOfDeleted, PayloadVariant,
-    Position, Project, StartsAt, TextEditorMode, TitleString, UpdateCommentPayload,
-    UpdateProjectPayload, User, UserId, UserProject, UserProjectId, UserRole, UserSetting,
-    UsernameString,
+    AuthMethods, AvatarUrl, BindToken, BoardGrouping, BurndownPoint, Code, Comment, CommentId,
+    CreateCommentPayload, CreateIssuePayload, CredentialsState, CumulativeFlowPoint, CustomField,
+    CustomFieldId, CustomFieldType, DescriptionString, EmailString, EndsAt, Epic, EpicId,
+    HighlightedCode, Invitation, InvitationId, InvitationToken, Issue, IssueCustomValue,
+    IssueFieldId, IssueHistory, IssueId, IssueLink, IssueLinkId, IssueLinkType, IssueStatus,
+    IssueStatusId, IssueType, Label, LabelId, Lang, ListPosition, Message, MessageId, NameString,
+    NotificationMode, NumberOfDeleted, PayloadVariant, Position, Project, Session, Sprint,
+    SprintId, StartsAt, TextEditorMode, TitleString, UpdateCommentPayload, UpdateProjectPayload,
+    User, UserId, UserProject, UserProjectId, UserRole, UserSetting, UsernameString, VelocityPoint,
+    WorkflowTransition, WorkflowTransitionId, Worklog, WorklogId,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -16,10 +20,15 @@ use crate::{
 pub enum WsError {
     InvalidLoginPair,
     InvalidSignInToken,
+    AuthMethodDisabled,
+    InvalidCredentials,
+    InvalidTotpCode,
+    PasswordTooShort,
 
     // Issue status
     NoIssueStatuses,
     FailedToFetchIssueStatuses,
+    InvalidIssueStatus,
 
     // tokens
     FailedToDisableBindToken,
@@ -53,12 +62,41 @@ pub enum WsError {
     FailedToUpdateEpic,
     UnableToDeleteEpic,
 
+    // sprints
+    InvalidSprint,
+    SprintAlreadyActive,
+    SprintNotActive,
+    InvalidIssueLink,
+    InvalidParentIssue,
+    InvalidLabel,
+    InvalidCustomField,
+    InvalidCustomValue,
+    InvalidWorklog,
+
+    // workflow
+    InvalidWorkflowTransition,
+    TransitionNotAllowed,
+    TransitionRoleRequired,
+    TransitionRequiresAssignee,
+    TransitionRequiresEstimate,
+    InvalidWipLimit,
+    WipLimitExceeded,
+
     // invitations
     FailedToLoadInvitations,
     InvalidInvitation,
     FailedToUpdateInvitation,
     UnableToDeleteInvitation,
     InvitationRevoked,
+
+    // permissions
+    Forbidden,
+
+    // rate limit
+    TooManyRequests,
+
+    // search
+    InvalidSearchQuery(SearchQueryError),
 }
 
 impl WsError {
@@ -66,10 +104,15 @@ impl WsError {
         match self {
             WsError::InvalidLoginPair => "E-Mail and Login does not match",
             WsError::InvalidSignInToken => "Given token is not valid",
+            WsError::AuthMethodDisabled => "This sign in method is disabled",
+            WsError::InvalidCredentials => "Invalid login, e-mail or password",
+            WsError::InvalidTotpCode => "Invalid authentication code",
+            WsError::PasswordTooShort => "Password must have at least 8 characters",
             WsError::NoIssueStatuses => {
                 "Failed to fetch first issue status. Are you sure there is any?"
             }
             WsError::FailedToFetchIssueStatuses => "Failed to load issue statuses",
+            WsError::InvalidIssueStatus => "Issue status does not belong to project",
             WsError::FailedToDisableBindToken => "Failed to disable one use token",
             WsError::BindTokenNotExists => "Used single use bind token does not exists in database",
             WsError::NoBindToken => "Current user does not have any active tokens",
@@ -107,6 +150,36 @@ impl WsError {
             }
             WsError::UnableToDeleteEpic => "Unable to delete epic",
 
+            // sprints
+            WsError::InvalidSprint => "There is something wrong with given sprint data",
+            WsError::SprintAlreadyActive => "Another sprint is already active",
+            WsError::SprintNotActive => "Only active sprint can be completed",
+            WsError::InvalidIssueLink => "Issue can't be linked with itself or other project issue",
+            WsError::InvalidParentIssue => "Sub-task can't be created for this issue",
+            WsError::InvalidLabel => "Label name must be unique and color must be valid",
+            WsError::InvalidCustomField => {
+                "Custom field name must be unique and select field must have options"
+            }
+            WsError::InvalidCustomValue => "Value does not match custom field",
+            WsError::InvalidWorklog => "Logged time must be positive and only author can change it",
+
+            // workflow
+            WsError::InvalidWorkflowTransition => {
+                "Transition must connect two different statuses of project"
+            }
+            WsError::TransitionNotAllowed => "Issue can't be moved to this status",
+            WsError::TransitionRoleRequired => {
+                "You don't have role required to move issue to this status"
+            }
+            WsError::TransitionRequiresAssignee => {
+                "Issue must be assigned before it's moved to this status"
+            }
+            WsError::TransitionRequiresEstimate => {
+                "Issue must be estimated before it's moved to this status"
+            }
+            WsError::InvalidWipLimit => "Work in progress limit must be positive",
+            WsError::WipLimitExceeded => "Status reached its work in progress limit",
+
             // invitations
             WsError::InvalidInvitation => "Given invitation contains problems",
             WsError::FailedToLoadInvitations => "Failed to load invitations. Please try later",
@@ -115,6 +188,15 @@ impl WsError {
             }
             WsError::UnableToDeleteInvitation => "Unable to delete invitation",
             WsError::InvitationRevoked => "This invitation is no longer valid",
+
+            // permissions
+            WsError::Forbidden => "You don't have permission to do this",
+
+            // rate limit
+            WsError::TooManyRequests => "Too many attempts, please try again later",
+
+            // search
+            WsError::InvalidSearchQuery(_) => "Search query is not valid",
         }
     }
 }
@@ -133,10 +215,37 @@ pub enum WsMsg {
     AuthenticateSuccess,
     BindTokenCheck(Uuid),
     BindTokenBad,
-    BindTokenOk(Uuid),
+    /// Access token and refresh token
+    BindTokenOk(Uuid, Uuid),
+    RefreshToken(Uuid),
+    /// New access token and refresh token
+    TokenRefreshed(Uuid, Uuid),
+    AuthMethodsLoad,
+    AuthMethodsLoaded(AuthMethods),
+    /// E-Mail, username, password and optional code from authenticator
+    PasswordSignInRequest(EmailString, UsernameString, String, Option<String>),
+    TotpRequired,
+
+    // credentials
+    CredentialsLoad,
+    CredentialsLoaded(CredentialsState),
+    /// New password, current password and code from authenticator, one of
+    /// them is required if user already has credentials
+    PasswordUpdate(String, Option<String>, Option<String>),
+    TotpSetupRequest,
+    /// Secret and link for authenticator application
+    TotpSetup(String, String),
+    TotpEnable(String),
+    TotpDisable(String),
+
+    // sessions
+    SessionsLoad,
+    SessionsLoaded(Vec<Session>),
+    SessionsRevokeAll,
 
     // Sign up
-    SignUpRequest(EmailString, UsernameString),
+    /// E-Mail, username and optional password
+    SignUpRequest(EmailString, UsernameString, Option<String>),
     SignUpSuccess,
     SignUpPairTaken,
 
@@ -159,7 +268,8 @@ pub enum WsMsg {
     InvitationRevokeSuccess(InvitationId),
     //
     InvitationAcceptRequest(InvitationToken),
-    InvitationAcceptSuccess(BindToken),
+    /// Access token and refresh token
+    InvitationAcceptSuccess(BindToken, Uuid),
     InvitationAcceptFailure(InvitationToken),
     //
     InvitationRejectRequest(InvitationToken),
@@ -186,13 +296,31 @@ pub enum WsMsg {
     IssueDeleted(IssueId, NumberOfDeleted),
     IssueCreate(CreateIssuePayload),
     IssueCreated(Issue),
-    IssueSyncListPosition(Vec<(IssueId, ListPosition, IssueStatusId, Option<IssueId>)>),
+    IssueSyncListPosition(Vec<(IssueId, ListPosition, IssueStatusId, Option<EpicId>)>),
+    IssueSearch(String),
+    IssueSearchResult(Vec<Issue>),
+    IssueHistoryLoad(IssueId),
+    IssueHistoryLoaded(IssueId, Vec<IssueHistory>),
+    IssueWatchersLoad(IssueId),
+    IssueWatchersLoaded(IssueId, Vec<UserId>),
+    /// Start or stop watching issue by current user
+    IssueWatch(IssueId, bool),
+
+    // issue links
+    IssueLinksLoad,
+    IssueLinksLoaded(Vec<IssueLink>),
+    /// Link type, source issue, target issue
+    IssueLinkCreate(IssueLinkType, IssueId, IssueId),
+    IssueLinkCreated(IssueLink),
+    IssueLinkDelete(IssueLinkId),
+    IssueLinkDeleted(IssueLinkId, NumberOfDeleted),
 
     // issue status
     IssueStatusesLoad,
     IssueStatusesLoaded(Vec<IssueStatus>),
     IssueStatusUpdate(IssueStatusId, TitleString, Position),
     IssueStatusUpdated(IssueStatus),
+    IssueStatusWipLimitUpdate(IssueStatusId, Option<i32>, bool),
     IssueStatusCreate(TitleString, Position),
     IssueStatusCreated(IssueStatus),
     IssueStatusDelete(IssueStatusId),
@@ -216,6 +344,8 @@ pub enum WsMsg {
     // user settings
     UserSettingUpdated(UserSetting),
     UserSettingSetEditorMode(TextEditorMode),
+    UserSettingSetBoardGrouping(BoardGrouping),
+    UserSettingSetNotificationMode(NotificationMode),
 
     // user projects
     UserProjectsLoad,
@@ -247,6 +377,73 @@ pub enum WsMsg {
     EpicDeleted(EpicId, NumberOfDeleted),
     EpicTransform(EpicId, IssueType),
 
+    // sprints
+    SprintsLoad,
+    SprintsLoaded(Vec<Sprint>),
+    SprintCreate(NameString, Option<DescriptionString>),
+    SprintCreated(Sprint),
+    SprintStart(SprintId, StartsAt, EndsAt),
+    /// Complete active sprint, unfinished issues are moved to given sprint or
+    /// to backlog
+    SprintComplete(SprintId, Option<SprintId>),
+    SprintUpdated(Sprint),
+
+    // labels
+    LabelsLoad,
+    LabelsLoaded(Vec<Label>),
+    /// Label name and css color
+    LabelCreate(NameString, String),
+    LabelCreated(Label),
+    LabelDelete(LabelId),
+    LabelDeleted(LabelId, NumberOfDeleted),
+
+    // custom fields
+    CustomFieldsLoad,
+    CustomFieldsLoaded(Vec<CustomField>),
+    /// Field name, type and select options
+    CustomFieldCreate(NameString, CustomFieldType, Vec<String>),
+    CustomFieldCreated(CustomField),
+    /// Field name and select options
+    CustomFieldUpdate(CustomFieldId, NameString, Vec<String>),
+    CustomFieldUpdated(CustomField),
+    CustomFieldDelete(CustomFieldId),
+    CustomFieldDeleted(CustomFieldId, NumberOfDeleted),
+    IssueCustomValuesLoad,
+    IssueCustomValuesLoaded(Vec<IssueCustomValue>),
+    /// All custom values of single issue
+    IssueCustomValuesUpdated(IssueId, Vec<IssueCustomValue>),
+
+    // worklogs
+    WorklogsLoad,
+    WorklogsLoaded(Vec<Worklog>),
+    /// Issue, duration, start time and optional comment
+    WorklogCreate(IssueId, i32, StartsAt, Option<String>),
+    WorklogCreated(Worklog),
+    /// Duration, start time and optional comment
+    WorklogUpdate(WorklogId, i32, StartsAt, Option<String>),
+    WorklogUpdated(Worklog),
+    WorklogDelete(WorklogId),
+    WorklogDeleted(WorklogId, NumberOfDeleted),
+
+    // reports
+    EpicBurndownLoad(EpicId),
+    EpicBurndownLoaded(EpicId, Vec<BurndownPoint>),
+    VelocityLoad,
+    VelocityLoaded(Vec<VelocityPoint>),
+    /// First and last day of range
+    CumulativeFlowLoad(StartsAt, EndsAt),
+    CumulativeFlowLoaded(Vec<CumulativeFlowPoint>),
+
+    // workflow
+    WorkflowTransitionsLoad,
+    WorkflowTransitionsLoaded(Vec<WorkflowTransition>),
+    /// From status, to status, required role, require assignee and require
+    /// estimate
+    WorkflowTransitionCreate(IssueStatusId, IssueStatusId, Option<UserRole>, bool, bool),
+    WorkflowTransitionCreated(WorkflowTransition),
+    WorkflowTransitionDelete(WorkflowTransitionId),
+    WorkflowTransitionDeleted(WorkflowTransitionId, NumberOfDeleted),
+
     // highlight
     HighlightCode(Lang, Code),
     HighlightedCode(HighlightedCode),
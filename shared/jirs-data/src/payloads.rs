@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    CommentId, EpicId, Issue, IssueId, IssuePriority, IssueStatusId, IssueType, ProjectCategory,
-    ProjectId, TimeTracking, UserId,
+    CommentId, CustomFieldId, CustomValue, EpicId, Issue, IssueId, IssuePriority, IssueStatusId,
+    IssueType, ProjectCategory, ProjectId, TimeTracking, UserId,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -33,6 +33,8 @@ pub struct CreateIssuePayload {
     pub reporter_id: UserId,
     pub issue_status_id: IssueStatusId,
     pub epic_id: Option<EpicId>,
+    pub parent_id: Option<IssueId>,
+    pub custom_values: Vec<(CustomFieldId, CustomValue)>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -51,6 +53,7 @@ pub enum PayloadVariant {
     VecI32(Vec<i32>),
     I32(i32),
     String(String),
+    CustomValue(Option<CustomValue>),
     IssueType(IssueType),
     IssuePriority(IssuePriority),
     ProjectCategory(ProjectCategory),
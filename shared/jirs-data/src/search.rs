@@ -0,0 +1,451 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{EpicName, IssuePriority, IssueStatusName, IssueType, UsernameString};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchField {
+    Text,
+    Assignee,
+    Reporter,
+    Priority,
+    Type,
+    Status,
+    Epic,
+}
+
+impl SearchField {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" => Some(SearchField::Text),
+            "assignee" => Some(SearchField::Assignee),
+            "reporter" => Some(SearchField::Reporter),
+            "priority" => Some(SearchField::Priority),
+            "type" => Some(SearchField::Type),
+            "status" => Some(SearchField::Status),
+            "epic" => Some(SearchField::Epic),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            SearchField::Text => "text",
+            SearchField::Assignee => "assignee",
+            SearchField::Reporter => "reporter",
+            SearchField::Priority => "priority",
+            SearchField::Type => "type",
+            SearchField::Status => "status",
+            SearchField::Epic => "epic",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchOperator {
+    Eq,
+    NotEq,
+    Gt,
+    GtEq,
+    Lt,
+    LtEq,
+    Contains,
+}
+
+impl SearchOperator {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            SearchOperator::Eq => "=",
+            SearchOperator::NotEq => "!=",
+            SearchOperator::Gt => ">",
+            SearchOperator::GtEq => ">=",
+            SearchOperator::Lt => "<",
+            SearchOperator::LtEq => "<=",
+            SearchOperator::Contains => "~",
+        }
+    }
+
+    /// Compare `left` with `right` using operator. `Contains` behaves as `Eq`
+    pub fn matches<T: PartialOrd>(&self, left: T, right: T) -> bool {
+        match self {
+            SearchOperator::Eq | SearchOperator::Contains => left == right,
+            SearchOperator::NotEq => left != right,
+            SearchOperator::Gt => left > right,
+            SearchOperator::GtEq => left >= right,
+            SearchOperator::Lt => left < right,
+            SearchOperator::LtEq => left <= right,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchUser {
+    Me,
+    Name(UsernameString),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchCondition {
+    /// Full text search over title, description and comments
+    Text(String),
+    Assignee(SearchOperator, SearchUser),
+    Reporter(SearchOperator, SearchUser),
+    Priority(SearchOperator, IssuePriority),
+    Type(SearchOperator, IssueType),
+    Status(SearchOperator, IssueStatusName),
+    Epic(SearchOperator, EpicName),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchQueryError {
+    UnsupportedOperator(SearchField, SearchOperator),
+    InvalidValue(SearchField, String),
+    MissingValue(SearchField),
+    UnterminatedString,
+    EmptyCondition,
+}
+
+impl std::fmt::Display for SearchQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchQueryError::UnsupportedOperator(field, op) => write!(
+                f,
+                "Operator \"{}\" can't be used with \"{}\"",
+                op.to_str(),
+                field.to_str()
+            ),
+            SearchQueryError::InvalidValue(field, value) => write!(
+                f,
+                "\"{}\" is not valid value for \"{}\"",
+                value,
+                field.to_str()
+            ),
+            SearchQueryError::MissingValue(field) => {
+                write!(f, "Missing value for \"{}\"", field.to_str())
+            }
+            SearchQueryError::UnterminatedString => f.write_str("Missing closing quote"),
+            SearchQueryError::EmptyCondition => f.write_str("Empty condition around AND"),
+        }
+    }
+}
+
+/// Parsed issue search query.
+///
+/// Query is a list of conditions joined with `AND`, for example:
+/// `assignee = me AND priority >= High AND status = "In Progress"`.
+/// Condition which doesn't start with known field followed by operator is
+/// treated as full text search.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueSearchQuery {
+    pub conditions: Vec<SearchCondition>,
+}
+
+impl IssueSearchQuery {
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+}
+
+impl std::str::FromStr for IssueSearchQuery {
+    type Err = SearchQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut conditions = vec![];
+        for clause in tokens.split(|token| *token == Token::And) {
+            match clause {
+                [] if tokens.is_empty() => {}
+                [] => return Err(SearchQueryError::EmptyCondition),
+                _ => conditions.push(parse_condition(clause)?),
+            }
+        }
+        Ok(Self { conditions })
+    }
+}
+
+/// Priorities which satisfy `op` compared to `priority`, higher priority is
+/// greater
+pub fn matching_priorities(op: SearchOperator, priority: IssuePriority) -> Vec<IssuePriority> {
+    priority
+        .into_iter()
+        .filter(|p| op.matches(priority_weight(*p), priority_weight(priority)))
+        .collect()
+}
+
+fn priority_weight(priority: IssuePriority) -> u8 {
+    match priority {
+        IssuePriority::Highest => 4,
+        IssuePriority::High => 3,
+        IssuePriority::Medium => 2,
+        IssuePriority::Low => 1,
+        IssuePriority::Lowest => 0,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    And,
+}
+
+impl Token {
+    fn text(&self) -> &str {
+        match self {
+            Token::Word(s) | Token::Quoted(s) => s.as_str(),
+            Token::And => "AND",
+        }
+    }
+}
+
+/// Split query into words, quoted strings and `AND`. Operators stay in words
+/// because they are meaningful only after field name.
+fn tokenize(s: &str) -> Result<Vec<Token>, SearchQueryError> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err(SearchQueryError::UnterminatedString),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            _ => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || *c == '"' {
+                        break;
+                    }
+                    word.push(*c);
+                    chars.next();
+                }
+                if word == "AND" || word == "and" {
+                    tokens.push(Token::And);
+                } else {
+                    tokens.push(Token::Word(word));
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Operator at the beginning of `s` and text after it
+fn split_operator(s: &str) -> Option<(SearchOperator, &str)> {
+    [
+        ("!=", SearchOperator::NotEq),
+        (">=", SearchOperator::GtEq),
+        ("<=", SearchOperator::LtEq),
+        ("=", SearchOperator::Eq),
+        ("~", SearchOperator::Contains),
+        (">", SearchOperator::Gt),
+        ("<", SearchOperator::Lt),
+    ]
+    .iter()
+    .find_map(|(prefix, op)| s.strip_prefix(prefix).map(|rest| (*op, rest)))
+}
+
+/// Field, operator and value of clause which starts with known field name
+/// followed by operator, with or without spaces between them
+fn split_clause(clause: &[Token]) -> Option<(SearchField, SearchOperator, String)> {
+    let first = match clause.first() {
+        Some(Token::Word(word)) => word.as_str(),
+        _ => return None,
+    };
+    let name_len = first
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or_else(|| first.len());
+    let field = SearchField::from_name(&first[..name_len])?;
+    let (rest, tail) = match &first[name_len..] {
+        "" => match clause.get(1) {
+            Some(Token::Word(word)) => (word.as_str(), &clause[2..]),
+            _ => return None,
+        },
+        rest => (rest, &clause[1..]),
+    };
+    let (op, head) = split_operator(rest)?;
+    let value = std::iter::once(head)
+        .chain(tail.iter().map(|token| token.text()))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Some((field, op, value))
+}
+
+fn parse_condition(clause: &[Token]) -> Result<SearchCondition, SearchQueryError> {
+    let (field, op, value) = match split_clause(clause) {
+        Some(parts) => parts,
+        None => return Ok(SearchCondition::Text(join_tokens(clause))),
+    };
+    if value.is_empty() {
+        return Err(SearchQueryError::MissingValue(field));
+    }
+
+    let eq_only = |op: SearchOperator| match op {
+        SearchOperator::Eq | SearchOperator::NotEq => Ok(op),
+        _ => Err(SearchQueryError::UnsupportedOperator(field, op)),
+    };
+    let condition = match field {
+        SearchField::Text => match op {
+            SearchOperator::Eq | SearchOperator::Contains => SearchCondition::Text(value),
+            _ => return Err(SearchQueryError::UnsupportedOperator(field, op)),
+        },
+        SearchField::Assignee => SearchCondition::Assignee(eq_only(op)?, parse_user(value)),
+        SearchField::Reporter => SearchCondition::Reporter(eq_only(op)?, parse_user(value)),
+        SearchField::Priority => match op {
+            SearchOperator::Contains => {
+                return Err(SearchQueryError::UnsupportedOperator(field, op))
+            }
+            _ => SearchCondition::Priority(op, parse_priority(field, value)?),
+        },
+        SearchField::Type => SearchCondition::Type(eq_only(op)?, parse_type(field, value)?),
+        SearchField::Status => SearchCondition::Status(eq_only(op)?, value),
+        SearchField::Epic => SearchCondition::Epic(eq_only(op)?, value),
+    };
+    Ok(condition)
+}
+
+fn join_tokens(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.text())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn parse_user(value: String) -> SearchUser {
+    if value.to_lowercase() == "me" {
+        SearchUser::Me
+    } else {
+        SearchUser::Name(value)
+    }
+}
+
+fn parse_priority(field: SearchField, value: String) -> Result<IssuePriority, SearchQueryError> {
+    IssuePriority::default()
+        .into_iter()
+        .find(|p| format!("{:?}", p).to_lowercase() == value.to_lowercase())
+        .ok_or(SearchQueryError::InvalidValue(field, value))
+}
+
+fn parse_type(field: SearchField, value: String) -> Result<IssueType, SearchQueryError> {
+    IssueType::default()
+        .into_iter()
+        .find(|t| format!("{:?}", t).to_lowercase() == value.to_lowercase())
+        .ok_or(SearchQueryError::InvalidValue(field, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<IssueSearchQuery, SearchQueryError> {
+        s.parse()
+    }
+
+    #[test]
+    fn parse_empty_query() {
+        assert_eq!(parse("  "), Ok(IssueSearchQuery::default()));
+    }
+
+    #[test]
+    fn parse_full_text() {
+        assert_eq!(
+            parse("login page crash"),
+            Ok(IssueSearchQuery {
+                conditions: vec![SearchCondition::Text("login page crash".to_string())]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_structured_query() {
+        assert_eq!(
+            parse(r#"assignee = me AND priority >= High and status = "In Progress""#),
+            Ok(IssueSearchQuery {
+                conditions: vec![
+                    SearchCondition::Assignee(SearchOperator::Eq, SearchUser::Me),
+                    SearchCondition::Priority(SearchOperator::GtEq, IssuePriority::High),
+                    SearchCondition::Status(SearchOperator::Eq, "In Progress".to_string()),
+                ]
+            })
+        );
+        assert_eq!(
+            parse("type!=bug AND crash"),
+            Ok(IssueSearchQuery {
+                conditions: vec![
+                    SearchCondition::Type(SearchOperator::NotEq, IssueType::Bug),
+                    SearchCondition::Text("crash".to_string()),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid_query() {
+        assert_eq!(
+            parse("status > Done"),
+            Err(SearchQueryError::UnsupportedOperator(
+                SearchField::Status,
+                SearchOperator::Gt
+            ))
+        );
+        assert_eq!(
+            parse("priority = Urgent"),
+            Err(SearchQueryError::InvalidValue(
+                SearchField::Priority,
+                "Urgent".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("status = \"Done"),
+            Err(SearchQueryError::UnterminatedString)
+        );
+        assert_eq!(
+            parse("assignee ="),
+            Err(SearchQueryError::MissingValue(SearchField::Assignee))
+        );
+        assert_eq!(parse("crash AND"), Err(SearchQueryError::EmptyCondition));
+    }
+
+    #[test]
+    fn parse_operators_in_text() {
+        let text = |s: &str| {
+            Ok(IssueSearchQuery {
+                conditions: vec![SearchCondition::Text(s.to_string())],
+            })
+        };
+        assert_eq!(parse("crash!"), text("crash!"));
+        assert_eq!(parse("a<b"), text("a<b"));
+        assert_eq!(parse("x=y"), text("x=y"));
+        assert_eq!(parse("owner = me"), text("owner = me"));
+        assert_eq!(parse("status report"), text("status report"));
+        assert_eq!(
+            parse("priority>=high AND a<b"),
+            Ok(IssueSearchQuery {
+                conditions: vec![
+                    SearchCondition::Priority(SearchOperator::GtEq, IssuePriority::High),
+                    SearchCondition::Text("a<b".to_string()),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn priorities_above_high() {
+        assert_eq!(
+            matching_priorities(SearchOperator::GtEq, IssuePriority::High),
+            vec![IssuePriority::Highest, IssuePriority::High]
+        );
+        assert_eq!(
+            matching_priorities(SearchOperator::Lt, IssuePriority::Low),
+            vec![IssuePriority::Lowest]
+        );
+    }
+}